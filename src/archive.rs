@@ -0,0 +1,107 @@
+use rkyv::{rancor, util::AlignedVec, Archive, Deserialize, Serialize};
+
+use crate::{IDTag, Lyrics, LyricsError, TimeTag};
+
+/// An ID tag of `LyricsRecord`.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+pub struct IDTagRecord {
+    pub label: String,
+    pub text:  String,
+}
+
+/// A timed line of `LyricsRecord`.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+pub struct TimedLineRecord {
+    /// The timestamp in milliseconds.
+    pub timestamp: i64,
+    pub text:      String,
+}
+
+/// A plain representation of `Lyrics` which can be archived with rkyv. The archived form, `ArchivedLyricsRecord`, can be accessed without re-parsing, like from a memory-mapped cache shared between processes.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+pub struct LyricsRecord {
+    pub metadata:    Vec<IDTagRecord>,
+    pub comments:    Vec<String>,
+    /// The timed lines in chronological order.
+    pub timed_lines: Vec<TimedLineRecord>,
+    pub lines:       Vec<String>,
+}
+
+impl From<&Lyrics> for LyricsRecord {
+    fn from(lyrics: &Lyrics) -> Self {
+        LyricsRecord {
+            metadata:    lyrics
+                .metadata
+                .iter()
+                .map(|id_tag| IDTagRecord {
+                    label: id_tag.get_label().to_string(),
+                    text:  id_tag.get_text().to_string(),
+                })
+                .collect(),
+            comments:    lyrics.comments.iter().map(|comment| comment.text().to_string()).collect(),
+            timed_lines: lyrics
+                .timed_lines
+                .iter()
+                .map(|(time_tag, line)| TimedLineRecord {
+                    timestamp: time_tag.get_timestamp(),
+                    text:      line.to_string(),
+                })
+                .collect(),
+            lines:       lyrics.lines.clone(),
+        }
+    }
+}
+
+impl ArchivedLyricsRecord {
+    /// Convert the archived record back to `Lyrics`. The tags and lines are validated like the `add_*` methods.
+    pub fn to_lyrics(&self) -> Result<Lyrics, LyricsError> {
+        let mut lyrics = Lyrics::new();
+
+        for id_tag in self.metadata.iter() {
+            lyrics
+                .metadata
+                .insert(IDTag::from_string(id_tag.label.as_str(), id_tag.text.as_str())?);
+        }
+
+        for comment in self.comments.iter() {
+            lyrics.add_comment(comment.as_str())?;
+        }
+
+        for timed_line in self.timed_lines.iter() {
+            lyrics.add_timed_line(
+                TimeTag::new(timed_line.timestamp.to_native()),
+                timed_line.text.as_str(),
+            )?;
+        }
+
+        for line in self.lines.iter() {
+            lyrics.add_line(line.as_str())?;
+        }
+
+        Ok(lyrics)
+    }
+}
+
+impl Lyrics {
+    /// Archive the lyrics with rkyv. Which time tags were written on the same line in the source is not kept.
+    #[inline]
+    pub fn to_archive(&self) -> AlignedVec {
+        rkyv::to_bytes::<rancor::Error>(&LyricsRecord::from(self)).unwrap()
+    }
+
+    /// Access archived lyrics without copying or re-parsing them. The bytes are validated first, and must be aligned to 16 bytes, like an `AlignedVec` or a memory map.
+    #[inline]
+    pub fn access_archive(bytes: &[u8]) -> Result<&ArchivedLyricsRecord, LyricsError> {
+        rkyv::access::<ArchivedLyricsRecord, rancor::Error>(bytes)
+            .map_err(|error| LyricsError::ParseError(error.to_string()))
+    }
+
+    /// Create a `Lyrics` instance from archived lyrics. The bytes do not need to be aligned.
+    pub fn from_archive(bytes: &[u8]) -> Result<Lyrics, LyricsError> {
+        let mut aligned = AlignedVec::<16>::with_capacity(bytes.len());
+
+        aligned.extend_from_slice(bytes);
+
+        Lyrics::access_archive(&aligned)?.to_lyrics()
+    }
+}
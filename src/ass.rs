@@ -0,0 +1,147 @@
+use std::fmt::{self, Write};
+
+use crate::{
+    enhanced::{word_end_time, word_segments},
+    unescape_brackets, Lyrics,
+};
+
+/// Options for exporting lyrics to Advanced SubStation Alpha.
+#[derive(Debug, Clone)]
+pub struct AssOptions {
+    /// How long the last timed line is shown in milliseconds, if the lyrics has no `[length:]` tag after it.
+    pub last_line_duration: i64,
+    /// The font of the default style.
+    pub font_name:          String,
+    /// The font size of the default style.
+    pub font_size:          u32,
+}
+
+impl Default for AssOptions {
+    #[inline]
+    fn default() -> Self {
+        AssOptions {
+            last_line_duration: 5000,
+            font_name:          String::from("Arial"),
+            font_size:          48,
+        }
+    }
+}
+
+impl Lyrics {
+    /// Export the timed lines to Advanced SubStation Alpha (ASS).
+    #[inline]
+    pub fn to_ass(&self) -> String {
+        self.to_ass_with_options(&AssOptions::default())
+    }
+
+    /// Export the timed lines to Advanced SubStation Alpha (ASS) with options. Every timed line becomes a dialogue event which ends when the next line begins. The word time tags of Enhanced LRC become `\k` karaoke tags. Lines with empty text are not written.
+    pub fn to_ass_with_options(&self, options: &AssOptions) -> String {
+        let mut s = String::new();
+
+        self.write_ass(&mut s, options).unwrap();
+
+        s
+    }
+
+    fn write_ass<W: Write>(&self, f: &mut W, options: &AssOptions) -> Result<(), fmt::Error> {
+        f.write_str("[Script Info]\n")?;
+
+        if let Some(title) = self.title() {
+            f.write_fmt(format_args!("Title: {}\n", title))?;
+        }
+
+        f.write_str("ScriptType: v4.00+\n\n")?;
+
+        f.write_str("[V4+ Styles]\n")?;
+        f.write_str(
+            "Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, \
+             BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, \
+             BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\n",
+        )?;
+        f.write_fmt(format_args!(
+            "Style: Default,{},{},&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,\
+             1,2,0,2,10,10,10,1\n\n",
+            options.font_name, options.font_size
+        ))?;
+
+        f.write_str("[Events]\n")?;
+        f.write_str(
+            "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n",
+        )?;
+
+        for (i, (time_tag, line)) in self.timed_lines.iter().enumerate() {
+            let segments = word_segments(line);
+
+            if segments.iter().all(|segment| segment.text.trim().is_empty()) {
+                continue;
+            }
+
+            let start = time_tag.get_timestamp();
+            let end = word_end_time(&segments, start)
+                .unwrap_or_else(|| self.line_end_time(i, options.last_line_duration));
+
+            f.write_str("Dialogue: 0,")?;
+            write_ass_time(f, start)?;
+            f.write_char(',')?;
+            write_ass_time(f, end)?;
+            f.write_str(",Default,,0,0,0,,")?;
+
+            let has_word_timings = segments.iter().any(|segment| segment.start.is_some());
+
+            for (j, segment) in segments.iter().enumerate() {
+                if segment.text.is_empty() {
+                    continue;
+                }
+
+                if has_word_timings {
+                    let segment_start = segment.start.unwrap_or(start);
+
+                    let segment_end =
+                        segments.get(j + 1).and_then(|segment| segment.start).unwrap_or(end);
+
+                    let duration = centiseconds(segment_end) - centiseconds(segment_start);
+
+                    f.write_fmt(format_args!("{{\\k{}}}", duration.max(0)))?;
+                }
+
+                write_ass_text(f, segment.text)?;
+            }
+
+            f.write_char('\n')?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Round milliseconds to centiseconds, the unit of ASS.
+#[inline]
+fn centiseconds(timestamp: i64) -> i64 {
+    (timestamp.max(0) + 5) / 10
+}
+
+/// Write a timestamp in the `h:mm:ss.cc` format. Negative timestamps are written as zero.
+fn write_ass_time<W: Write>(f: &mut W, timestamp: i64) -> Result<(), fmt::Error> {
+    let centiseconds = centiseconds(timestamp);
+
+    f.write_fmt(format_args!(
+        "{}:{:02}:{:02}.{:02}",
+        centiseconds / 360000,
+        centiseconds / 6000 % 60,
+        centiseconds / 100 % 60,
+        centiseconds % 100
+    ))
+}
+
+/// Write text with the escapes of brackets removed and the braces escaped, so that they are not read as override blocks.
+fn write_ass_text<W: Write>(f: &mut W, text: &str) -> Result<(), fmt::Error> {
+    for c in unescape_brackets(text).chars() {
+        match c {
+            '{' => f.write_str("\\{")?,
+            '}' => f.write_str("\\}")?,
+            _ => f.write_char(c)?,
+        }
+    }
+
+    Ok(())
+}
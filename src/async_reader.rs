@@ -0,0 +1,42 @@
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::{Lyrics, LyricsError, LyricsPushParser, ParseOptions, ParseWarning};
+
+impl Lyrics {
+    /// Create a `Lyrics` instance by reading from an asynchronous `reader`, parsing every line as soon as it arrives.
+    #[inline]
+    pub async fn from_async_reader<R: AsyncRead + Unpin>(reader: R) -> Result<Lyrics, LyricsError> {
+        Self::from_async_reader_with_options(reader, &ParseOptions::default()).await
+    }
+
+    /// Create a `Lyrics` instance with options by reading from an asynchronous `reader`, parsing every line as soon as it arrives.
+    #[inline]
+    pub async fn from_async_reader_with_options<R: AsyncRead + Unpin>(
+        reader: R,
+        options: &ParseOptions,
+    ) -> Result<Lyrics, LyricsError> {
+        Self::from_async_reader_with_warnings(reader, options).await.map(|(lyrics, _)| lyrics)
+    }
+
+    /// Create a `Lyrics` instance with options by reading from an asynchronous `reader`. The warnings reported while parsing are returned along with the lyrics.
+    pub async fn from_async_reader_with_warnings<R: AsyncRead + Unpin>(
+        mut reader: R,
+        options: &ParseOptions,
+    ) -> Result<(Lyrics, Vec<ParseWarning>), LyricsError> {
+        let mut parser = LyricsPushParser::with_options(options.clone());
+
+        let mut buffer = [0u8; 4096];
+
+        loop {
+            let len = reader.read(&mut buffer).await?;
+
+            if len == 0 {
+                break;
+            }
+
+            parser.feed(&buffer[..len])?;
+        }
+
+        parser.finish_with_warnings()
+    }
+}
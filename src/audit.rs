@@ -0,0 +1,141 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::Lyrics;
+
+/// The kind of a suspicious character found by auditing the text.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SuspiciousCharKind {
+    /// A control character other than the tab, like the C1 control U+009B CONTROL SEQUENCE INTRODUCER.
+    Control,
+    /// A character which overrides or isolates the direction of bidirectional text, like U+202E RIGHT-TO-LEFT OVERRIDE.
+    Bidi,
+    /// A zero-width character, like U+200B ZERO WIDTH SPACE and U+200D ZERO WIDTH JOINER.
+    ZeroWidth,
+    /// Another character which is normally invisible, like U+00AD SOFT HYPHEN and the Hangul fillers.
+    Invisible,
+}
+
+impl SuspiciousCharKind {
+    /// Classify `c`. `None` if it is not suspicious.
+    pub fn of(c: char) -> Option<SuspiciousCharKind> {
+        match c {
+            '\t' => None,
+            '\u{0}'..='\u{1F}' | '\u{7F}'..='\u{9F}' => Some(SuspiciousCharKind::Control),
+            '\u{61C}'
+            | '\u{200E}'
+            | '\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2066}'..='\u{2069}' => Some(SuspiciousCharKind::Bidi),
+            '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}' => {
+                Some(SuspiciousCharKind::ZeroWidth)
+            },
+            '\u{AD}'
+            | '\u{34F}'
+            | '\u{115F}'
+            | '\u{1160}'
+            | '\u{180E}'
+            | '\u{2061}'..='\u{2064}'
+            | '\u{3164}'
+            | '\u{FFA0}'
+            | '\u{E0000}'..='\u{E007F}' => Some(SuspiciousCharKind::Invisible),
+            _ => None,
+        }
+    }
+}
+
+/// Where the text with a suspicious character is.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TextLocation {
+    /// The index of a timed line.
+    TimedLine(usize),
+    /// The index of a line without time tags.
+    Line(usize),
+}
+
+/// A suspicious character found by auditing the text.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TextIssue {
+    pub location:  TextLocation,
+    /// The byte offset of the character in the text.
+    pub offset:    usize,
+    pub character: char,
+    pub kind:      SuspiciousCharKind,
+}
+
+impl Display for TextIssue {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        let (name, index) = match self.location {
+            TextLocation::TimedLine(index) => ("timed line", index),
+            TextLocation::Line(index) => ("line", index),
+        };
+
+        f.write_fmt(format_args!(
+            "The {} {} contains U+{:04X} ({:?}) at byte {}.",
+            name, index, self.character as u32, self.kind, self.offset
+        ))
+    }
+}
+
+fn audit(text: &str, location: TextLocation, issues: &mut Vec<TextIssue>) {
+    for (offset, character) in text.char_indices() {
+        if let Some(kind) = SuspiciousCharKind::of(character) {
+            issues.push(TextIssue {
+                location,
+                offset,
+                character,
+                kind,
+            });
+        }
+    }
+}
+
+#[inline]
+fn has_suspicious_chars(text: &str) -> bool {
+    text.chars().any(|c| SuspiciousCharKind::of(c).is_some())
+}
+
+#[inline]
+fn remove_suspicious_chars(text: &str) -> String {
+    text.chars().filter(|c| SuspiciousCharKind::of(*c).is_none()).collect()
+}
+
+impl Lyrics {
+    /// Find control characters, bidirectional overrides, zero-width characters and other invisible characters in the lines, which may be used to spoof the displayed text.
+    pub fn audit_text(&self) -> Vec<TextIssue> {
+        let mut issues = Vec::new();
+
+        for (i, (_, line)) in self.timed_lines.iter().enumerate() {
+            audit(line, TextLocation::TimedLine(i), &mut issues);
+        }
+
+        for (i, line) in self.lines.iter().enumerate() {
+            audit(line, TextLocation::Line(i), &mut issues);
+        }
+
+        issues
+    }
+
+    /// Remove the characters which `audit_text` reports from the lines. Return the number of changed lines.
+    pub fn sanitize_text(&mut self) -> usize {
+        let mut count = 0;
+
+        for (_, line) in self.timed_lines.iter_mut() {
+            if has_suspicious_chars(line) {
+                *line = remove_suspicious_chars(line).into();
+
+                count += 1;
+            }
+        }
+
+        for line in self.lines.iter_mut() {
+            if has_suspicious_chars(line) {
+                *line = remove_suspicious_chars(line);
+
+                count += 1;
+            }
+        }
+
+        count
+    }
+}
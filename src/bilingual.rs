@@ -0,0 +1,103 @@
+use crate::{LineText, Lyrics, TextMatchOptions, TimeTag};
+
+/// Lyrics with a translation whose timed lines share the time tags of the original lyrics.
+#[derive(Debug, Clone, Default)]
+pub struct BilingualLyrics {
+    pub original:    Lyrics,
+    pub translation: Lyrics,
+}
+
+impl BilingualLyrics {
+    #[inline]
+    pub fn new(original: Lyrics, translation: Lyrics) -> BilingualLyrics {
+        BilingualLyrics {
+            original,
+            translation,
+        }
+    }
+
+    /// Split interleaved lyrics, where the first line of each time tag is the original and the second one is the translation.
+    #[inline]
+    pub fn from_interleaved(lyrics: &Lyrics) -> BilingualLyrics {
+        Self::split_interleaved(lyrics, |_, _| true)
+    }
+
+    /// Split interleaved lyrics like `from_interleaved`, but the second line of a time tag is only paired as the translation if its text is different from the first line under `options`. Otherwise it is a repeated line and stays in the original lyrics.
+    #[inline]
+    pub fn pair_translations(lyrics: &Lyrics, options: &TextMatchOptions) -> BilingualLyrics {
+        Self::split_interleaved(lyrics, |original, translated| {
+            !options.texts_eq(original, translated)
+        })
+    }
+
+    fn split_interleaved<F: Fn(&str, &str) -> bool>(
+        lyrics: &Lyrics,
+        is_translation: F,
+    ) -> BilingualLyrics {
+        let mut original = Lyrics::new();
+        let mut translation = Lyrics::new();
+
+        original.metadata = lyrics.metadata.clone();
+        original.comments = lyrics.comments.clone();
+        original.lines = lyrics.lines.clone();
+
+        let mut previous: Option<&(TimeTag, LineText)> = None;
+
+        for timed_line in lyrics.timed_lines.iter() {
+            let (time_tag, line) = timed_line;
+
+            match previous {
+                Some((previous_time_tag, previous_line))
+                    if previous_time_tag == time_tag && is_translation(previous_line, line) =>
+                {
+                    unsafe { translation.add_timed_line_unchecked(*time_tag, line.clone()) };
+
+                    previous = None;
+                },
+                _ => {
+                    unsafe { original.add_timed_line_unchecked(*time_tag, line.clone()) };
+
+                    previous = Some(timed_line);
+                },
+            }
+        }
+
+        BilingualLyrics {
+            original,
+            translation,
+        }
+    }
+
+    /// Get the timed lines of the original lyrics paired with the translated lines which have the same time tags.
+    pub fn pairs(&self) -> Vec<(TimeTag, &str, Option<&str>)> {
+        let translated_lines = &self.translation.timed_lines;
+
+        self.original
+            .timed_lines
+            .iter()
+            .map(|(time_tag, line)| {
+                let index = translated_lines.partition_point(|(t, _)| t < time_tag);
+
+                let translated = translated_lines
+                    .get(index)
+                    .filter(|(t, _)| t == time_tag)
+                    .map(|(_, line)| line.as_ref());
+
+                (*time_tag, line.as_ref(), translated)
+            })
+            .collect()
+    }
+
+    /// Merge the translation into the original lyrics. Each translated line is put right after the original line with the same time tag, which many players render as stacked bilingual lyrics.
+    pub fn to_interleaved(&self) -> Lyrics {
+        let mut lyrics = self.original.clone();
+
+        for (time_tag, line) in self.translation.timed_lines.iter() {
+            unsafe {
+                lyrics.add_timed_line_unchecked(*time_tag, line.clone());
+            }
+        }
+
+        lyrics
+    }
+}
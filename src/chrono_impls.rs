@@ -0,0 +1,19 @@
+use chrono::Duration;
+
+use crate::Timestamp;
+
+impl From<Timestamp> for Duration {
+    /// The only timestamp out of the range of `chrono::Duration`, `i64::MIN` milliseconds, saturates.
+    #[inline]
+    fn from(t: Timestamp) -> Duration {
+        Duration::try_milliseconds(t.get_timestamp()).unwrap_or(Duration::MIN)
+    }
+}
+
+impl From<Duration> for Timestamp {
+    /// The part smaller than a millisecond is truncated.
+    #[inline]
+    fn from(duration: Duration) -> Timestamp {
+        Timestamp::new(duration.num_milliseconds())
+    }
+}
@@ -0,0 +1,188 @@
+//! A corpus of tricky real-world LRC samples, for checking how a set of parse options deals with them.
+
+use crate::{Lyrics, LyricsError, ParseOptions, TimeTag};
+
+/// A sample of the corpus.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Sample {
+    /// The name of the sample.
+    pub name:        &'static str,
+    /// What is tricky about the sample.
+    pub description: &'static str,
+    /// The content of the sample.
+    pub input:       &'static str,
+    /// The timed lines which the sample is expected to be parsed into, as timestamps in milliseconds and texts.
+    pub expected:    &'static [(i64, &'static str)],
+}
+
+macro_rules! sample {
+    ($name:literal, $description:literal, [$(($time:literal, $text:literal)),* $(,)?]) => {
+        Sample {
+            name:        $name,
+            description: $description,
+            input:       include_str!(concat!("corpus/", $name, ".lrc")),
+            expected:    &[$(($time, $text)),*],
+        }
+    };
+}
+
+static SAMPLES: [Sample; 12] = [
+    sample!("bom_crlf", "A UTF-8 byte order mark and Windows line breaks.", [
+        (1000, "One"),
+        (2000, "Two")
+    ]),
+    sample!("mixed_line_endings", "`\\r`, `\\n` and `\\r\\n` line breaks in the same file.", [
+        (1000, "One"),
+        (2000, "Two"),
+        (3000, "Three")
+    ]),
+    sample!("multiple_time_tags", "A line with more than one time tag, like a repeated chorus.", [
+        (1000, "Chorus"),
+        (2000, "Verse"),
+        (3000, "Chorus")
+    ]),
+    sample!("spaces_between_tags", "Spaces between the time tags and before the text.", [
+        (1000, "Chorus"),
+        (2000, "Verse"),
+        (3000, "Chorus")
+    ]),
+    sample!("no_fraction", "Time tags without hundredths of a second, like `[00:01]`.", [
+        (1000, "One"),
+        (2000, "Two")
+    ]),
+    sample!(
+        "millisecond_fraction",
+        "Time tags with milliseconds, like `[00:01.500]`. Needs the relaxed dialect.",
+        [(1500, "One"), (2250, "Two")]
+    ),
+    sample!(
+        "full_width_separators",
+        "Time tags with full-width separators, like `[00：01．50]`. Needs the relaxed dialect.",
+        [(1500, "One")]
+    ),
+    sample!(
+        "apostrophe_separators",
+        "Time tags with apostrophes as separators, like `[00'01\"50]`. Needs the relaxed dialect.",
+        [(1500, "One")]
+    ),
+    sample!("comment", "A comment line in the `[:]` format.", [(1000, "One")]),
+    sample!("empty_lines", "A timed line without text, which clears the screen.", [
+        (1000, "One"),
+        (2000, ""),
+        (3000, "Three")
+    ]),
+    sample!("unsorted", "Timed lines which are not in chronological order.", [
+        (1000, "One"),
+        (2000, "Two"),
+        (3000, "Three")
+    ]),
+    sample!(
+        "duplicate_time_tags",
+        "The same time tag twice on a line, which should not duplicate the line.",
+        [(1000, "One")]
+    ),
+];
+
+/// Get the samples of the corpus.
+#[inline]
+pub fn samples() -> &'static [Sample] {
+    &SAMPLES
+}
+
+/// The outcome of parsing a sample.
+#[derive(Debug)]
+pub enum Outcome {
+    /// The sample is parsed into the expected timed lines.
+    Passed,
+    /// The sample is parsed into other timed lines.
+    Mismatch(Vec<(TimeTag, String)>),
+    /// The sample cannot be parsed.
+    Error(LyricsError),
+}
+
+/// The result of a sample.
+#[derive(Debug)]
+pub struct SampleResult {
+    pub sample:  &'static Sample,
+    pub outcome: Outcome,
+}
+
+impl SampleResult {
+    #[inline]
+    pub fn is_passed(&self) -> bool {
+        matches!(self.outcome, Outcome::Passed)
+    }
+}
+
+/// The results of all samples.
+#[derive(Debug)]
+pub struct Report {
+    pub results: Vec<SampleResult>,
+}
+
+impl Report {
+    /// Whether all samples are passed.
+    #[inline]
+    pub fn is_all_passed(&self) -> bool {
+        self.results.iter().all(SampleResult::is_passed)
+    }
+
+    /// Get the results of the samples which are passed.
+    #[inline]
+    pub fn passed(&self) -> impl Iterator<Item = &SampleResult> {
+        self.results.iter().filter(|r| r.is_passed())
+    }
+
+    /// Get the results of the samples which are not passed.
+    #[inline]
+    pub fn failed(&self) -> impl Iterator<Item = &SampleResult> {
+        self.results.iter().filter(|r| !r.is_passed())
+    }
+
+    /// Get the result of the sample named `name`.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&SampleResult> {
+        self.results.iter().find(|r| r.sample.name == name)
+    }
+}
+
+/// Parse every sample of the corpus with `options` and check whether the expected timed lines are got.
+pub fn run(options: &ParseOptions) -> Report {
+    let results = SAMPLES
+        .iter()
+        .map(|sample| {
+            let outcome = match Lyrics::from_str_with_options(sample.input, options) {
+                Ok(lyrics) => {
+                    let actual: Vec<(TimeTag, String)> = lyrics
+                        .get_timed_lines()
+                        .iter()
+                        .map(|(time_tag, line)| (*time_tag, line.to_string()))
+                        .collect();
+
+                    let passed = actual.len() == sample.expected.len()
+                        && actual.iter().zip(sample.expected.iter()).all(
+                            |((time_tag, line), (time, text))| {
+                                time_tag.get_timestamp() == *time && line == text
+                            },
+                        );
+
+                    if passed {
+                        Outcome::Passed
+                    } else {
+                        Outcome::Mismatch(actual)
+                    }
+                },
+                Err(error) => Outcome::Error(error),
+            };
+
+            SampleResult {
+                sample,
+                outcome,
+            }
+        })
+        .collect();
+
+    Report {
+        results,
+    }
+}
@@ -0,0 +1,26 @@
+use super::{FormatCapabilities, FormatDescriptor};
+use crate::Lyrics;
+
+/// Advanced SubStation Alpha (ASS), which lyrics can only be exported to. Use `Lyrics::to_ass_with_options` for options.
+#[derive(Debug, Copy, Clone)]
+pub struct Ass;
+
+impl Ass {
+    /// The description of this format.
+    pub const DESCRIPTOR: FormatDescriptor = FormatDescriptor {
+        name:         "ASS",
+        extensions:   &["ass"],
+        capabilities: FormatCapabilities {
+            word_timing: true,
+            end_times:   true,
+            metadata:    true,
+            import:      false,
+        },
+    };
+
+    /// Serialize lyrics into this format.
+    #[inline]
+    pub fn serialize(lyrics: &Lyrics) -> String {
+        lyrics.to_ass()
+    }
+}
@@ -0,0 +1,29 @@
+use super::{FormatCapabilities, FormatDescriptor, LyricsFormat};
+use crate::{Lyrics, LyricsError};
+
+/// CSV with the `timestamp_ms` and `text` columns, which has no metadata.
+#[derive(Debug, Copy, Clone)]
+pub struct Csv;
+
+impl LyricsFormat for Csv {
+    const DESCRIPTOR: FormatDescriptor = FormatDescriptor {
+        name:         "CSV",
+        extensions:   &["csv"],
+        capabilities: FormatCapabilities {
+            word_timing: false,
+            end_times:   false,
+            metadata:    false,
+            import:      true,
+        },
+    };
+
+    #[inline]
+    fn parse(s: &str) -> Result<Lyrics, LyricsError> {
+        Lyrics::from_csv(s)
+    }
+
+    #[inline]
+    fn serialize(lyrics: &Lyrics) -> String {
+        lyrics.to_csv()
+    }
+}
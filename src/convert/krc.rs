@@ -0,0 +1,108 @@
+use std::fmt::Write;
+
+use miniz_oxide::{deflate::compress_to_vec_zlib, inflate::decompress_to_vec_zlib};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{
+    parse_word_timed, serialize_word_timed, FormatCapabilities, FormatDescriptor, LyricsFormat,
+    WordTimingSyntax,
+};
+use crate::{Lyrics, LyricsError};
+
+/// The magic number at the beginning of a KRC file.
+const KRC_MAGIC: &[u8] = b"krc1";
+
+/// The key which the compressed data of a KRC file is XORed with.
+const KRC_KEY: [u8; 16] = [64, 71, 97, 119, 94, 50, 116, 71, 81, 54, 49, 45, 206, 210, 110, 105];
+
+/// The timing before a word of KRC, like `<500,505,0>`. The offset is relative to the start of the line.
+static KRC_WORD_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<([0-9]+),([0-9]+),-?[0-9]+>").unwrap());
+
+/// The word timings of KRC, like `<500,505,0>Penda `.
+static KRC_SYNTAX: WordTimingSyntax = WordTimingSyntax {
+    word_re:            &KRC_WORD_RE,
+    after_word:         false,
+    relative:           true,
+    write_timing:       |s, offset, duration| {
+        s.write_fmt(format_args!("<{},{},0>", offset, duration)).unwrap();
+    },
+    parse_special_line: None,
+};
+
+/// The KRC format of Kugou Music, whose lines are like `[12000,3300]<0,500,0>Naku <500,505,0>Penda `. The word timings are converted from and to the word time tags of Enhanced LRC.
+///
+/// `parse` and `serialize` work on the decoded text. Use `parse_bytes` and `serialize_bytes` for the content of `.krc` files, which is compressed with zlib and XORed with a key.
+#[derive(Debug, Copy, Clone)]
+pub struct Krc;
+
+impl Krc {
+    /// Decode the content of a `.krc` file into text.
+    pub fn decode(bytes: &[u8]) -> Result<String, LyricsError> {
+        let data = bytes
+            .strip_prefix(KRC_MAGIC)
+            .ok_or(LyricsError::FormatError("The data is not in the KRC format."))?;
+
+        let data: Vec<u8> =
+            data.iter().zip(KRC_KEY.iter().cycle()).map(|(byte, key)| byte ^ key).collect();
+
+        let data = decompress_to_vec_zlib(&data)
+            .map_err(|_| LyricsError::FormatError("The KRC data cannot be decompressed."))?;
+
+        let s = String::from_utf8(data)
+            .map_err(|_| LyricsError::FormatError("The KRC data is not valid UTF-8."))?;
+
+        match s.strip_prefix('\u{feff}') {
+            Some(s) => Ok(s.to_string()),
+            None => Ok(s),
+        }
+    }
+
+    /// Encode text into the content of a `.krc` file.
+    pub fn encode(s: &str) -> Vec<u8> {
+        let data = compress_to_vec_zlib(s.as_bytes(), 6);
+
+        let mut bytes = Vec::with_capacity(KRC_MAGIC.len() + data.len());
+
+        bytes.extend_from_slice(KRC_MAGIC);
+        bytes.extend(data.iter().zip(KRC_KEY.iter().cycle()).map(|(byte, key)| byte ^ key));
+
+        bytes
+    }
+
+    /// Parse the content of a `.krc` file.
+    #[inline]
+    pub fn parse_bytes(bytes: &[u8]) -> Result<Lyrics, LyricsError> {
+        Self::parse(&Self::decode(bytes)?)
+    }
+
+    /// Serialize lyrics into the content of a `.krc` file.
+    #[inline]
+    pub fn serialize_bytes(lyrics: &Lyrics) -> Vec<u8> {
+        Self::encode(&Self::serialize(lyrics))
+    }
+}
+
+impl LyricsFormat for Krc {
+    const DESCRIPTOR: FormatDescriptor = FormatDescriptor {
+        name:         "KRC",
+        extensions:   &["krc"],
+        capabilities: FormatCapabilities {
+            word_timing: true,
+            end_times:   true,
+            metadata:    true,
+            import:      true,
+        },
+    };
+
+    #[inline]
+    fn parse(s: &str) -> Result<Lyrics, LyricsError> {
+        parse_word_timed(s, &KRC_SYNTAX)
+    }
+
+    #[inline]
+    fn serialize(lyrics: &Lyrics) -> String {
+        serialize_word_timed(lyrics, &KRC_SYNTAX)
+    }
+}
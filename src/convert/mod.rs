@@ -0,0 +1,386 @@
+//! Conversions between `Lyrics` and other lyrics file formats.
+
+#[cfg(feature = "ass")]
+mod ass;
+mod csv;
+#[cfg(feature = "krc")]
+mod krc;
+mod qrc;
+mod srt;
+#[cfg(feature = "ttml")]
+mod ttml;
+#[cfg(feature = "netease")]
+mod yrc;
+
+use std::fmt::Write;
+
+#[cfg(feature = "ass")]
+pub use ass::Ass;
+pub use csv::Csv;
+#[cfg(feature = "krc")]
+pub use krc::Krc;
+use once_cell::sync::Lazy;
+pub use qrc::Qrc;
+use regex::Regex;
+pub use srt::Srt;
+#[cfg(feature = "ttml")]
+pub use ttml::Ttml;
+#[cfg(feature = "netease")]
+pub(crate) use yrc::parse_credit_line;
+#[cfg(feature = "netease")]
+pub use yrc::Yrc;
+
+use crate::{
+    enhanced::{push_word_time_tag, word_end_time, word_segments},
+    escape_brackets, split_lines, unescape_brackets, Comment, IDTag, LineText, Lyrics, LyricsError,
+    TimeTag,
+};
+
+/// A line of the formats with word timings like QRC, whose start time and duration are at the beginning, like `[12000,3300]...`.
+static WORD_TIMED_LINE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\[([0-9]+),([0-9]+)\](.*)$").unwrap());
+
+/// How long the last timed line lasts if the lyrics has no `[length:]` tag after it, in milliseconds.
+const LAST_LINE_DURATION: i64 = 5000;
+
+/// Parses a line which is special to a format. Returns `false` if the line is not such a line.
+type SpecialLineParser = fn(&mut Lyrics, &str) -> Result<bool, LyricsError>;
+
+/// How the word timings are written in a format whose lines are like `[12000,3300]...`.
+pub(crate) struct WordTimingSyntax {
+    /// Matches a word timing, whose first two groups are the time and the duration of the word.
+    pub(crate) word_re:            &'static Lazy<Regex>,
+    /// Whether a word timing is after its word instead of before it.
+    pub(crate) after_word:         bool,
+    /// Whether the time of a word is relative to the start of its line.
+    pub(crate) relative:           bool,
+    /// Write a word timing with the time and the duration of the word.
+    pub(crate) write_timing:       fn(&mut String, i64, i64),
+    /// Parse a line which is special to the format, before it is parsed as a line with word timings.
+    pub(crate) parse_special_line: Option<SpecialLineParser>,
+}
+
+/// Parse a string in a format whose lines are like `[12000,3300]...`. The word timings are converted to the word time tags of Enhanced LRC, and other lines are parsed as LRC.
+pub(crate) fn parse_word_timed(s: &str, syntax: &WordTimingSyntax) -> Result<Lyrics, LyricsError> {
+    let mut lyrics = Lyrics::new();
+
+    for line in split_lines(s) {
+        let line = line.trim();
+
+        if let Some(parse_special_line) = syntax.parse_special_line {
+            if parse_special_line(&mut lyrics, line)? {
+                continue;
+            }
+        }
+
+        let c = match WORD_TIMED_LINE_RE.captures(line) {
+            Some(c) => c,
+            None => {
+                // ID tags and lines without timings are the same as LRC
+                lyrics.parse_line(line)?;
+
+                continue;
+            },
+        };
+
+        let start = c[1]
+            .parse::<i64>()
+            .map_err(|_| LyricsError::ParseError(format!("The line {:?} is invalid.", line)))?;
+
+        let content = c.get(3).unwrap().as_str();
+
+        let mut text = String::new();
+        let mut word_start = 0;
+        let mut end = None;
+
+        for word in syntax.word_re.captures_iter(content) {
+            let m = word.get(0).unwrap();
+
+            let timing = match (word[1].parse::<i64>(), word[2].parse::<i64>()) {
+                (Ok(time), Ok(duration)) => {
+                    let time = if syntax.relative { start.checked_add(time) } else { Some(time) };
+
+                    time.and_then(|time| Some((time, time.checked_add(duration)?)))
+                },
+                _ => None,
+            };
+
+            let (time, word_end) = timing.ok_or_else(|| {
+                LyricsError::ParseError(format!("The word timing {:?} is invalid.", m.as_str()))
+            })?;
+
+            let word_text = escape_brackets(&content[word_start..m.start()]);
+
+            if syntax.after_word {
+                push_word_time_tag(&mut text, time);
+                text.push_str(&word_text);
+            } else {
+                text.push_str(&word_text);
+                push_word_time_tag(&mut text, time);
+            }
+
+            word_start = m.end();
+            end = Some(word_end);
+        }
+
+        text.push_str(&escape_brackets(&content[word_start..]));
+
+        if let Some(end) = end {
+            push_word_time_tag(&mut text, end);
+        }
+
+        lyrics.add_timed_line(TimeTag::new(start), text)?;
+    }
+
+    Ok(lyrics)
+}
+
+/// Serialize lyrics into a format whose lines are like `[12000,3300]...`. Lines with empty text are not written.
+pub(crate) fn serialize_word_timed(lyrics: &Lyrics, syntax: &WordTimingSyntax) -> String {
+    let mut s = String::new();
+
+    for id_tag in lyrics.metadata.iter() {
+        s.write_fmt(format_args!("[{}:{}]\n", id_tag.get_label(), id_tag.get_text())).unwrap();
+    }
+
+    for (i, (time_tag, line)) in lyrics.timed_lines.iter().enumerate() {
+        let segments = word_segments(line);
+
+        if segments.iter().all(|segment| segment.text.trim().is_empty()) {
+            continue;
+        }
+
+        // relative times cannot be negative, so the line starts at zero at the earliest
+        let start = if syntax.relative {
+            time_tag.get_timestamp().max(0)
+        } else {
+            time_tag.get_timestamp()
+        };
+
+        let end = word_end_time(&segments, start)
+            .unwrap_or_else(|| lyrics.line_end_time(i, LAST_LINE_DURATION));
+
+        s.write_fmt(format_args!("[{},{}]", start.max(0), end.saturating_sub(start).max(0)))
+            .unwrap();
+
+        for (j, segment) in segments.iter().enumerate() {
+            if segment.text.is_empty() {
+                continue;
+            }
+
+            let mut word_start = segment.start.unwrap_or(start);
+
+            if syntax.relative {
+                word_start = word_start.max(start);
+            }
+
+            let word_end = segments.get(j + 1).and_then(|segment| segment.start).unwrap_or(end);
+
+            let time = if syntax.relative { word_start - start } else { word_start.max(0) };
+            let duration = word_end.saturating_sub(word_start).max(0);
+
+            if syntax.after_word {
+                s.push_str(&unescape_brackets(segment.text));
+                (syntax.write_timing)(&mut s, time, duration);
+            } else {
+                (syntax.write_timing)(&mut s, time, duration);
+                s.push_str(&unescape_brackets(segment.text));
+            }
+        }
+
+        s.push('\n');
+    }
+
+    s
+}
+
+/// What a lyrics file format can represent.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FormatCapabilities {
+    /// Whether the format has per-word timings.
+    pub word_timing: bool,
+    /// Whether the format has end times of lines.
+    pub end_times:   bool,
+    /// Whether the format has metadata like the title and the artist.
+    pub metadata:    bool,
+    /// Whether lyrics can be parsed from the format. Some formats can only be exported to.
+    pub import:      bool,
+}
+
+/// The description of a lyrics file format.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FormatDescriptor {
+    /// The name of the format.
+    pub name:         &'static str,
+    /// The file extensions of the format, without dots.
+    pub extensions:   &'static [&'static str],
+    pub capabilities: FormatCapabilities,
+}
+
+/// A lyrics file format which can be converted from and to `Lyrics`.
+pub trait LyricsFormat {
+    /// The description of this format.
+    const DESCRIPTOR: FormatDescriptor;
+
+    /// Parse a string in this format.
+    fn parse(s: &str) -> Result<Lyrics, LyricsError>;
+
+    /// Serialize lyrics into this format.
+    fn serialize(lyrics: &Lyrics) -> String;
+}
+
+/// The LRC format.
+#[derive(Debug, Copy, Clone)]
+pub struct Lrc;
+
+impl LyricsFormat for Lrc {
+    const DESCRIPTOR: FormatDescriptor = FormatDescriptor {
+        name:         "LRC",
+        extensions:   &["lrc"],
+        capabilities: FormatCapabilities {
+            word_timing: false,
+            end_times:   false,
+            metadata:    true,
+            import:      true,
+        },
+    };
+
+    #[inline]
+    fn parse(s: &str) -> Result<Lyrics, LyricsError> {
+        Lyrics::from_str(s)
+    }
+
+    #[inline]
+    fn serialize(lyrics: &Lyrics) -> String {
+        lyrics.to_string()
+    }
+}
+
+/// Get the descriptions of all lyrics file formats compiled into this crate.
+pub fn formats() -> Vec<FormatDescriptor> {
+    #[allow(unused_mut)]
+    let mut formats = vec![Lrc::DESCRIPTOR, Qrc::DESCRIPTOR, Csv::DESCRIPTOR, Srt::DESCRIPTOR];
+
+    #[cfg(feature = "krc")]
+    formats.push(Krc::DESCRIPTOR);
+
+    #[cfg(feature = "netease")]
+    formats.push(Yrc::DESCRIPTOR);
+
+    #[cfg(feature = "ttml")]
+    formats.push(Ttml::DESCRIPTOR);
+
+    #[cfg(feature = "ass")]
+    formats.push(Ass::DESCRIPTOR);
+
+    formats
+}
+
+/// What is lost after lyrics are converted to a format and back.
+#[derive(Debug, Clone, Default)]
+pub struct LossReport {
+    /// ID tags which are missing or whose text is changed.
+    pub dropped_metadata:    Vec<IDTag>,
+    /// Comments which are missing.
+    pub dropped_comments:    Vec<Comment>,
+    /// Timed lines which are missing.
+    pub dropped_timed_lines: Vec<(TimeTag, LineText)>,
+    /// Timed lines whose time tags are changed, as pairs of the original and the converted time tags.
+    pub changed_times:       Vec<(TimeTag, TimeTag)>,
+    /// Plain lines which are missing.
+    pub dropped_lines:       Vec<String>,
+}
+
+impl LossReport {
+    /// Whether nothing is lost.
+    #[inline]
+    pub fn is_lossless(&self) -> bool {
+        self.dropped_metadata.is_empty()
+            && self.dropped_comments.is_empty()
+            && self.dropped_timed_lines.is_empty()
+            && self.changed_times.is_empty()
+            && self.dropped_lines.is_empty()
+    }
+}
+
+/// Convert `input` to the format `F` and back, and report what is lost during the round trip.
+pub fn verify_roundtrip<F: LyricsFormat>(input: &Lyrics) -> Result<LossReport, LyricsError> {
+    let output = F::parse(&F::serialize(input))?;
+
+    let mut report = LossReport::default();
+
+    for id_tag in input.metadata.iter() {
+        let found = output.metadata.iter().any(|t| {
+            t.get_label().eq_ignore_ascii_case(id_tag.get_label())
+                && t.get_text() == id_tag.get_text()
+        });
+
+        if !found {
+            report.dropped_metadata.push(id_tag.clone());
+        }
+    }
+
+    let mut used = vec![false; output.get_comments().len()];
+
+    for comment in input.get_comments() {
+        let found = output
+            .get_comments()
+            .iter()
+            .enumerate()
+            .position(|(i, c)| !used[i] && c.text() == comment.text());
+
+        match found {
+            Some(i) => used[i] = true,
+            None => report.dropped_comments.push(comment.clone()),
+        }
+    }
+
+    let output_timed_lines = output.get_timed_lines();
+    let mut used = vec![false; output_timed_lines.len()];
+
+    for (time_tag, line) in input.get_timed_lines() {
+        let exact = output_timed_lines
+            .iter()
+            .enumerate()
+            .position(|(i, (t, l))| !used[i] && t == time_tag && l == line);
+
+        if let Some(i) = exact {
+            used[i] = true;
+            continue;
+        }
+
+        let nearest = output_timed_lines
+            .iter()
+            .enumerate()
+            .filter(|(i, (_, l))| !used[*i] && l == line)
+            .min_by_key(|(_, (t, _))| (t.get_timestamp() - time_tag.get_timestamp()).abs())
+            .map(|(i, _)| i);
+
+        match nearest {
+            Some(i) => {
+                used[i] = true;
+                report.changed_times.push((*time_tag, output_timed_lines[i].0));
+            },
+            None => report.dropped_timed_lines.push((*time_tag, line.clone())),
+        }
+    }
+
+    let output_lines: Vec<&str> =
+        output.get_lines().iter().map(|l| l.trim()).filter(|l| !l.is_empty()).collect();
+    let mut used = vec![false; output_lines.len()];
+
+    for line in input.get_lines() {
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        match output_lines.iter().enumerate().position(|(i, l)| !used[i] && *l == trimmed) {
+            Some(i) => used[i] = true,
+            None => report.dropped_lines.push(line.clone()),
+        }
+    }
+
+    Ok(report)
+}
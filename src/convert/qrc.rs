@@ -0,0 +1,51 @@
+use std::fmt::Write;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{
+    parse_word_timed, serialize_word_timed, FormatCapabilities, FormatDescriptor, LyricsFormat,
+    WordTimingSyntax,
+};
+use crate::{Lyrics, LyricsError};
+
+/// The timing after a word of QRC, like `(12000,500)`.
+static QRC_WORD_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\(([0-9]+),([0-9]+)\)").unwrap());
+
+/// The word timings of QRC, like `Naku (12000,500)`.
+static QRC_SYNTAX: WordTimingSyntax = WordTimingSyntax {
+    word_re:            &QRC_WORD_RE,
+    after_word:         true,
+    relative:           false,
+    write_timing:       |s, time, duration| {
+        s.write_fmt(format_args!("({},{})", time, duration)).unwrap();
+    },
+    parse_special_line: None,
+};
+
+/// The decrypted text form of the QRC format of QQ Music, whose lines are like `[12000,3300]Naku (12000,500)Penda (12500,500)`. The word timings are converted from and to the word time tags of Enhanced LRC.
+#[derive(Debug, Copy, Clone)]
+pub struct Qrc;
+
+impl LyricsFormat for Qrc {
+    const DESCRIPTOR: FormatDescriptor = FormatDescriptor {
+        name:         "QRC",
+        extensions:   &["qrc"],
+        capabilities: FormatCapabilities {
+            word_timing: true,
+            end_times:   true,
+            metadata:    true,
+            import:      true,
+        },
+    };
+
+    #[inline]
+    fn parse(s: &str) -> Result<Lyrics, LyricsError> {
+        parse_word_timed(s, &QRC_SYNTAX)
+    }
+
+    #[inline]
+    fn serialize(lyrics: &Lyrics) -> String {
+        serialize_word_timed(lyrics, &QRC_SYNTAX)
+    }
+}
@@ -0,0 +1,26 @@
+use super::{FormatCapabilities, FormatDescriptor};
+use crate::Lyrics;
+
+/// SubRip (SRT), which lyrics can only be exported to. Use `Lyrics::to_srt_with_options` for options.
+#[derive(Debug, Copy, Clone)]
+pub struct Srt;
+
+impl Srt {
+    /// The description of this format.
+    pub const DESCRIPTOR: FormatDescriptor = FormatDescriptor {
+        name:         "SRT",
+        extensions:   &["srt"],
+        capabilities: FormatCapabilities {
+            word_timing: false,
+            end_times:   true,
+            metadata:    false,
+            import:      false,
+        },
+    };
+
+    /// Serialize lyrics into this format.
+    #[inline]
+    pub fn serialize(lyrics: &Lyrics) -> String {
+        lyrics.to_srt()
+    }
+}
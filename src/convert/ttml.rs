@@ -0,0 +1,29 @@
+use super::{FormatCapabilities, FormatDescriptor, LyricsFormat};
+use crate::{Lyrics, LyricsError};
+
+/// Timed Text Markup Language (TTML), like the syllable-timed lyrics of Apple Music. The `<span>` elements with begin times are converted from and to the word time tags of Enhanced LRC.
+#[derive(Debug, Copy, Clone)]
+pub struct Ttml;
+
+impl LyricsFormat for Ttml {
+    const DESCRIPTOR: FormatDescriptor = FormatDescriptor {
+        name:         "TTML",
+        extensions:   &["ttml"],
+        capabilities: FormatCapabilities {
+            word_timing: true,
+            end_times:   true,
+            metadata:    true,
+            import:      true,
+        },
+    };
+
+    #[inline]
+    fn parse(s: &str) -> Result<Lyrics, LyricsError> {
+        Lyrics::from_ttml(s)
+    }
+
+    #[inline]
+    fn serialize(lyrics: &Lyrics) -> String {
+        lyrics.to_ttml()
+    }
+}
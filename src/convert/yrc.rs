@@ -0,0 +1,79 @@
+use std::fmt::Write;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+
+use super::{
+    parse_word_timed, serialize_word_timed, FormatCapabilities, FormatDescriptor, LyricsFormat,
+    WordTimingSyntax,
+};
+use crate::{escape_brackets, Lyrics, LyricsError, TimeTag};
+
+/// The timing before a word of YRC, like `(12500,505,0)`.
+static YRC_WORD_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\(([0-9]+),([0-9]+),-?[0-9]+\)").unwrap());
+
+/// The word timings of YRC, like `(12500,505,0)Penda `. Credit lines in JSON are parsed before them.
+static YRC_SYNTAX: WordTimingSyntax = WordTimingSyntax {
+    word_re:            &YRC_WORD_RE,
+    after_word:         false,
+    relative:           false,
+    write_timing:       |s, time, duration| {
+        s.write_fmt(format_args!("({},{},0)", time, duration)).unwrap();
+    },
+    parse_special_line: Some(parse_credit_line),
+};
+
+/// Parse a credit line of NetEase Cloud Music, like `{"t":0,"c":[{"tx":"Composer: "},{"tx":"Magic Len"}]}`, into a timed line. Returns `false` if the line is not a credit line.
+pub(crate) fn parse_credit_line(lyrics: &mut Lyrics, line: &str) -> Result<bool, LyricsError> {
+    if !line.starts_with('{') {
+        return Ok(false);
+    }
+
+    let value: Value = serde_json::from_str(line)
+        .map_err(|_| LyricsError::ParseError(format!("The credit line {:?} is invalid.", line)))?;
+
+    let time = value.get("t").and_then(Value::as_i64).unwrap_or(0);
+
+    let mut text = String::new();
+
+    if let Some(parts) = value.get("c").and_then(Value::as_array) {
+        for part in parts {
+            if let Some(tx) = part.get("tx").and_then(Value::as_str) {
+                text.push_str(tx);
+            }
+        }
+    }
+
+    lyrics.add_timed_line(TimeTag::new(time), escape_brackets(text.trim()))?;
+
+    Ok(true)
+}
+
+/// The YRC format of NetEase Cloud Music, whose lines are like `[12000,3300](12000,500,0)Naku (12500,505,0)Penda `. The word timings are converted from and to the word time tags of Enhanced LRC. Credit lines in JSON become timed lines.
+#[derive(Debug, Copy, Clone)]
+pub struct Yrc;
+
+impl LyricsFormat for Yrc {
+    const DESCRIPTOR: FormatDescriptor = FormatDescriptor {
+        name:         "YRC",
+        extensions:   &["yrc"],
+        capabilities: FormatCapabilities {
+            word_timing: true,
+            end_times:   true,
+            metadata:    true,
+            import:      true,
+        },
+    };
+
+    #[inline]
+    fn parse(s: &str) -> Result<Lyrics, LyricsError> {
+        parse_word_timed(s, &YRC_SYNTAX)
+    }
+
+    #[inline]
+    fn serialize(lyrics: &Lyrics) -> String {
+        serialize_word_timed(lyrics, &YRC_SYNTAX)
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{escape_brackets, unescape_brackets, Lyrics, LyricsError, TimeTag};
+
+/// The header row of CSV.
+const CSV_HEADER: &str = "timestamp_ms,text";
+
+/// Whether `text`, after its leading apostrophes, starts with a character which makes spreadsheets evaluate it as a formula, like `=1+1`.
+#[inline]
+fn needs_formula_guard(text: &str) -> bool {
+    text.trim_start_matches('\'').starts_with(['=', '+', '-', '@', '\t', '\r'])
+}
+
+/// Append a text field to `s`, quoted if it contains a comma, a quote or a line break. A text which spreadsheets would evaluate as a formula is also prefixed with `'` and quoted.
+fn push_csv_field(s: &mut String, field: &str) {
+    let guard = needs_formula_guard(field);
+
+    if guard || field.contains([',', '"', '\r', '\n']) {
+        s.push('"');
+
+        if guard {
+            s.push('\'');
+        }
+
+        s.push_str(&field.replace('"', "\"\""));
+        s.push('"');
+    } else {
+        s.push_str(field);
+    }
+}
+
+/// Split CSV into records of fields. Quoted fields may contain commas, escaped quotes (`""`) and line breaks.
+fn parse_csv_records(s: &str) -> Result<Vec<Vec<String>>, LyricsError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+
+    let mut chars = s.chars().peekable();
+
+    let mut quoted = false;
+    let mut empty = true;
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            } else {
+                field.push(c);
+            }
+
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => {
+                quoted = true;
+                empty = false;
+            },
+            ',' => {
+                record.push(std::mem::take(&mut field));
+                empty = false;
+            },
+            '\r' | '\n' => {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+
+                if !empty || !field.is_empty() {
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+
+                empty = true;
+            },
+            _ => {
+                field.push(c);
+                empty = false;
+            },
+        }
+    }
+
+    if quoted {
+        return Err(LyricsError::ParseError(String::from(
+            "A quoted field of the CSV is not closed.",
+        )));
+    }
+
+    if !empty || !field.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+impl Lyrics {
+    /// Export the timed lines to CSV with the `timestamp_ms` and `text` columns, in chronological order. The escapes of brackets are removed from the texts. Texts starting with `=`, `+`, `-`, `@`, a tab or a carriage return are prefixed with `'`, so that spreadsheets do not evaluate them as formulas.
+    pub fn to_csv(&self) -> String {
+        let mut s = String::from(CSV_HEADER);
+
+        s.push_str("\r\n");
+
+        for (time_tag, line) in self.timed_lines.iter() {
+            s.push_str(&time_tag.get_timestamp().to_string());
+            s.push(',');
+            push_csv_field(&mut s, &unescape_brackets(line));
+            s.push_str("\r\n");
+        }
+
+        s
+    }
+
+    /// Create a `Lyrics` instance from CSV with the `timestamp_ms` and `text` columns, like the output of `to_csv`. The header row is optional, the `'` added by `to_csv` before texts which look like formulas is removed, and brackets in the texts are escaped.
+    pub fn from_csv<S: AsRef<str>>(s: S) -> Result<Lyrics, LyricsError> {
+        let s = s.as_ref();
+        let s = s.strip_prefix('\u{feff}').unwrap_or(s);
+
+        let mut lyrics = Lyrics::new();
+
+        for (i, record) in parse_csv_records(s)?.into_iter().enumerate() {
+            if i == 0 && record.first().map(|field| field.trim()) == Some("timestamp_ms") {
+                continue;
+            }
+
+            let (timestamp, text) = match record.as_slice() {
+                [timestamp] => (timestamp, ""),
+                [timestamp, text] => (timestamp, text.as_str()),
+                _ => {
+                    return Err(LyricsError::ParseError(format!(
+                        "The record {} of the CSV does not have two fields.",
+                        i + 1
+                    )))
+                },
+            };
+
+            let timestamp = timestamp.trim().parse::<i64>().map_err(|_| {
+                LyricsError::ParseError(format!(
+                    "The timestamp {:?} in the record {} of the CSV is invalid.",
+                    timestamp,
+                    i + 1
+                ))
+            })?;
+
+            let text = match text.strip_prefix('\'') {
+                Some(unguarded) if needs_formula_guard(text) => unguarded,
+                _ => text,
+            };
+
+            lyrics.add_timed_line(TimeTag::new(timestamp), escape_brackets(text))?;
+        }
+
+        Ok(lyrics)
+    }
+}
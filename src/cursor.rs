@@ -0,0 +1,193 @@
+use crate::{Lyrics, LyricsError, TimeTag};
+
+/// What should be displayed from a specific moment.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CursorEvent<'a> {
+    /// Display a timed line.
+    Line {
+        /// The index of the timed line.
+        index:    usize,
+        time_tag: TimeTag,
+        text:     &'a str,
+    },
+    /// Display nothing.
+    Clear,
+}
+
+impl<'a> CursorEvent<'a> {
+    #[inline]
+    fn line(lyrics: &'a Lyrics, index: usize) -> CursorEvent<'a> {
+        let (time_tag, text) = &lyrics.timed_lines[index];
+
+        CursorEvent::Line {
+            index,
+            time_tag: *time_tag,
+            text,
+        }
+    }
+}
+
+/// A cursor which follows the playback position and reports when the displayed line changes.
+#[derive(Debug, Clone)]
+pub struct LyricsCursor<'a> {
+    lyrics:        &'a Lyrics,
+    clear_after:   Option<i64>,
+    current:       Option<CursorEvent<'a>>,
+    rate:          f32,
+    /// The real position and the position in the lyrics where the current rate took effect.
+    anchor:        (i64, i64),
+    last_position: Option<i64>,
+}
+
+impl<'a> LyricsCursor<'a> {
+    /// Create a cursor for the timed lines of `lyrics`.
+    #[inline]
+    pub fn new(lyrics: &'a Lyrics) -> LyricsCursor<'a> {
+        LyricsCursor {
+            lyrics,
+            clear_after: None,
+            current: None,
+            rate: 1.0,
+            anchor: (0, 0),
+            last_position: None,
+        }
+    }
+
+    /// Emit a `Clear` event when no new line starts within `silence` milliseconds after a line starts. `None` to never clear the screen between lines.
+    #[inline]
+    pub fn set_clear_after<N: Into<i64>>(&mut self, silence: Option<N>) {
+        self.clear_after = silence.map(|n| n.into());
+    }
+
+    /// Get the playback rate.
+    #[inline]
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    /// Set the playback rate, like `1.5` for playing 1.5 times as fast. The positions given to `update` are the elapsed real time, and the new rate takes effect from the last updated position.
+    pub fn set_rate(&mut self, rate: f32) -> Result<(), LyricsError> {
+        if !(rate.is_finite() && rate > 0.0) {
+            return Err(LyricsError::FormatError("The rate must be a positive finite number."));
+        }
+
+        if let Some(position) = self.last_position {
+            self.anchor = (position, self.lyrics_position(position));
+        }
+
+        self.rate = rate;
+
+        Ok(())
+    }
+
+    /// Convert the elapsed real time to the position in the lyrics.
+    #[inline]
+    fn lyrics_position(&self, position: i64) -> i64 {
+        let (real, lyrics) = self.anchor;
+
+        lyrics + ((position - real) as f64 * self.rate as f64).floor() as i64
+    }
+
+    /// Get what is being displayed. `None` if the cursor has never been updated.
+    #[inline]
+    pub fn current(&self) -> Option<CursorEvent<'a>> {
+        self.current
+    }
+
+    /// Move the cursor to `position` in milliseconds of real time, which is scaled by the playback rate. Return an event if what should be displayed changes.
+    pub fn update<N: Into<i64>>(&mut self, position: N) -> Option<CursorEvent<'a>> {
+        let real_position = position.into();
+
+        self.last_position = Some(real_position);
+
+        let position = self.lyrics_position(real_position);
+
+        let event = match self.lyrics.find_timed_line_index(position) {
+            Some(index) => {
+                let start = self.lyrics.timed_lines[index].0.get_timestamp();
+
+                // the silence is in real time so that lines stay for the same time at any rate
+                let silence = self
+                    .clear_after
+                    .map(|silence| (silence as f64 * self.rate as f64).round() as i64);
+
+                match silence {
+                    Some(silence) if position - start >= silence => CursorEvent::Clear,
+                    _ => CursorEvent::line(self.lyrics, index),
+                }
+            },
+            None => CursorEvent::Clear,
+        };
+
+        if self.current == Some(event) {
+            None
+        } else {
+            self.current = Some(event);
+
+            Some(event)
+        }
+    }
+}
+
+/// An iterator over the events of the whole timeline, in chronological order.
+#[derive(Debug, Clone)]
+pub struct CursorEvents<'a> {
+    lyrics:        &'a Lyrics,
+    clear_after:   Option<i64>,
+    index:         usize,
+    pending_clear: Option<TimeTag>,
+}
+
+impl<'a> Iterator for CursorEvents<'a> {
+    type Item = (TimeTag, CursorEvent<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(time_tag) = self.pending_clear.take() {
+            return Some((time_tag, CursorEvent::Clear));
+        }
+
+        let timed_lines = &self.lyrics.timed_lines;
+
+        if self.index >= timed_lines.len() {
+            return None;
+        }
+
+        let index = self.index;
+        let time_tag = timed_lines[index].0;
+
+        self.index += 1;
+
+        if let Some(silence) = self.clear_after {
+            let clear_time = time_tag.get_timestamp().saturating_add(silence);
+
+            let next_starts_before_clear = timed_lines
+                .get(self.index)
+                .map_or(false, |(next, _)| next.get_timestamp() <= clear_time);
+
+            if !next_starts_before_clear {
+                self.pending_clear = Some(TimeTag::new(clear_time));
+            }
+        }
+
+        Some((time_tag, CursorEvent::line(self.lyrics, index)))
+    }
+}
+
+impl Lyrics {
+    /// Create a cursor for the timed lines.
+    #[inline]
+    pub fn cursor(&self) -> LyricsCursor<'_> {
+        LyricsCursor::new(self)
+    }
+
+    /// Iterate over the events of the whole timeline. If `clear_after` is set, a `Clear` event is emitted when no new line starts within that many milliseconds after a line starts.
+    #[inline]
+    pub fn cursor_events<N: Into<i64>>(&self, clear_after: Option<N>) -> CursorEvents<'_> {
+        CursorEvents {
+            lyrics:        self,
+            clear_after:   clear_after.map(|n| n.into()),
+            index:         0,
+            pending_clear: None,
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use std::cmp::Ordering;
+
+use crate::{LineText, Lyrics, TextMatchOptions, TimeTag};
+
+/// A row of the alignment of the timed lines of two lyrics, for displaying them side by side.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AlignedLine<'a> {
+    /// A line whose text appears in both lyrics.
+    Matched {
+        /// The index of the timed line in the left lyrics.
+        left_index:  usize,
+        /// The index of the timed line in the right lyrics.
+        right_index: usize,
+        left_time:   TimeTag,
+        right_time:  TimeTag,
+        text:        &'a str,
+    },
+    /// A line which only appears in the left lyrics.
+    LeftOnly { index: usize, time_tag: TimeTag, text: &'a str },
+    /// A line which only appears in the right lyrics.
+    RightOnly { index: usize, time_tag: TimeTag, text: &'a str },
+}
+
+impl<'a> AlignedLine<'a> {
+    /// Get how many milliseconds the line in the right lyrics is later than the one in the left lyrics. `None` if the line does not appear in both lyrics.
+    #[inline]
+    pub fn time_delta(&self) -> Option<i64> {
+        match self {
+            AlignedLine::Matched {
+                left_time,
+                right_time,
+                ..
+            } => Some(left_time.duration_until(*right_time).get_timestamp()),
+            _ => None,
+        }
+    }
+}
+
+impl Lyrics {
+    /// Whether a timed line of this lyrics begins within `tolerance` milliseconds of `time_tag`.
+    fn has_time_near(&self, time_tag: TimeTag, tolerance: i64) -> bool {
+        let index = self
+            .timed_lines
+            .partition_point(|(t, _)| t.cmp_with_tolerance(time_tag, tolerance) == Ordering::Less);
+
+        self.timed_lines.get(index).map_or(false, |(t, _)| t.approx_eq(time_tag, tolerance))
+    }
+
+    /// Get the timed lines whose time tags also appear in `other`, within `tolerance` milliseconds.
+    pub fn intersect_times<N: Into<i64>>(
+        &self,
+        other: &Lyrics,
+        tolerance: N,
+    ) -> Vec<&(TimeTag, LineText)> {
+        let tolerance = tolerance.into();
+
+        self.timed_lines.iter().filter(|(t, _)| other.has_time_near(*t, tolerance)).collect()
+    }
+
+    /// Get the timed lines whose time tags do not appear in `other`, within `tolerance` milliseconds.
+    pub fn subtract_times<N: Into<i64>>(
+        &self,
+        other: &Lyrics,
+        tolerance: N,
+    ) -> Vec<&(TimeTag, LineText)> {
+        let tolerance = tolerance.into();
+
+        self.timed_lines.iter().filter(|(t, _)| !other.has_time_near(*t, tolerance)).collect()
+    }
+
+    /// Get how similar the timed lines of this lyrics and `other` are, from `0.0` for no lines in common to `1.0` for the same lines in the same order. The texts are compared under `options`, and the time tags are ignored. Two lyrics without timed lines are the same.
+    pub fn similarity(&self, other: &Lyrics, options: &TextMatchOptions) -> f64 {
+        let total = self.timed_lines.len() + other.timed_lines.len();
+
+        if total == 0 {
+            return 1.0;
+        }
+
+        let matched = self
+            .diff_alignment_with_options(other, options)
+            .iter()
+            .filter(|aligned| matches!(aligned, AlignedLine::Matched { .. }))
+            .count();
+
+        (matched * 2) as f64 / total as f64
+    }
+
+    /// Align the timed lines of this lyrics (left) and `other` (right) by the longest common subsequence of their texts.
+    #[inline]
+    pub fn diff_alignment<'a>(&'a self, other: &'a Lyrics) -> Vec<AlignedLine<'a>> {
+        self.diff_alignment_with_options(other, &TextMatchOptions::exact())
+    }
+
+    /// Align the timed lines of this lyrics (left) and `other` (right) by the longest common subsequence of their texts, which are compared under `options`. The text of a matched line is the one in the left lyrics.
+    pub fn diff_alignment_with_options<'a>(
+        &'a self,
+        other: &'a Lyrics,
+        options: &TextMatchOptions,
+    ) -> Vec<AlignedLine<'a>> {
+        let left = &self.timed_lines;
+        let right = &other.timed_lines;
+
+        let left_texts: Vec<_> = left.iter().map(|(_, line)| options.normalize(line)).collect();
+        let right_texts: Vec<_> = right.iter().map(|(_, line)| options.normalize(line)).collect();
+
+        let width = right.len() + 1;
+
+        // lengths[i * width + j] is the length of the LCS of `left[i..]` and `right[j..]`
+        let mut lengths = vec![0usize; (left.len() + 1) * width];
+
+        for i in (0..left.len()).rev() {
+            for j in (0..right.len()).rev() {
+                lengths[i * width + j] = if left_texts[i] == right_texts[j] {
+                    lengths[(i + 1) * width + j + 1] + 1
+                } else {
+                    lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+                };
+            }
+        }
+
+        let mut alignment = Vec::with_capacity(left.len().max(right.len()));
+
+        let left_only = |i: usize| AlignedLine::LeftOnly {
+            index:    i,
+            time_tag: left[i].0,
+            text:     &left[i].1,
+        };
+
+        let right_only = |j: usize| AlignedLine::RightOnly {
+            index:    j,
+            time_tag: right[j].0,
+            text:     &right[j].1,
+        };
+
+        let (mut i, mut j) = (0, 0);
+
+        while i < left.len() && j < right.len() {
+            if left_texts[i] == right_texts[j] {
+                alignment.push(AlignedLine::Matched {
+                    left_index:  i,
+                    right_index: j,
+                    left_time:   left[i].0,
+                    right_time:  right[j].0,
+                    text:        &left[i].1,
+                });
+
+                i += 1;
+                j += 1;
+            } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+                alignment.push(left_only(i));
+
+                i += 1;
+            } else {
+                alignment.push(right_only(j));
+
+                j += 1;
+            }
+        }
+
+        alignment.extend((i..left.len()).map(left_only));
+        alignment.extend((j..right.len()).map(right_only));
+
+        alignment
+    }
+}
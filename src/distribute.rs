@@ -0,0 +1,56 @@
+use crate::{escape_brackets, Lyrics, LyricsError, TimeTag, Timestamp};
+
+/// How to distribute the timestamps of lines across the duration of a track.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Educe)]
+#[educe(Default)]
+pub enum Distribution {
+    /// Every line gets the same time.
+    #[educe(Default)]
+    Even,
+    /// Every line gets time in proportion to the number of its characters.
+    WeightedByLength,
+}
+
+impl Lyrics {
+    /// Create a `Lyrics` instance from unsynchronized lyrics by distributing timestamps across `duration`, as a rough starting point for manual correction. Every non-empty line of `text` becomes a timed line, and the first one begins at zero. The timestamps are rounded down to hundredths of a second, and the `[length:]` tag is set to `duration`.
+    pub fn from_plain_text<S: AsRef<str>>(
+        text: S,
+        duration: Timestamp,
+        distribution: Distribution,
+    ) -> Result<Lyrics, LyricsError> {
+        let duration = duration.get_timestamp();
+
+        if duration < 0 {
+            return Err(LyricsError::FormatError("The duration is negative."));
+        }
+
+        let lines: Vec<&str> =
+            text.as_ref().lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+
+        let weights: Vec<i128> = lines
+            .iter()
+            .map(|line| match distribution {
+                Distribution::Even => 1,
+                Distribution::WeightedByLength => line.chars().count() as i128,
+            })
+            .collect();
+
+        let total: i128 = weights.iter().sum();
+
+        let mut lyrics = Lyrics::new();
+
+        let mut elapsed = 0;
+
+        for (line, weight) in lines.into_iter().zip(weights) {
+            let timestamp = (i128::from(duration) * elapsed / total) as i64 / 10 * 10;
+
+            lyrics.add_timed_line(TimeTag::new(timestamp), escape_brackets(line))?;
+
+            elapsed += weight;
+        }
+
+        lyrics.set_length(Timestamp::new(duration));
+
+        Ok(lyrics)
+    }
+}
@@ -0,0 +1,189 @@
+use std::{
+    fmt::{self, Display, Formatter, Write},
+    str::FromStr,
+};
+
+use crate::{
+    check_line, split_lines, strip_bom, Comment, IDTag, Lyrics, LyricsError, ParseOptions,
+    ParseWarning, ParseWarningKind, ParsedLine, TimeTag,
+};
+
+/// An item of lyrics in the order it appears in a document.
+#[derive(Debug, Clone)]
+pub enum LyricsItem {
+    /// An ID tag.
+    Metadata(IDTag),
+    /// A line with one or more time tags.
+    Timed(Vec<TimeTag>, String),
+    /// A line without time tags.
+    Plain(String),
+    /// A comment.
+    Comment(Comment),
+}
+
+impl Display for LyricsItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            LyricsItem::Metadata(id_tag) => Display::fmt(id_tag, f),
+            LyricsItem::Timed(time_tags, line) => {
+                for time_tag in time_tags {
+                    Display::fmt(time_tag, f)?;
+                }
+
+                f.write_str(line)
+            },
+            LyricsItem::Plain(line) => f.write_str(line),
+            LyricsItem::Comment(comment) => Display::fmt(comment, f),
+        }
+    }
+}
+
+/// Lyrics which keep the original order of all their items, unlike `Lyrics` which groups them by kind.
+#[derive(Debug, Clone, Default)]
+pub struct LyricsDocument {
+    pub items: Vec<LyricsItem>,
+}
+
+impl LyricsDocument {
+    /// Create a `LyricsDocument` instance with a string.
+    #[allow(clippy::should_implement_trait)]
+    #[inline]
+    pub fn from_str<S: AsRef<str>>(s: S) -> Result<LyricsDocument, LyricsError> {
+        Self::from_str_with_options(s, &ParseOptions::default())
+    }
+
+    /// Create a `LyricsDocument` instance with a string and options.
+    #[inline]
+    pub fn from_str_with_options<S: AsRef<str>>(
+        s: S,
+        options: &ParseOptions,
+    ) -> Result<LyricsDocument, LyricsError> {
+        Self::from_str_with_warnings(s, options).map(|(document, _)| document)
+    }
+
+    /// Create a `LyricsDocument` instance with a string and options. The warnings reported while parsing are returned along with the document.
+    pub fn from_str_with_warnings<S: AsRef<str>>(
+        s: S,
+        options: &ParseOptions,
+    ) -> Result<(LyricsDocument, Vec<ParseWarning>), LyricsError> {
+        let mut items = Vec::new();
+        let mut warnings = Vec::new();
+
+        let s = strip_bom(s.as_ref());
+
+        options.limits.check_bytes(s.len())?;
+
+        for (i, line) in split_lines(s).enumerate() {
+            options.limits.check_line(i + 1, line)?;
+
+            let parsed = ParsedLine::parse(line, Some(i + 1), options, &mut warnings)
+                .and_then(|parsed| check_line(parsed.text).map(|_| parsed));
+
+            let parsed = match parsed {
+                Ok(parsed) => parsed,
+                Err(_) if options.skip_invalid_lines => {
+                    warnings.push(ParseWarning {
+                        line_number: i + 1,
+                        kind:        ParseWarningKind::SkippedLine,
+                    });
+
+                    continue;
+                },
+                Err(error) => return Err(error),
+            };
+
+            let id_tags_is_empty = parsed.id_tags.is_empty();
+
+            items.extend(parsed.id_tags.into_iter().map(LyricsItem::Metadata));
+
+            if !parsed.time_tags.is_empty() {
+                items.push(LyricsItem::Timed(parsed.time_tags, parsed.text.to_string()));
+            } else if id_tags_is_empty && parsed.comment.is_none() {
+                items.push(LyricsItem::Plain(parsed.text.to_string()));
+            }
+
+            if let Some(comment) = parsed.comment {
+                items.push(LyricsItem::Comment(comment));
+            }
+        }
+
+        Ok((
+            LyricsDocument {
+                items,
+            },
+            warnings,
+        ))
+    }
+}
+
+impl LyricsDocument {
+    /// Convert this document into `Lyrics`, grouping the items by kind.
+    pub fn to_lyrics(&self) -> Result<Lyrics, LyricsError> {
+        let mut lyrics = Lyrics::new();
+
+        for item in self.items.iter() {
+            match item {
+                LyricsItem::Metadata(id_tag) => {
+                    lyrics.metadata.insert(id_tag.clone());
+                },
+                LyricsItem::Timed(time_tags, line) => {
+                    lyrics.add_line_with_multiple_time_tags(time_tags, line.as_str())?;
+                },
+                LyricsItem::Plain(line) => {
+                    lyrics.add_line(line.as_str())?;
+                },
+                LyricsItem::Comment(comment) => {
+                    lyrics.comments.push(comment.clone());
+                },
+            }
+        }
+
+        Ok(lyrics)
+    }
+}
+
+impl From<&Lyrics> for LyricsDocument {
+    fn from(lyrics: &Lyrics) -> Self {
+        let mut items = Vec::new();
+
+        items.extend(lyrics.metadata.iter().cloned().map(LyricsItem::Metadata));
+        items.extend(lyrics.comments.iter().cloned().map(LyricsItem::Comment));
+        items.extend(
+            lyrics
+                .timed_lines
+                .iter()
+                .map(|(time_tag, line)| LyricsItem::Timed(vec![*time_tag], line.to_string())),
+        );
+        items.extend(lyrics.lines.iter().cloned().map(LyricsItem::Plain));
+
+        LyricsDocument {
+            items,
+        }
+    }
+}
+
+impl Display for LyricsDocument {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        let mut iter = self.items.iter();
+
+        if let Some(item) = iter.next() {
+            Display::fmt(item, f)?;
+
+            for item in iter {
+                f.write_char('\n')?;
+                Display::fmt(item, f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for LyricsDocument {
+    type Err = LyricsError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LyricsDocument::from_str(s)
+    }
+}
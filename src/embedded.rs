@@ -0,0 +1,78 @@
+use std::{io, path::Path};
+
+use lofty::{
+    config::WriteOptions,
+    prelude::{ItemKey, TagExt, TaggedFileExt},
+    tag::Tag,
+};
+
+use crate::{Lyrics, LyricsError};
+
+impl Lyrics {
+    /// Create a `Lyrics` instance from the lyrics embedded in a tag, like the `LYRICS` field of Vorbis comments. The synchronized `Lyrics` item is preferred over the `UnsyncLyrics` one. `None` if the tag has no lyrics.
+    pub fn from_tag(tag: &Tag) -> Result<Option<Lyrics>, LyricsError> {
+        tag.get_string(ItemKey::Lyrics)
+            .or_else(|| tag.get_string(ItemKey::UnsyncLyrics))
+            .map(Lyrics::from_str)
+            .transpose()
+    }
+
+    /// Embed the lyrics into a tag in LRC. The `Lyrics` item is used if the tag supports it, like Vorbis comments, MP4 ilst and APE tags. Otherwise, the `UnsyncLyrics` item is used, like ID3v2 tags whose `USLT` frame is read by most players as LRC. Returns `false` if the tag supports neither.
+    pub fn write_to_tag(&self, tag: &mut Tag) -> bool {
+        let s = self.to_string();
+
+        if tag.insert_text(ItemKey::Lyrics, s.clone()) {
+            tag.remove_key(ItemKey::UnsyncLyrics);
+
+            true
+        } else {
+            tag.insert_text(ItemKey::UnsyncLyrics, s)
+        }
+    }
+
+    /// Create a `Lyrics` instance from the lyrics embedded in the tags of the audio file at `path`, like FLAC, Ogg, MP4 and APE files. The primary tag is preferred. `None` if no tag has lyrics.
+    pub fn from_tagged_file<P: AsRef<Path>>(path: P) -> Result<Option<Lyrics>, LyricsError> {
+        let path = path.as_ref();
+
+        let read = || -> Result<Option<Lyrics>, LyricsError> {
+            let tagged_file = lofty::read_from_path(path)
+                .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+
+            for tag in tagged_file.primary_tag().into_iter().chain(tagged_file.tags()) {
+                if let Some(lyrics) = Lyrics::from_tag(tag)? {
+                    return Ok(Some(lyrics));
+                }
+            }
+
+            Ok(None)
+        };
+
+        read().map_err(|error| LyricsError::FileError(path.to_path_buf(), Box::new(error)))
+    }
+
+    /// Embed the lyrics into the primary tag of the audio file at `path`, which is created if the file does not have one.
+    pub fn to_tagged_file<P: AsRef<Path>>(&self, path: P) -> Result<(), LyricsError> {
+        let path = path.as_ref();
+
+        let write = || -> Result<(), LyricsError> {
+            let tagged_file = lofty::read_from_path(path)
+                .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+
+            let mut tag = match tagged_file.primary_tag() {
+                Some(tag) => tag.clone(),
+                None => Tag::new(tagged_file.primary_tag_type()),
+            };
+
+            if !self.write_to_tag(&mut tag) {
+                return Err(LyricsError::FormatError("The tag does not support lyrics."));
+            }
+
+            tag.save_to_path(path, WriteOptions::default())
+                .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+
+            Ok(())
+        };
+
+        write().map_err(|error| LyricsError::FileError(path.to_path_buf(), Box::new(error)))
+    }
+}
@@ -0,0 +1,109 @@
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+
+use crate::{Lyrics, LyricsError, SerializeError, SerializeOptions};
+
+/// Detect the encoding of `bytes` by its byte order mark, or by its content if there is no BOM.
+pub fn detect_encoding(bytes: &[u8]) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return encoding;
+    }
+
+    if std::str::from_utf8(bytes).is_ok() {
+        return UTF_8;
+    }
+
+    let mut detector = EncodingDetector::new();
+
+    detector.feed(bytes, true);
+
+    detector.guess(None, true)
+}
+
+impl Lyrics {
+    /// Create a `Lyrics` instance with bytes in an unknown encoding, such as GBK, Big5 or Shift_JIS. The detected encoding is returned along with the lyrics.
+    pub fn from_bytes<B: AsRef<[u8]>>(
+        bytes: B,
+    ) -> Result<(Lyrics, &'static Encoding), LyricsError> {
+        let bytes = bytes.as_ref();
+
+        let encoding = detect_encoding(bytes);
+
+        let (s, encoding, _) = encoding.decode(bytes);
+
+        Ok((Lyrics::from_str(s)?, encoding))
+    }
+
+    /// Create a `Lyrics` instance with bytes in the specific encoding.
+    #[inline]
+    pub fn from_bytes_with_encoding<B: AsRef<[u8]>>(
+        bytes: B,
+        encoding: &'static Encoding,
+    ) -> Result<Lyrics, LyricsError> {
+        let (s, ..) = encoding.decode(bytes.as_ref());
+
+        Lyrics::from_str(s)
+    }
+}
+
+impl Lyrics {
+    /// Serialize the lyrics into bytes in `encoding`. Return an error if some characters cannot be represented in the encoding.
+    #[inline]
+    pub fn to_bytes(&self, encoding: &'static Encoding) -> Result<Vec<u8>, SerializeError> {
+        self.to_bytes_with_options(encoding, &SerializeOptions::default())
+    }
+
+    /// Serialize the lyrics with options into bytes in `encoding`. Return an error if some characters cannot be represented in the encoding, or if the encoding cannot be used for output, like `REPLACEMENT`. The BOM of `options` is only written for UTF-8, and UTF-16 is always written with a BOM so that it can be detected when read.
+    pub fn to_bytes_with_options(
+        &self,
+        encoding: &'static Encoding,
+        options: &SerializeOptions,
+    ) -> Result<Vec<u8>, SerializeError> {
+        let bom = options.bom && encoding == UTF_8;
+
+        let s = if options.bom {
+            let options = SerializeOptions {
+                bom: false,
+                ..options.clone()
+            };
+
+            self.to_string_with_options(&options)
+        } else {
+            self.to_string_with_options(options)
+        };
+
+        if encoding == UTF_16LE || encoding == UTF_16BE {
+            // encoding_rs only decodes UTF-16, and encodes UTF-8 instead
+            let to_bytes = if encoding == UTF_16LE { u16::to_le_bytes } else { u16::to_be_bytes };
+
+            let mut v = Vec::with_capacity((s.len() + 1) * 2);
+
+            for unit in std::iter::once(0xFEFF).chain(s.encode_utf16()) {
+                v.extend_from_slice(&to_bytes(unit));
+            }
+
+            return Ok(v);
+        }
+
+        let (bytes, output_encoding, unmappable) = encoding.encode(&s);
+
+        if output_encoding != encoding {
+            return Err(SerializeError::UnsupportedEncoding(encoding.name()));
+        }
+
+        if unmappable {
+            return Err(SerializeError::Unencodable(encoding.name()));
+        }
+
+        if bom {
+            let mut v = Vec::with_capacity(bytes.len() + 3);
+
+            v.extend_from_slice(b"\xEF\xBB\xBF");
+            v.extend_from_slice(&bytes);
+
+            Ok(v)
+        } else {
+            Ok(bytes.into_owned())
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{unescape_brackets, Lyrics, Rounding, SerializeOptions, TimeTag, Timestamp};
+
+/// Word time tags of Enhanced LRC, which are in the format **<mm:ss.xx>**.
+pub(crate) static WORD_TIME_TAG_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<-?[0-9]{1,10}:-?[0-9]{1,2}(\.-?[0-9]{1,3})?>").unwrap());
+
+/// A part of a line of Enhanced LRC which begins at a word time tag.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct WordSegment<'a> {
+    /// The timestamp of the word time tag in milliseconds. `None` for the text before the first word time tag.
+    pub(crate) start: Option<i64>,
+    pub(crate) text:  &'a str,
+}
+
+/// Parse a word time tag, like `<00:12.34>` or `<00:12.345>`, into milliseconds.
+pub(crate) fn parse_word_time_tag(tag: &str) -> Option<i64> {
+    let tag = tag.strip_prefix('<')?.strip_suffix('>')?;
+
+    let negative = tag.contains('-');
+
+    let (minute, rest) = tag.split_once(':')?;
+
+    let (second, fraction) = match rest.split_once('.') {
+        Some((second, fraction)) => (second, fraction.trim_start_matches('-')),
+        None => (rest, ""),
+    };
+
+    let minute = minute.trim_start_matches('-').parse::<i64>().ok()?;
+    let second = second.trim_start_matches('-').parse::<i64>().ok()?;
+
+    let millisecond = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<i64>().ok()? * 10i64.pow(3 - fraction.len() as u32)
+    };
+
+    let timestamp = minute * 60000 + second * 1000 + millisecond;
+
+    Some(if negative { -timestamp } else { timestamp })
+}
+
+/// Get the text of a line as it is sung, without word time tags and escapes of brackets.
+pub(crate) fn plain_line_text(line: &str) -> String {
+    let text: String = word_segments(line).into_iter().map(|segment| segment.text).collect();
+
+    unescape_brackets(&text).into_owned()
+}
+
+/// Split a line of Enhanced LRC at its word time tags. The text before the first word time tag is only included if it is not empty.
+pub(crate) fn word_segments(line: &str) -> Vec<WordSegment<'_>> {
+    let mut segments = Vec::new();
+
+    let mut start = None;
+    let mut text_start = 0;
+
+    for m in WORD_TIME_TAG_RE.find_iter(line) {
+        let text = &line[text_start..m.start()];
+
+        if start.is_some() || !text.is_empty() {
+            segments.push(WordSegment {
+                start,
+                text,
+            });
+        }
+
+        start = parse_word_time_tag(m.as_str());
+        text_start = m.end();
+    }
+
+    let text = &line[text_start..];
+
+    if start.is_some() || !text.is_empty() {
+        segments.push(WordSegment {
+            start,
+            text,
+        });
+    }
+
+    segments
+}
+
+/// Get the end time of a line which begins at `start`, from the word time tag at the end of the line, if any.
+pub(crate) fn word_end_time(segments: &[WordSegment], start: i64) -> Option<i64> {
+    let last = segments.last()?;
+
+    match last.start {
+        Some(last_start) if last.text.is_empty() && last_start > start => Some(last_start),
+        _ => None,
+    }
+}
+
+/// Write a word time tag of Enhanced LRC, like `<00:12.35>`.
+#[inline]
+pub(crate) fn push_word_time_tag(s: &mut String, timestamp: i64) {
+    s.push('<');
+    s.push_str(&Timestamp::new(timestamp).round_to_hundredths(Rounding::HalfUp).to_string());
+    s.push('>');
+}
+
+/// A timed line which loses information when written in core LRC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DegradedLine {
+    /// The index of the timed line.
+    pub index:                usize,
+    pub time_tag:             TimeTag,
+    /// The number of dropped word timings.
+    pub dropped_word_timings: usize,
+    /// The dropped end time of the line, which is a word time tag at the end of the line.
+    pub dropped_end_time:     Option<Timestamp>,
+}
+
+/// What is dropped when lyrics with word timings is written in core LRC.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DegradationReport {
+    pub lines: Vec<DegradedLine>,
+}
+
+impl DegradationReport {
+    /// Whether nothing is dropped.
+    #[inline]
+    pub fn is_lossless(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// The total number of dropped word timings.
+    #[inline]
+    pub fn dropped_word_timings(&self) -> usize {
+        self.lines.iter().map(|line| line.dropped_word_timings).sum()
+    }
+
+    /// The total number of dropped end times.
+    #[inline]
+    pub fn dropped_end_times(&self) -> usize {
+        self.lines.iter().filter(|line| line.dropped_end_time.is_some()).count()
+    }
+}
+
+impl Lyrics {
+    /// Serialize the lyrics in core LRC by removing word time tags from lines. What is removed is reported along with the output.
+    #[inline]
+    pub fn to_core_string(&self) -> (String, DegradationReport) {
+        self.to_core_string_with_options(&SerializeOptions::default())
+    }
+
+    /// Serialize the lyrics with options in core LRC by removing word time tags from lines. What is removed is reported along with the output.
+    pub fn to_core_string_with_options(
+        &self,
+        options: &SerializeOptions,
+    ) -> (String, DegradationReport) {
+        let mut report = DegradationReport::default();
+        let mut lyrics = self.clone();
+
+        for (index, (time_tag, line)) in lyrics.timed_lines.iter_mut().enumerate() {
+            let tags: Vec<_> = WORD_TIME_TAG_RE.find_iter(line).collect();
+
+            if tags.is_empty() {
+                continue;
+            }
+
+            let last = tags.last().unwrap();
+
+            let dropped_end_time = if line[last.end()..].trim().is_empty() {
+                parse_word_time_tag(last.as_str()).map(Timestamp::new)
+            } else {
+                None
+            };
+
+            report.lines.push(DegradedLine {
+                index,
+                time_tag: *time_tag,
+                dropped_word_timings: tags.len() - usize::from(dropped_end_time.is_some()),
+                dropped_end_time,
+            });
+
+            let stripped = WORD_TIME_TAG_RE.replace_all(line, "");
+
+            *line = stripped.trim().into();
+        }
+
+        (lyrics.to_string_with_options(options), report)
+    }
+}
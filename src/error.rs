@@ -1,13 +1,55 @@
 use std::{
     error::Error,
     fmt::{self, Debug, Display, Formatter},
+    io,
+    path::PathBuf,
 };
 
-#[derive(Debug, PartialEq)]
+use crate::TimeTag;
+
+#[derive(Debug)]
 pub enum LyricsError {
     ParseError(String),
     IDTagError(IDTagErrorKind),
     FormatError(&'static str),
+    NegativeTime(TimeTag),
+    LimitExceeded(LimitKind),
+    IOError(io::Error),
+    /// An error occurred while reading or writing the file at the path.
+    FileError(PathBuf, Box<LyricsError>),
+    /// The lyrics cannot be serialized as requested.
+    SerializeError(SerializeError),
+}
+
+impl PartialEq for LyricsError {
+    #[inline]
+    fn eq(&self, other: &LyricsError) -> bool {
+        match (self, other) {
+            (LyricsError::ParseError(a), LyricsError::ParseError(b)) => a == b,
+            (LyricsError::IDTagError(a), LyricsError::IDTagError(b)) => a == b,
+            (LyricsError::FormatError(a), LyricsError::FormatError(b)) => a == b,
+            (LyricsError::NegativeTime(a), LyricsError::NegativeTime(b)) => a == b,
+            (LyricsError::LimitExceeded(a), LyricsError::LimitExceeded(b)) => a == b,
+            (LyricsError::IOError(a), LyricsError::IOError(b)) => a.kind() == b.kind(),
+            (LyricsError::FileError(a, e), LyricsError::FileError(b, f)) => a == b && e == f,
+            (LyricsError::SerializeError(a), LyricsError::SerializeError(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl From<io::Error> for LyricsError {
+    #[inline]
+    fn from(error: io::Error) -> Self {
+        LyricsError::IOError(error)
+    }
+}
+
+impl From<SerializeError> for LyricsError {
+    #[inline]
+    fn from(error: SerializeError) -> Self {
+        LyricsError::SerializeError(error)
+    }
 }
 
 impl Display for LyricsError {
@@ -17,12 +59,60 @@ impl Display for LyricsError {
             LyricsError::ParseError(s) => f.write_str(s),
             LyricsError::IDTagError(k) => f.write_fmt(format_args!("Set a wrong {}.", k)),
             LyricsError::FormatError(s) => f.write_str(s),
+            LyricsError::NegativeTime(t) => {
+                f.write_fmt(format_args!("The time tag {} is negative.", t))
+            },
+            LyricsError::LimitExceeded(k) => {
+                f.write_fmt(format_args!("The limit of {} is exceeded.", k))
+            },
+            LyricsError::IOError(e) => Display::fmt(e, f),
+            LyricsError::FileError(p, e) => f.write_fmt(format_args!("{}: {}", p.display(), e)),
+            LyricsError::SerializeError(e) => Display::fmt(e, f),
         }
     }
 }
 
 impl Error for LyricsError {}
 
+/// A limit of `ParseLimits`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LimitKind {
+    /// The total number of bytes.
+    Bytes,
+    /// The number of lines.
+    Lines,
+    /// The number of bytes of a line.
+    LineLength,
+}
+
+impl Display for LimitKind {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            LimitKind::Bytes => f.write_str("total bytes"),
+            LimitKind::Lines => f.write_str("lines"),
+            LimitKind::LineLength => f.write_str("line length"),
+        }
+    }
+}
+
+/// An error which makes a line be skipped while parsing.
+#[derive(Debug, PartialEq)]
+pub struct LineError {
+    /// The line number, starting from 1.
+    pub line_number: usize,
+    pub error:       LyricsError,
+}
+
+impl Display for LineError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_fmt(format_args!("Line {}: {}", self.line_number, self.error))
+    }
+}
+
+impl Error for LineError {}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum IDTagErrorKind {
     Label,
@@ -40,3 +130,49 @@ impl Display for IDTagErrorKind {
 }
 
 impl Error for IDTagErrorKind {}
+
+/// Errors which occur when lyrics cannot be serialized losslessly.
+#[derive(Debug, PartialEq)]
+pub enum SerializeError {
+    /// A time tag is negative.
+    NegativeTime(TimeTag),
+    /// A time tag exceeds `99:59.99`.
+    TimeOverflow(TimeTag),
+    /// A time tag has a precision finer than hundredths of a second.
+    PrecisionLoss(TimeTag),
+    /// A timed line contains per-word time tags. The value is the index of the timed line.
+    WordTimings(usize),
+    /// Some characters cannot be represented in the encoding. The value is the name of the encoding.
+    Unencodable(&'static str),
+    /// The encoding cannot be used for output. The value is the name of the encoding.
+    UnsupportedEncoding(&'static str),
+}
+
+impl Display for SerializeError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            SerializeError::NegativeTime(t) => {
+                f.write_fmt(format_args!("The time tag {} is negative.", t))
+            },
+            SerializeError::TimeOverflow(t) => {
+                f.write_fmt(format_args!("The time tag {} has more than two digits of minutes.", t))
+            },
+            SerializeError::PrecisionLoss(t) => f.write_fmt(format_args!(
+                "The time tag {} cannot be represented in hundredths of a second.",
+                t
+            )),
+            SerializeError::WordTimings(i) => {
+                f.write_fmt(format_args!("The timed line {} contains per-word time tags.", i))
+            },
+            SerializeError::Unencodable(e) => {
+                f.write_fmt(format_args!("Some characters cannot be encoded in {}.", e))
+            },
+            SerializeError::UnsupportedEncoding(e) => {
+                f.write_fmt(format_args!("The lyrics cannot be written in {}.", e))
+            },
+        }
+    }
+}
+
+impl Error for SerializeError {}
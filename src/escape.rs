@@ -0,0 +1,36 @@
+use std::borrow::Cow;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static ESCAPE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\\[\\\[\]]").unwrap());
+
+/// Escape `[`, `]` and `\` in `text` with `\`, so that the text can be put in lyrics even if it looks like a tag.
+pub fn escape_brackets(text: &str) -> Cow<'_, str> {
+    if !text.contains(['[', ']', '\\']) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut s = String::with_capacity(text.len() + 2);
+
+    for c in text.chars() {
+        if matches!(c, '[' | ']' | '\\') {
+            s.push('\\');
+        }
+
+        s.push(c);
+    }
+
+    Cow::Owned(s)
+}
+
+/// Unescape the `\[`, `\]` and `\\` sequences in `text` which are made by `escape_brackets`.
+pub fn unescape_brackets(text: &str) -> Cow<'_, str> {
+    ESCAPE_RE.replace_all(text, |c: &regex::Captures| c[0][1..].to_string())
+}
+
+/// Remove the escape sequences in `text`, so that escaped brackets are not treated as tags.
+#[inline]
+pub(crate) fn remove_escapes(text: &str) -> Cow<'_, str> {
+    ESCAPE_RE.replace_all(text, "")
+}
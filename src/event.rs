@@ -0,0 +1,137 @@
+use std::collections::VecDeque;
+
+use crate::{
+    check_line, split_lines, strip_bom, Comment, IDTag, Lines, LyricsError, ParseOptions,
+    ParseWarning, ParseWarningKind, ParsedLine, TimeTag,
+};
+
+/// An event emitted by `LyricsEventParser`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LyricsEvent<'a> {
+    /// An ID tag.
+    IdTag(IDTag),
+    /// A line with one or more time tags.
+    TimedLine(Vec<TimeTag>, &'a str),
+    /// A line without time tags.
+    PlainLine(&'a str),
+    /// A comment.
+    Comment(Comment),
+    /// A warning reported while parsing, which comes before the events of its line. A line skipped because of `skip_invalid_lines` only has a warning.
+    Warning(ParseWarning),
+}
+
+/// A parser which yields the events of lyrics as it consumes the input, without building `Lyrics` in memory.
+#[derive(Debug)]
+pub struct LyricsEventParser<'a> {
+    lines:       Lines<'a>,
+    line_number: usize,
+    options:     ParseOptions,
+    pending:     VecDeque<LyricsEvent<'a>>,
+    error:       Option<LyricsError>,
+    done:        bool,
+}
+
+impl<'a> LyricsEventParser<'a> {
+    /// Create a `LyricsEventParser` instance with a string.
+    #[inline]
+    pub fn new(s: &'a str) -> LyricsEventParser<'a> {
+        Self::with_options(s, ParseOptions::default())
+    }
+
+    /// Create a `LyricsEventParser` instance with a string and options.
+    pub fn with_options(s: &'a str, options: ParseOptions) -> LyricsEventParser<'a> {
+        let s = strip_bom(s);
+
+        let error = options.limits.check_bytes(s.len()).err();
+
+        LyricsEventParser {
+            lines: split_lines(s),
+            line_number: 0,
+            options,
+            pending: VecDeque::new(),
+            error,
+            done: false,
+        }
+    }
+
+    fn parse_next_line(&mut self) -> Result<bool, LyricsError> {
+        let line = match self.lines.next() {
+            Some(line) => line,
+            None => return Ok(false),
+        };
+
+        self.line_number += 1;
+
+        self.options.limits.check_line(self.line_number, line)?;
+
+        let mut warnings = Vec::new();
+
+        let parsed = ParsedLine::parse(line, Some(self.line_number), &self.options, &mut warnings)
+            .and_then(|parsed| check_line(parsed.text).map(|_| parsed));
+
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(_) if self.options.skip_invalid_lines => {
+                self.pending.push_back(LyricsEvent::Warning(ParseWarning {
+                    line_number: self.line_number,
+                    kind:        ParseWarningKind::SkippedLine,
+                }));
+
+                return Ok(true);
+            },
+            Err(error) => return Err(error),
+        };
+
+        self.pending.extend(warnings.into_iter().map(LyricsEvent::Warning));
+
+        let id_tags_is_empty = parsed.id_tags.is_empty();
+
+        self.pending.extend(parsed.id_tags.into_iter().map(LyricsEvent::IdTag));
+
+        if !parsed.time_tags.is_empty() {
+            self.pending.push_back(LyricsEvent::TimedLine(parsed.time_tags, parsed.text));
+        } else if id_tags_is_empty && parsed.comment.is_none() {
+            self.pending.push_back(LyricsEvent::PlainLine(parsed.text));
+        }
+
+        if let Some(comment) = parsed.comment {
+            self.pending.push_back(LyricsEvent::Comment(comment));
+        }
+
+        Ok(true)
+    }
+}
+
+impl<'a> Iterator for LyricsEventParser<'a> {
+    type Item = Result<LyricsEvent<'a>, LyricsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+
+            if self.done {
+                return None;
+            }
+
+            if let Some(error) = self.error.take() {
+                self.done = true;
+
+                return Some(Err(error));
+            }
+
+            match self.parse_next_line() {
+                Ok(true) => (),
+                Ok(false) => {
+                    self.done = true;
+                },
+                Err(error) => {
+                    self.done = true;
+
+                    return Some(Err(error));
+                },
+            }
+        }
+    }
+}
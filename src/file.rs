@@ -0,0 +1,178 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "encoding")]
+use encoding_rs::Encoding;
+
+use crate::{Lyrics, LyricsError, SerializeOptions};
+
+impl Lyrics {
+    /// Create a `Lyrics` instance by reading a file. The BOM is skipped, and the encoding is detected if the `encoding` feature is enabled.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Lyrics, LyricsError> {
+        let path = path.as_ref();
+
+        Self::from_file_inner(path)
+            .map_err(|error| LyricsError::FileError(path.to_path_buf(), Box::new(error)))
+    }
+
+    #[cfg(feature = "encoding")]
+    #[inline]
+    fn from_file_inner(path: &Path) -> Result<Lyrics, LyricsError> {
+        let bytes = std::fs::read(path)?;
+
+        Lyrics::from_bytes(bytes).map(|(lyrics, _)| lyrics)
+    }
+
+    #[cfg(not(feature = "encoding"))]
+    #[inline]
+    fn from_file_inner(path: &Path) -> Result<Lyrics, LyricsError> {
+        let file = File::open(path)?;
+
+        Lyrics::from_reader(std::io::BufReader::new(file))
+    }
+
+    /// Find the sidecar LRC file of the audio file `audio_path` with `find_sidecar` and parse it. `None` if there is no sidecar.
+    #[inline]
+    pub fn from_sidecar<P: AsRef<Path>>(audio_path: P) -> Result<Option<Lyrics>, LyricsError> {
+        find_sidecar(audio_path).map(Lyrics::from_file).transpose()
+    }
+
+    /// Write the lyrics into a file in UTF-8.
+    #[inline]
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), LyricsError> {
+        self.to_file_with_options(path, &SerializeOptions::default())
+    }
+
+    /// Write the lyrics with options into a file in UTF-8.
+    pub fn to_file_with_options<P: AsRef<Path>>(
+        &self,
+        path: P,
+        options: &SerializeOptions,
+    ) -> Result<(), LyricsError> {
+        let path = path.as_ref();
+
+        let write = || -> Result<(), LyricsError> {
+            let mut writer = BufWriter::new(File::create(path)?);
+
+            self.to_writer_with_options(&mut writer, options)?;
+
+            writer.flush()?;
+
+            Ok(())
+        };
+
+        write().map_err(|error| LyricsError::FileError(path.to_path_buf(), Box::new(error)))
+    }
+
+    /// Write the lyrics with options into a file in `encoding`.
+    #[cfg(feature = "encoding")]
+    pub fn to_file_with_encoding<P: AsRef<Path>>(
+        &self,
+        path: P,
+        encoding: &'static Encoding,
+        options: &SerializeOptions,
+    ) -> Result<(), LyricsError> {
+        let path = path.as_ref();
+
+        let write = || -> Result<(), LyricsError> {
+            let bytes = self.to_bytes_with_options(encoding, options)?;
+
+            std::fs::write(path, bytes)?;
+
+            Ok(())
+        };
+
+        write().map_err(|error| LyricsError::FileError(path.to_path_buf(), Box::new(error)))
+    }
+}
+
+/// Load all `.lrc` files in the directory `path`, and in its subdirectories if `recursive` is `true`. Symbolic links to directories are not followed. Each file, or directory which cannot be read, comes with its own result, sorted by path.
+pub fn load_dir<P: AsRef<Path>>(
+    path: P,
+    recursive: bool,
+) -> Vec<(PathBuf, Result<Lyrics, LyricsError>)> {
+    let mut results = Vec::new();
+
+    let mut directories = vec![path.as_ref().to_path_buf()];
+
+    while let Some(directory) = directories.pop() {
+        let entries = match std::fs::read_dir(&directory) {
+            Ok(entries) => entries,
+            Err(error) => {
+                let error = LyricsError::FileError(directory.clone(), Box::new(error.into()));
+
+                results.push((directory, Err(error)));
+
+                continue;
+            },
+        };
+
+        for entry in entries {
+            // symbolic links are not followed, so that a link to an ancestor does not cause a loop
+            let (path, is_dir) = match entry
+                .and_then(|entry| Ok((entry.path(), entry.file_type()?.is_dir())))
+            {
+                Ok(entry) => entry,
+                Err(error) => {
+                    let error = LyricsError::FileError(directory.clone(), Box::new(error.into()));
+
+                    results.push((directory.clone(), Err(error)));
+
+                    continue;
+                },
+            };
+
+            if is_dir {
+                if recursive {
+                    directories.push(path);
+                }
+            } else if path.extension().map_or(false, |e| e.eq_ignore_ascii_case("lrc")) {
+                let lyrics = Lyrics::from_file(&path);
+
+                results.push((path, lyrics));
+            }
+        }
+    }
+
+    results.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    results
+}
+
+/// The extensions tried for sidecar files, in order.
+const SIDECAR_EXTENSIONS: [&str; 3] = ["lrc", "LRC", "Lrc"];
+
+/// The subdirectories of the audio directory where sidecar files are also looked for, in order.
+const SIDECAR_DIRECTORIES: [&str; 2] = ["Lyrics", "lyrics"];
+
+/// Find the sidecar LRC file of the audio file `audio_path`, which has the same name with the `.lrc` extension in any common case, in the same directory or in its `Lyrics` subdirectory.
+pub fn find_sidecar<P: AsRef<Path>>(audio_path: P) -> Option<PathBuf> {
+    let audio_path = audio_path.as_ref();
+
+    let stem = audio_path.file_stem()?;
+
+    let directory = audio_path.parent().unwrap_or_else(|| Path::new(""));
+
+    let directories = std::iter::once(directory.to_path_buf())
+        .chain(SIDECAR_DIRECTORIES.iter().map(|d| directory.join(d)));
+
+    for directory in directories {
+        for extension in SIDECAR_EXTENSIONS.iter() {
+            let mut file_name = stem.to_os_string();
+
+            file_name.push(".");
+            file_name.push(extension);
+
+            let path = directory.join(file_name);
+
+            if path.is_file() {
+                return Some(path);
+            }
+        }
+    }
+
+    None
+}
@@ -0,0 +1,87 @@
+use std::fmt::Write;
+
+use crate::Lyrics;
+
+impl Lyrics {
+    /// Serialize the lyrics in the canonical form, which only depends on the content of the lyrics and not on how they were built.
+    ///
+    /// * Lines are separated by `\n` and there is no BOM and no trailing newline.
+    /// * ID tags are written as `[label:text]` with lowercased labels, sorted by the label and then by the text.
+    /// * Comments are written as `[:]text` in their order.
+    /// * Timed lines are written as `[mm:ss.xxx]text` in milliseconds, sorted by the time and then by the text.
+    /// * Plain lines are written in their order, with leading and trailing empty lines removed.
+    /// * Each section begins with a line of `#metadata`, `#comments`, `#timed` or `#plain`, even if it is empty.
+    ///
+    /// This format will not change unless the major version of this crate is bumped.
+    pub fn to_canonical_string(&self) -> String {
+        let mut s = String::new();
+
+        let mut metadata: Vec<(String, &str)> = self
+            .metadata
+            .iter()
+            .map(|id_tag| (id_tag.get_label().to_lowercase(), id_tag.get_text()))
+            .collect();
+
+        metadata.sort();
+
+        s.push_str("#metadata");
+
+        for (label, text) in metadata {
+            s.write_fmt(format_args!("\n[{}:{}]", label, text)).unwrap();
+        }
+
+        s.push_str("\n#comments");
+
+        for comment in self.comments.iter() {
+            s.write_fmt(format_args!("\n[:]{}", comment.text())).unwrap();
+        }
+
+        let mut timed_lines: Vec<(i64, &str)> = self
+            .timed_lines
+            .iter()
+            .map(|(time_tag, line)| (time_tag.get_timestamp(), line.as_ref()))
+            .collect();
+
+        timed_lines.sort();
+
+        s.push_str("\n#timed");
+
+        for (timestamp, line) in timed_lines {
+            let sign = if timestamp < 0 { "-" } else { "" };
+            let timestamp = timestamp.unsigned_abs();
+
+            s.write_fmt(format_args!(
+                "\n[{}{:02}:{:02}.{:03}]{}",
+                sign,
+                timestamp / 60000,
+                (timestamp % 60000) / 1000,
+                timestamp % 1000,
+                line
+            ))
+            .unwrap();
+        }
+
+        s.push_str("\n#plain");
+
+        let lines = &self.lines;
+        let start = lines.iter().position(|line| !line.trim().is_empty()).unwrap_or(lines.len());
+        let end = lines.iter().rposition(|line| !line.trim().is_empty()).map_or(start, |i| i + 1);
+
+        for line in &lines[start..end] {
+            s.push('\n');
+            s.push_str(line);
+        }
+
+        s
+    }
+
+    /// Get a 64-bit FNV-1a hash of the canonical form of the lyrics (see `to_canonical_string`), which is stable across versions of this crate unless the major version is bumped.
+    pub fn fingerprint(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xCBF29CE484222325;
+        const PRIME: u64 = 0x100000001B3;
+
+        self.to_canonical_string()
+            .bytes()
+            .fold(OFFSET_BASIS, |hash, b| (hash ^ b as u64).wrapping_mul(PRIME))
+    }
+}
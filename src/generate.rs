@@ -0,0 +1,116 @@
+use crate::{IDTag, Lyrics, TimeTag, Timestamp};
+
+const WORDS: [&str; 32] = [
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+    "enim",
+    "ad",
+    "minim",
+    "veniam",
+    "quis",
+    "nostrud",
+    "exercitation",
+    "ullamco",
+    "laboris",
+    "nisi",
+    "aliquip",
+    "ex",
+    "ea",
+];
+
+/// A SplitMix64 pseudorandom number generator.
+struct Rng(u64);
+
+impl Rng {
+    #[inline]
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.0;
+
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+        z ^ (z >> 31)
+    }
+
+    /// Get a number in `[low, high]`.
+    #[inline]
+    fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next() % (high - low + 1)
+    }
+
+    /// Get some capitalized words.
+    fn words(&mut self, min: u64, max: u64) -> String {
+        let count = self.range(min, max);
+
+        let mut s = String::new();
+
+        for i in 0..count {
+            let word = WORDS[self.range(0, WORDS.len() as u64 - 1) as usize];
+
+            if i == 0 {
+                let mut chars = word.chars();
+
+                s.extend(chars.next().unwrap().to_uppercase());
+                s.push_str(chars.as_str());
+            } else {
+                s.push(' ');
+                s.push_str(word);
+            }
+        }
+
+        s
+    }
+}
+
+/// Generate synthetic lyrics with `lines` timed lines of placeholder text and plausible metadata. The gaps between lines vary around `avg_gap_ms` milliseconds. The same `seed` always generates the same lyrics.
+pub fn generate(seed: u64, lines: usize, avg_gap_ms: u32) -> Lyrics {
+    let mut rng = Rng(seed);
+    let mut lyrics = Lyrics::new();
+
+    for (label, min, max) in [("ti", 1, 4), ("ar", 2, 3), ("al", 1, 4)] {
+        let text = rng.words(min, max);
+
+        lyrics.metadata.insert(IDTag::from_string(label, text).unwrap());
+    }
+
+    lyrics.metadata.insert(IDTag::from_string("by", "lrc").unwrap());
+
+    let avg_gap_ms = u64::from(avg_gap_ms) / 10 * 10;
+
+    let mut timestamp = rng.range(0, avg_gap_ms / 10) * 10;
+
+    for i in 0..lines {
+        if i > 0 {
+            timestamp += rng.range(avg_gap_ms / 20, avg_gap_ms * 3 / 20) * 10;
+        }
+
+        let line = rng.words(3, 8);
+
+        unsafe {
+            lyrics.add_timed_line_unchecked(TimeTag::new(timestamp as i64), line.into());
+        }
+    }
+
+    lyrics.set_length(Timestamp::new((timestamp + avg_gap_ms) as i64 / 1000 * 1000 + 1000));
+
+    lyrics
+}
@@ -0,0 +1,60 @@
+use std::ops::Range;
+
+use aho_corasick::AhoCorasick;
+
+use crate::{enhanced::plain_line_text, LineMatch, Lyrics, TimeTag};
+
+/// A line found by `Lyrics::search_patterns`, with the patterns it contains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternLineMatch<'a> {
+    pub line:       LineMatch<'a>,
+    /// When the line is shown, from when it begins to when the next timed line begins. `None` for plain lines.
+    pub time_range: Option<Range<TimeTag>>,
+    /// The indices of the patterns found in the line, in ascending order.
+    pub patterns:   Vec<usize>,
+}
+
+/// Get the indices of the patterns of `automaton` found in `text`, sorted and deduplicated. Overlapping matches are found if the match kind of `automaton` supports it.
+fn find_patterns(automaton: &AhoCorasick, text: &str) -> Vec<usize> {
+    let mut patterns: Vec<usize> = match automaton.try_find_overlapping_iter(text) {
+        Ok(iter) => iter.map(|m| m.pattern().as_usize()).collect(),
+        Err(_) => automaton.find_iter(text).map(|m| m.pattern().as_usize()).collect(),
+    };
+
+    patterns.sort_unstable();
+    patterns.dedup();
+
+    patterns
+}
+
+impl Lyrics {
+    /// Find the timed lines and then the plain lines which contain any pattern of `automaton`, scanning every line once for all patterns. Word time tags and escapes of brackets in the lines are ignored. The last timed line ends at the `[length:]` tag if possible, or `last_line_duration` milliseconds after it begins.
+    pub fn search_patterns<N: Into<i64>>(
+        &self,
+        automaton: &AhoCorasick,
+        last_line_duration: N,
+    ) -> Vec<PatternLineMatch<'_>> {
+        let timed_lines = self.timed_line_spans(last_line_duration).enumerate().map(
+            |(index, (start, end, text))| PatternLineMatch {
+                line:       LineMatch::Timed {
+                    index,
+                    time_tag: start,
+                    text,
+                },
+                time_range: Some(start..end),
+                patterns:   find_patterns(automaton, &plain_line_text(text)),
+            },
+        );
+
+        let lines = self.lines.iter().enumerate().map(|(index, line)| PatternLineMatch {
+            line:       LineMatch::Plain {
+                index,
+                text: line,
+            },
+            time_range: None,
+            patterns:   find_patterns(automaton, &plain_line_text(line)),
+        });
+
+        timed_lines.chain(lines).filter(|line_match| !line_match.patterns.is_empty()).collect()
+    }
+}
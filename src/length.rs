@@ -0,0 +1,147 @@
+use std::fmt::{self, Write};
+
+use crate::{IDTag, KnownTag, Lyrics, Timestamp};
+
+/// How to write the value of the `[length:]` tag.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Educe)]
+#[educe(Default)]
+pub enum LengthFormat {
+    /// Keep the original text.
+    #[educe(Default)]
+    Original,
+    /// `mm:ss`
+    MinutesSeconds,
+    /// `h:mm:ss`
+    HoursMinutesSeconds,
+    /// The number of seconds.
+    Seconds,
+}
+
+/// Parse the value of the `[length:]` tag, which can be in the `m:ss`, `mm:ss`, `h:mm:ss` or `NNN` (seconds) format, optionally with a fraction of a second.
+pub fn parse_length<S: AsRef<str>>(s: S) -> Option<Timestamp> {
+    let s = s.as_ref().trim();
+
+    let parse_seconds = |s: &str| -> Option<i64> {
+        let (seconds, fraction) = match s.split_once('.') {
+            Some((seconds, fraction)) => (seconds, fraction),
+            None => (s, ""),
+        };
+
+        if seconds.is_empty()
+            || fraction.len() > 3
+            || !seconds.bytes().all(|b| b.is_ascii_digit())
+            || !fraction.bytes().all(|b| b.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let mut millisecond = 0;
+
+        for (i, b) in fraction.bytes().enumerate() {
+            millisecond += (b - b'0') as i64 * [100, 10, 1][i];
+        }
+
+        seconds.parse::<i64>().ok()?.checked_mul(1000)?.checked_add(millisecond)
+    };
+
+    let parse_integer = |s: &str| -> Option<i64> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        s.parse::<i64>().ok()
+    };
+
+    let parts: Vec<&str> = s.split(':').collect();
+
+    let millisecond = match parts.as_slice() {
+        [seconds] => parse_seconds(seconds)?,
+        [minutes, seconds] => {
+            let seconds = parse_seconds(seconds)?;
+
+            if seconds >= 60000 {
+                return None;
+            }
+
+            parse_integer(minutes)?.checked_mul(60000)?.checked_add(seconds)?
+        },
+        [hours, minutes, seconds] => {
+            let minutes = parse_integer(minutes)?;
+            let seconds = parse_seconds(seconds)?;
+
+            if minutes >= 60 || seconds >= 60000 {
+                return None;
+            }
+
+            parse_integer(hours)?.checked_mul(3600000)?.checked_add(minutes * 60000 + seconds)?
+        },
+        _ => return None,
+    };
+
+    Some(Timestamp::new(millisecond))
+}
+
+/// Write `length` in `format`. Return `false` if `format` is `LengthFormat::Original`.
+pub(crate) fn write_length<W: Write>(
+    f: &mut W,
+    length: Timestamp,
+    format: LengthFormat,
+) -> Result<bool, fmt::Error> {
+    let seconds = (length.get_timestamp() + 500) / 1000;
+
+    match format {
+        LengthFormat::Original => return Ok(false),
+        LengthFormat::MinutesSeconds => {
+            f.write_fmt(format_args!("{:02}:{:02}", seconds / 60, seconds % 60))?
+        },
+        LengthFormat::HoursMinutesSeconds => f.write_fmt(format_args!(
+            "{}:{:02}:{:02}",
+            seconds / 3600,
+            (seconds % 3600) / 60,
+            seconds % 60
+        ))?,
+        LengthFormat::Seconds => f.write_fmt(format_args!("{}", seconds))?,
+    }
+
+    Ok(true)
+}
+
+impl Lyrics {
+    /// Get the length of the track from the `[length:]` tag.
+    #[inline]
+    pub fn length(&self) -> Option<Timestamp> {
+        self.get_tag(KnownTag::Length).and_then(parse_length)
+    }
+
+    /// Set the `[length:]` tag in the `mm:ss` format.
+    #[inline]
+    pub fn set_length(&mut self, length: Timestamp) {
+        let mut text = String::new();
+
+        write_length(&mut text, length, LengthFormat::MinutesSeconds).unwrap();
+
+        self.metadata.replace(unsafe { IDTag::from_string_unchecked("length", text) });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(Some(Timestamp::new(143000)), parse_length("2:23"));
+        assert_eq!(Some(Timestamp::new(143000)), parse_length("02:23"));
+        assert_eq!(Some(Timestamp::new(143500)), parse_length("02:23.5"));
+        assert_eq!(Some(Timestamp::new(3743000)), parse_length("1:02:23"));
+        assert_eq!(Some(Timestamp::new(143000)), parse_length("143"));
+        assert_eq!(Some(Timestamp::new(143250)), parse_length(" 143.25 "));
+
+        assert_eq!(None, parse_length(""));
+        assert_eq!(None, parse_length("2:60"));
+        assert_eq!(None, parse_length("1:60:00"));
+        assert_eq!(None, parse_length("-2:23"));
+        assert_eq!(None, parse_length("1:2:3:4"));
+        assert_eq!(None, parse_length("abc"));
+    }
+}
@@ -10,7 +10,7 @@ use lrc::{Lyrics, IDTag, TimeTag};
 
 let mut lyrics = Lyrics::new();
 
-let metadata = &mut lyrics.metadata;
+let metadata = lyrics.get_metadata_mut();
 metadata.insert(IDTag::from_string("ti", "Let's Twist Again").unwrap());
 metadata.insert(IDTag::from_string("al", "Hits Of The 60's - Vol. 2 – Oldies").unwrap());
 
@@ -47,28 +47,161 @@ if let Some(index) = lyrics.find_timed_line_index(TimeTag::from_str("00:13.00").
 #[macro_use]
 extern crate educe;
 
+#[cfg(feature = "rkyv")]
+mod archive;
+#[cfg(feature = "ass")]
+mod ass;
+#[cfg(feature = "tokio")]
+mod async_reader;
+mod audit;
+mod bilingual;
+#[cfg(feature = "chrono")]
+mod chrono_impls;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+pub mod convert;
+mod csv;
+mod cursor;
+mod diff;
+mod distribute;
+mod document;
+#[cfg(feature = "lofty")]
+mod embedded;
+#[cfg(feature = "encoding")]
+mod encoding;
+mod enhanced;
 mod error;
+mod escape;
+mod event;
+mod file;
+mod fingerprint;
+mod generate;
+#[cfg(feature = "aho-corasick")]
+mod keyword;
+mod length;
+mod lookup;
+mod markdown;
+mod metadata;
+#[cfg(feature = "netease")]
+mod netease;
+#[cfg(feature = "unicode-normalization")]
+mod normalize;
+mod parse;
+pub mod prelude;
+mod push;
+mod reader;
+mod search;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod serialize;
+mod shift;
+mod srt;
+#[cfg(feature = "subparse")]
+mod subtitle;
 pub mod tags;
+mod text_match;
+pub mod time;
+#[cfg(feature = "time")]
+mod time_impls;
 mod timestamp;
+mod transaction;
+#[cfg(feature = "ttml")]
+mod ttml;
+mod uslt;
+mod validate;
+mod writer;
 
 use std::{
-    collections::BTreeSet,
-    fmt::{self, Display, Formatter, Write},
-    rc::Rc,
+    fmt::{self, Display, Formatter},
     str::FromStr,
 };
 
+#[cfg(feature = "aho-corasick")]
+pub use aho_corasick;
+#[cfg(feature = "rkyv")]
+pub use archive::*;
+#[cfg(feature = "ass")]
+pub use ass::*;
+pub use audit::*;
+pub use bilingual::*;
+#[cfg(feature = "chrono")]
+pub use chrono;
+pub use convert::formats;
+pub use cursor::*;
+pub use diff::*;
+pub use distribute::*;
+pub use document::*;
+#[cfg(feature = "encoding")]
+pub use encoding::*;
+#[cfg(feature = "encoding")]
+pub use encoding_rs;
+pub use enhanced::*;
 pub use error::*;
+pub use escape::*;
+pub use event::*;
+pub use file::*;
+pub use generate::*;
+#[cfg(feature = "aho-corasick")]
+pub use keyword::*;
+pub use length::*;
+#[cfg(feature = "lofty")]
+pub use lofty;
+pub use lookup::*;
+pub use metadata::*;
+#[cfg(feature = "krc")]
+pub use miniz_oxide;
+#[cfg(feature = "netease")]
+pub use netease::*;
 use once_cell::sync::Lazy;
+pub use parse::*;
+pub use push::*;
+#[cfg(feature = "regex-search")]
+pub use regex;
 use regex::Regex;
+#[cfg(feature = "rkyv")]
+pub use rkyv;
+pub use search::*;
+#[cfg(feature = "serde")]
+pub use serde;
+#[cfg(feature = "netease")]
+pub use serde_json;
+pub use serialize::*;
+pub use shift::*;
+pub use srt::*;
+#[cfg(feature = "subparse")]
+pub use subparse;
 pub use tags::*;
+pub use text_match::*;
+pub use timestamp::*;
+#[cfg(feature = "tokio")]
+pub use tokio;
+pub use uslt::*;
+pub use validate::*;
+
+/// The text of a timed line, shared by the time tags of the same line.
+#[cfg(not(any(feature = "compact-lines", feature = "arc-lines", feature = "box-lines")))]
+pub type LineText = std::rc::Rc<str>;
+
+/// The text of a timed line, shared by the time tags of the same line across threads.
+#[cfg(all(feature = "arc-lines", not(feature = "compact-lines")))]
+pub type LineText = std::sync::Arc<str>;
+
+/// The text of a timed line, owned by each time tag of the same line.
+#[cfg(all(feature = "box-lines", not(any(feature = "compact-lines", feature = "arc-lines"))))]
+pub type LineText = Box<str>;
+
+/// The text of a timed line. Short lines are stored inline without heap allocation.
+#[cfg(feature = "compact-lines")]
+pub type LineText = compact_str::CompactString;
 
 static LYRICS_RE: Lazy<Regex> = Lazy::new(|| Regex::new("^[^\x00-\x08\x0A-\x1F\x7F]*$").unwrap());
 static TAG_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[.*:.*\]").unwrap());
-static LINE_STARTS_WITH_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new("^\\[([^\x00-\x08\x0A-\x1F\x7F\\[\\]:]*):([^\x00-\x08\x0A-\x1F\x7F\\[\\]]*)\\]")
-        .unwrap()
-});
+
+/// Whether `text` can be added as the text of a line, which means it contains no control characters and nothing like a tag.
+#[inline]
+pub fn is_valid_line_text<S: AsRef<str>>(text: S) -> bool {
+    check_line(text).is_ok()
+}
 
 fn check_line<S: AsRef<str>>(line: S) -> Result<(), LyricsError> {
     let line = line.as_ref();
@@ -77,7 +210,7 @@ fn check_line<S: AsRef<str>>(line: S) -> Result<(), LyricsError> {
         return Err(LyricsError::FormatError("Incorrect lyrics."));
     }
 
-    if TAG_RE.is_match(line) {
+    if may_contain_tag(line) && TAG_RE.is_match(&remove_escapes(line)) {
         return Err(LyricsError::FormatError("Lyrics contain tags."));
     }
 
@@ -87,66 +220,179 @@ fn check_line<S: AsRef<str>>(line: S) -> Result<(), LyricsError> {
 #[derive(Debug, Clone, Educe)]
 #[educe(Default(new))]
 pub struct Lyrics {
-    /// Metadata about this lyrics.
-    pub metadata: BTreeSet<IDTag>,
-    timed_lines:  Vec<(TimeTag, Rc<str>)>,
-    lines:        Vec<String>,
+    metadata:        Metadata,
+    comments:        Vec<Comment>,
+    timed_lines:     Vec<(TimeTag, LineText)>,
+    lines:           Vec<String>,
+    /// The ID of the group of time tags which were added together for the same line, for each timed line. A group is dissolved when any of its lines is removed.
+    line_groups:     Vec<Option<usize>>,
+    /// The ID of the next group.
+    next_line_group: usize,
 }
 
 impl Lyrics {
     /// Create a `Lyrics` instance with a string.
     #[allow(clippy::should_implement_trait)]
+    #[inline]
     pub fn from_str<S: AsRef<str>>(s: S) -> Result<Lyrics, LyricsError> {
+        Self::from_str_with_options(s, &ParseOptions::default())
+    }
+
+    /// Create a `Lyrics` instance with a string and options.
+    #[inline]
+    pub fn from_str_with_options<S: AsRef<str>>(
+        s: S,
+        options: &ParseOptions,
+    ) -> Result<Lyrics, LyricsError> {
+        Self::from_str_with_warnings(s, options).map(|(lyrics, _)| lyrics)
+    }
+
+    /// Create a `Lyrics` instance with a string and options. The warnings reported while parsing are returned along with the lyrics.
+    pub fn from_str_with_warnings<S: AsRef<str>>(
+        s: S,
+        options: &ParseOptions,
+    ) -> Result<(Lyrics, Vec<ParseWarning>), LyricsError> {
+        let mut lyrics = Lyrics::with_parse_options(options);
+        let mut warnings = Vec::new();
+
+        let s = strip_bom(s.as_ref());
+
+        options.limits.check_bytes(s.len())?;
+
+        for (i, line) in split_lines(s).enumerate() {
+            lyrics.parse_source_line(line, i + 1, options, &mut warnings)?;
+        }
+
+        lyrics.finish_parsing(options);
+
+        Ok((lyrics, warnings))
+    }
+
+    /// Create a `Lyrics` instance with a string. Lines which cannot be parsed are skipped instead of aborting, and the errors of them are returned along with the lyrics.
+    pub fn from_str_partial<S: AsRef<str>>(s: S) -> (Lyrics, Vec<LineError>) {
         let mut lyrics: Lyrics = Lyrics::new();
-        let s = s.as_ref();
+        let mut errors = Vec::new();
+        let options = ParseOptions::default();
+        let s = strip_bom(s.as_ref());
+
+        for (i, line) in split_lines(s).enumerate() {
+            if let Err(error) =
+                lyrics.parse_line_inner(line, Some(i + 1), &options, &mut Vec::new())
+            {
+                errors.push(LineError {
+                    line_number: i + 1,
+                    error,
+                });
+            }
+        }
 
-        let lines: Vec<&str> = s.split('\n').collect();
+        (lyrics, errors)
+    }
 
-        for line in lines {
-            let mut time_tags: Vec<TimeTag> = Vec::new();
-            let mut has_id_tag = false;
+    /// Parse a line with the same rules as `from_str` and add its content to this lyrics. Nothing is added if the line is incorrect.
+    pub fn parse_line<S: AsRef<str>>(&mut self, line: S) -> Result<LineOutcome, LyricsError> {
+        let line = line.as_ref();
 
-            let mut line = line.trim();
+        if line.contains('\n') {
+            return Err(LyricsError::FormatError("The line contains a newline."));
+        }
 
-            while let Some(c) = LINE_STARTS_WITH_RE.captures(line) {
-                let tag = c.get(0).unwrap().as_str();
-                let tag_len = tag.len();
+        self.parse_line_inner(line, None, &ParseOptions::default(), &mut Vec::new())
+    }
 
-                match TimeTag::from_str(tag) {
-                    Ok(time_tag) => {
-                        time_tags.push(time_tag);
-                    },
-                    Err(_) => {
-                        let label = c.get(1).unwrap().as_str().trim();
+    /// Create an empty `Lyrics` instance which is ready to be filled by parsing with options.
+    pub(crate) fn with_parse_options(options: &ParseOptions) -> Lyrics {
+        let mut lyrics = Lyrics::new();
 
-                        if label.is_empty() {
-                            // A comment tag, usually in the format [:] ignores the characters after it.
-                            line = "";
-                            break;
-                        }
+        lyrics.metadata.set_duplicate_policy(options.duplicate_id_tags);
+        lyrics.metadata.set_order(options.metadata_order);
 
-                        let text = c.get(2).unwrap().as_str().trim();
+        lyrics
+    }
 
-                        has_id_tag = true;
-                        lyrics
-                            .metadata
-                            .insert(unsafe { IDTag::from_string_unchecked(label, text) });
-                    },
-                }
+    /// Parse a line of the source with options, checking the limits and skipping the line if it is invalid and the options allow.
+    pub(crate) fn parse_source_line(
+        &mut self,
+        line: &str,
+        line_number: usize,
+        options: &ParseOptions,
+        warnings: &mut Vec<ParseWarning>,
+    ) -> Result<(), LyricsError> {
+        options.limits.check_line(line_number, line)?;
+
+        match self.parse_line_inner(line, Some(line_number), options, warnings) {
+            Ok(_) => Ok(()),
+            Err(_) if options.skip_invalid_lines => {
+                warnings.push(ParseWarning {
+                    line_number,
+                    kind: ParseWarningKind::SkippedLine,
+                });
+
+                Ok(())
+            },
+            Err(error) => Err(error),
+        }
+    }
 
-                line = line[tag_len..].trim_start();
-            }
+    /// Apply the options which take effect after all lines are parsed.
+    #[allow(unused_variables)]
+    pub(crate) fn finish_parsing(&mut self, options: &ParseOptions) {
+        #[cfg(feature = "unicode-normalization")]
+        if options.nfc {
+            self.normalize_nfc();
+        }
+    }
+
+    /// Parse a line and add its content. Nothing is added if the line is incorrect. `line_number` is `None` if the line does not come from a source.
+    fn parse_line_inner(
+        &mut self,
+        line: &str,
+        line_number: Option<usize>,
+        options: &ParseOptions,
+        warnings: &mut Vec<ParseWarning>,
+    ) -> Result<LineOutcome, LyricsError> {
+        let parsed = ParsedLine::parse(line, line_number, options, warnings)?;
+
+        let mut outcome = LineOutcome::default();
+
+        if parsed.id_tags.is_empty() || !parsed.time_tags.is_empty() {
+            self.add_line_with_multiple_time_tags(&parsed.time_tags, parsed.text)?;
+
+            outcome.timed_lines = parsed.time_tags.len();
+            outcome.plain_line = parsed.time_tags.is_empty();
+        }
 
-            if !has_id_tag || !time_tags.is_empty() {
-                lyrics.add_line_with_multiple_time_tags(&time_tags, line)?;
+        for id_tag in parsed.id_tags {
+            if self.metadata.insert(id_tag) {
+                outcome.id_tags += 1;
             }
         }
 
-        Ok(lyrics)
+        if let Some(comment) = parsed.comment {
+            self.comments.push(comment);
+
+            outcome.comment = true;
+        }
+
+        Ok(outcome)
     }
 }
 
 impl Lyrics {
+    /// Add a comment which will be written in the format **[:] text**.
+    #[inline]
+    pub fn add_comment<S: Into<String>>(&mut self, text: S) -> Result<(), LyricsError> {
+        let text = text.into();
+
+        if !LYRICS_RE.is_match(&text) {
+            return Err(LyricsError::FormatError("Incorrect comment."));
+        }
+
+        self.comments.push(Comment::new(None, text.trim()));
+
+        Ok(())
+    }
+
     #[inline]
     pub fn add_line<S: Into<String>>(&mut self, line: S) -> Result<(), LyricsError> {
         let line = line.into();
@@ -158,6 +404,7 @@ impl Lyrics {
         Ok(())
     }
 
+    /// Add a timed line, keeping the timed lines in chronological order. Adding lines in chronological order is the fast path, which only appends. Lines with the same time tag keep the order they were added in.
     #[inline]
     pub fn add_timed_line<S: Into<String>>(
         &mut self,
@@ -175,6 +422,38 @@ impl Lyrics {
         Ok(())
     }
 
+    /// Add many timed lines at once, sorting the timed lines only once at the end instead of inserting every line at its position. Nothing is added if any of the lines is incorrect.
+    pub fn extend_timed_lines<S: Into<String>, I: IntoIterator<Item = (TimeTag, S)>>(
+        &mut self,
+        timed_lines: I,
+    ) -> Result<(), LyricsError> {
+        let timed_lines = timed_lines
+            .into_iter()
+            .map(|(time_tag, line)| {
+                let line = line.into();
+
+                check_line(&line)?;
+
+                Ok((time_tag, line.into()))
+            })
+            .collect::<Result<Vec<(TimeTag, LineText)>, LyricsError>>()?;
+
+        let len = timed_lines.len();
+
+        self.timed_lines.extend(timed_lines);
+        self.line_groups.extend(std::iter::repeat(None).take(len));
+
+        let mut entries: Vec<_> =
+            self.timed_lines.drain(..).zip(self.line_groups.drain(..)).collect();
+
+        // the sort is stable, so lines with the same time tag keep the order they were added in
+        entries.sort_by_key(|((time_tag, _), _)| *time_tag);
+
+        (self.timed_lines, self.line_groups) = entries.into_iter().unzip();
+
+        Ok(())
+    }
+
     pub fn add_line_with_multiple_time_tags<S: Into<String>>(
         &mut self,
         time_tags: &[TimeTag],
@@ -189,18 +468,26 @@ impl Lyrics {
         if len == 0 {
             self.lines.push(line);
         } else {
-            let line: Rc<str> = line.into();
+            let line: LineText = line.into();
+
+            let group = if len > 1 {
+                self.next_line_group += 1;
+
+                Some(self.next_line_group - 1)
+            } else {
+                None
+            };
 
             let len_dec = len - 1;
 
             for time_tag in time_tags.iter().copied().take(len_dec) {
                 unsafe {
-                    self.add_timed_line_unchecked(time_tag, line.clone());
+                    self.insert_timed_line_unchecked(time_tag, line.clone(), group);
                 }
             }
 
             unsafe {
-                self.add_timed_line_unchecked(time_tags[len_dec], line);
+                self.insert_timed_line_unchecked(time_tags[len_dec], line, group);
             }
         }
 
@@ -208,121 +495,137 @@ impl Lyrics {
     }
 
     #[inline]
-    unsafe fn add_timed_line_unchecked(&mut self, time_tag: TimeTag, line: Rc<str>) {
-        let mut insert_index = self.timed_lines.len();
+    pub(crate) unsafe fn add_timed_line_unchecked(&mut self, time_tag: TimeTag, line: LineText) {
+        self.insert_timed_line_unchecked(time_tag, line, None)
+    }
+
+    #[inline]
+    unsafe fn insert_timed_line_unchecked(
+        &mut self,
+        time_tag: TimeTag,
+        line: LineText,
+        group: Option<usize>,
+    ) {
+        match self.timed_lines.last() {
+            Some((last, _)) if last > &time_tag => {
+                let insert_index = self.timed_lines.partition_point(|(t, _)| t <= &time_tag);
+
+                self.timed_lines.insert(insert_index, (time_tag, line));
+                self.line_groups.insert(insert_index, group);
+            },
+            _ => {
+                self.timed_lines.push((time_tag, line));
+                self.line_groups.push(group);
+            },
+        }
+    }
 
-        while insert_index > 0 {
-            insert_index -= 1;
+    /// Keep only the timed lines for which `f` returns `true`, dissolving the groups of the removed lines.
+    pub(crate) fn retain_timed_lines<F: FnMut(&(TimeTag, LineText)) -> bool>(&mut self, mut f: F) {
+        let mut removed_groups = Vec::new();
 
-            let temp = &self.timed_lines[insert_index].0;
+        let entries: Vec<_> = self.timed_lines.drain(..).zip(self.line_groups.drain(..)).collect();
 
-            if temp <= &time_tag {
-                insert_index += 1;
-                break;
-            }
+        (self.timed_lines, self.line_groups) = entries
+            .into_iter()
+            .filter(|(entry, group)| {
+                let keep = f(entry);
+
+                if !keep {
+                    removed_groups.extend(*group);
+                }
+
+                keep
+            })
+            .unzip();
+
+        self.dissolve_line_groups(&removed_groups);
+    }
+
+    /// Make the lines of `groups` no longer belong to any group, so that they are written separately.
+    fn dissolve_line_groups(&mut self, groups: &[usize]) {
+        if groups.is_empty() {
+            return;
         }
 
-        self.timed_lines.insert(insert_index, (time_tag, line));
+        for group in self.line_groups.iter_mut() {
+            if matches!(group, Some(id) if groups.contains(id)) {
+                *group = None;
+            }
+        }
     }
 }
 
 impl Lyrics {
+    /// Get the metadata about this lyrics.
+    #[inline]
+    pub fn get_metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Get the metadata about this lyrics for modification.
+    #[inline]
+    pub fn get_metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Get the comments in the order they were parsed or added. Only their order and line numbers are kept, not their positions among the other lines. See `CommentPlacement`.
+    #[inline]
+    pub fn get_comments(&self) -> &[Comment] {
+        &self.comments
+    }
+
     #[inline]
     pub fn get_lines(&self) -> &[String] {
         &self.lines
     }
 
     #[inline]
-    pub fn get_timed_lines(&self) -> &[(TimeTag, Rc<str>)] {
+    pub fn get_timed_lines(&self) -> &[(TimeTag, LineText)] {
         &self.timed_lines
     }
 
+    #[inline]
+    pub fn remove_comment(&mut self, index: usize) -> Comment {
+        self.comments.remove(index)
+    }
+
     #[inline]
     pub fn remove_line(&mut self, index: usize) -> String {
         self.lines.remove(index)
     }
 
     #[inline]
-    pub fn remove_timed_line(&mut self, index: usize) -> (TimeTag, Rc<str>) {
-        self.timed_lines.remove(index)
+    pub fn remove_timed_line(&mut self, index: usize) -> (TimeTag, LineText) {
+        let timed_line = self.timed_lines.remove(index);
+
+        if let Some(group) = self.line_groups.remove(index) {
+            self.dissolve_line_groups(&[group]);
+        }
+
+        timed_line
     }
 
     #[inline]
     pub fn find_timed_line_index<N: Into<i64>>(&self, timestamp: N) -> Option<usize> {
-        let target_time_tag = TimeTag::new(timestamp);
+        self.find_timed_line_index_with_boundary(timestamp, Boundary::Inclusive)
+    }
 
-        for (i, (time_tag, _)) in self.timed_lines.iter().enumerate().rev() {
-            if target_time_tag >= *time_tag {
-                return Some(i);
-            }
-        }
+    /// Find the timed line which should be on screen at `timestamp`, which is the last one beginning at or before it.
+    #[inline]
+    pub fn find_timed_line<N: Into<i64>>(&self, timestamp: N) -> Option<(&TimeTag, &str)> {
+        self.find_timed_line_index(timestamp).map(|index| {
+            let (time_tag, line) = &self.timed_lines[index];
 
-        None
+            (time_tag, line.as_ref())
+        })
     }
 }
 
 impl Display for Lyrics {
+    #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        let metadata_not_empty = !self.metadata.is_empty();
-        let timed_lines_not_empty = !self.timed_lines.is_empty();
-        let lines_not_empty = !self.lines.is_empty();
-
-        if metadata_not_empty {
-            let mut iter = self.metadata.iter();
-
-            Display::fmt(iter.next().unwrap(), f)?;
-
-            for id_tag in iter {
-                f.write_char('\n')?;
-                Display::fmt(id_tag, f)?;
-            }
-        }
-
-        if timed_lines_not_empty {
-            if metadata_not_empty {
-                f.write_char('\n')?;
-                f.write_char('\n')?;
-            }
-
-            let mut iter = self.timed_lines.iter();
-
-            let (time_tag, line) = iter.next().unwrap();
-
-            Display::fmt(time_tag, f)?;
-            f.write_str(line)?;
-
-            for (time_tag, line) in iter {
-                f.write_char('\n')?;
-                Display::fmt(time_tag, f)?;
-                f.write_str(line)?;
-            }
-        }
-
-        if lines_not_empty {
-            let mut buffer = String::new();
-
-            let mut iter = self.lines.iter();
-
-            buffer.push_str(iter.next().unwrap());
-
-            for line in iter {
-                buffer.push('\n');
-                buffer.push_str(line);
-            }
-
-            let s = buffer.trim();
-
-            if !s.is_empty() {
-                if metadata_not_empty || timed_lines_not_empty {
-                    f.write_char('\n')?;
-                    f.write_char('\n')?;
-                }
-
-                f.write_str(s)?;
-            }
-        }
-
-        Ok(())
+        self.write_with_options(f, &SerializeOptions::default())
     }
 }
 
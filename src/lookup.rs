@@ -0,0 +1,217 @@
+use std::cmp::Ordering;
+
+use crate::{Lyrics, TimeTag};
+
+/// Which line is found when a timestamp is exactly at the beginning of a timed line.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Educe)]
+#[educe(Default)]
+pub enum Boundary {
+    /// The line which begins at the timestamp is found.
+    #[educe(Default)]
+    Inclusive,
+    /// The line before the one which begins at the timestamp is found.
+    Exclusive,
+}
+
+/// A timed line found by a lookup, along with the time tag of the next timed line.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FoundLine<'a> {
+    /// The index of the timed line.
+    pub index:     usize,
+    pub time:      TimeTag,
+    pub text:      &'a str,
+    /// The time tag of the next timed line, which is when this line ends. `None` if this is the last one.
+    pub next_time: Option<TimeTag>,
+}
+
+/// An iterator over the timed lines with their start and end time tags, in chronological order. Created by `Lyrics::timed_line_spans`.
+#[derive(Debug, Clone)]
+pub struct TimedLineSpans<'a> {
+    lyrics:             &'a Lyrics,
+    last_line_duration: i64,
+    index:              usize,
+}
+
+impl<'a> Iterator for TimedLineSpans<'a> {
+    /// The start, the end and the text of a timed line.
+    type Item = (TimeTag, TimeTag, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (time_tag, line) = self.lyrics.timed_lines.get(self.index)?;
+
+        let end = self.lyrics.line_end_time(self.index, self.last_line_duration);
+
+        self.index += 1;
+
+        Some((*time_tag, TimeTag::new(end), line))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.lyrics.timed_lines.len() - self.index;
+
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for TimedLineSpans<'a> {}
+
+impl Lyrics {
+    /// Iterate over the timed lines with their start and end time tags. Each line ends when the next one begins. The last one ends at the `[length:]` tag, which is the length of the track, if it is after the line begins, or `last_line_duration` milliseconds after it begins.
+    #[inline]
+    pub fn timed_line_spans<N: Into<i64>>(&self, last_line_duration: N) -> TimedLineSpans<'_> {
+        TimedLineSpans {
+            lyrics:             self,
+            last_line_duration: last_line_duration.into(),
+            index:              0,
+        }
+    }
+
+    /// Get the timed line at `index` as a `FoundLine`.
+    #[inline]
+    pub fn get_found_line(&self, index: usize) -> Option<FoundLine<'_>> {
+        let (time, text) = self.timed_lines.get(index)?;
+
+        Some(FoundLine {
+            index,
+            time: *time,
+            text,
+            next_time: self.timed_lines.get(index + 1).map(|(time_tag, _)| *time_tag),
+        })
+    }
+
+    /// Find the timed line at `timestamp`.
+    #[inline]
+    pub fn find_line<N: Into<i64>>(&self, timestamp: N) -> Option<FoundLine<'_>> {
+        self.find_line_with_boundary(timestamp, Boundary::Inclusive)
+    }
+
+    /// Find the timed line at `timestamp`, treating an exact match according to `boundary`.
+    #[inline]
+    pub fn find_line_with_boundary<N: Into<i64>>(
+        &self,
+        timestamp: N,
+        boundary: Boundary,
+    ) -> Option<FoundLine<'_>> {
+        self.find_timed_line_index_with_boundary(timestamp, boundary)
+            .and_then(|index| self.get_found_line(index))
+    }
+
+    /// Find the timed line at `timestamp`, treating a timestamp at most `tolerance` milliseconds before the beginning of a timed line as at that line, for player clocks which jitter.
+    #[inline]
+    pub fn find_line_with_tolerance<N: Into<i64>, T: Into<i64>>(
+        &self,
+        timestamp: N,
+        tolerance: T,
+    ) -> Option<FoundLine<'_>> {
+        let target_time_tag = TimeTag::new(timestamp);
+        let tolerance = tolerance.into();
+
+        self.timed_lines
+            .iter()
+            .rposition(|(time_tag, _)| {
+                time_tag.cmp_with_tolerance(target_time_tag, tolerance) != Ordering::Greater
+            })
+            .and_then(|index| self.get_found_line(index))
+    }
+
+    /// Get the index of the timed line at `timestamp` and how far it has progressed, from 0.0 when it begins to 1.0 when the next one begins. The last line ends at the `[length:]` tag if it is after the line begins, or is treated as finished otherwise.
+    pub fn line_progress<N: Into<i64>>(&self, timestamp: N) -> Option<(usize, f64)> {
+        let timestamp = timestamp.into();
+
+        let index = self.find_timed_line_index(timestamp)?;
+
+        let start = self.timed_lines[index].0.get_timestamp();
+
+        let end = match self.timed_lines.get(index + 1) {
+            Some((next, _)) => next.get_timestamp(),
+            None => match self.length() {
+                Some(length) if length.get_timestamp() > start => length.get_timestamp(),
+                _ => return Some((index, 1.0)),
+            },
+        };
+
+        let progress = (timestamp as f64 - start as f64) / (end as f64 - start as f64);
+
+        Some((index, progress.clamp(0.0, 1.0)))
+    }
+
+    /// Get the first timed line which begins after `timestamp`, which is the upcoming line to pre-render.
+    #[inline]
+    pub fn next_timed_line_after<N: Into<i64>>(&self, timestamp: N) -> Option<(&TimeTag, &str)> {
+        let target_time_tag = TimeTag::new(timestamp);
+
+        let index = self.timed_lines.partition_point(|(time_tag, _)| time_tag <= &target_time_tag);
+
+        self.timed_lines.get(index).map(|(time_tag, line)| (time_tag, line.as_ref()))
+    }
+
+    /// Get the last timed line which begins before `timestamp`. Pass the time tag of the current line to get the line before it.
+    #[inline]
+    pub fn previous_timed_line_before<N: Into<i64>>(
+        &self,
+        timestamp: N,
+    ) -> Option<(&TimeTag, &str)> {
+        let target_time_tag = TimeTag::new(timestamp);
+
+        let index = self.timed_lines.partition_point(|(time_tag, _)| time_tag < &target_time_tag);
+
+        index
+            .checked_sub(1)
+            .map(|index| &self.timed_lines[index])
+            .map(|(time_tag, line)| (time_tag, line.as_ref()))
+    }
+
+    /// Find the index of the timed line which begins nearest to `timestamp`, before or after it. If two lines are equally near, the earlier one is found. `None` if there is no timed line, or the nearest one begins more than `max_distance` milliseconds away.
+    pub fn find_nearest_timed_line_index<N: Into<i64>>(
+        &self,
+        timestamp: N,
+        max_distance: Option<i64>,
+    ) -> Option<usize> {
+        let timestamp = timestamp.into();
+
+        let index = self.timed_lines.partition_point(|(t, _)| t.get_timestamp() < timestamp);
+
+        let distance = |index: usize| self.timed_lines[index].0.get_timestamp().abs_diff(timestamp);
+
+        let nearest = match (index.checked_sub(1), index < self.timed_lines.len()) {
+            (Some(before), true) => {
+                if distance(before) <= distance(index) {
+                    before
+                } else {
+                    index
+                }
+            },
+            (Some(before), false) => before,
+            (None, true) => index,
+            (None, false) => return None,
+        };
+
+        match max_distance {
+            Some(max_distance) if distance(nearest) > max_distance.max(0) as u64 => None,
+            _ => Some(nearest),
+        }
+    }
+
+    /// Find the index of the timed line at `timestamp`, treating an exact match according to `boundary`.
+    pub fn find_timed_line_index_with_boundary<N: Into<i64>>(
+        &self,
+        timestamp: N,
+        boundary: Boundary,
+    ) -> Option<usize> {
+        let target_time_tag = TimeTag::new(timestamp);
+
+        for (i, (time_tag, _)) in self.timed_lines.iter().enumerate().rev() {
+            let found = match boundary {
+                Boundary::Inclusive => target_time_tag >= *time_tag,
+                Boundary::Exclusive => target_time_tag > *time_tag,
+            };
+
+            if found {
+                return Some(i);
+            }
+        }
+
+        None
+    }
+}
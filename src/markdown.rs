@@ -0,0 +1,154 @@
+use std::fmt::{self, Write};
+
+use crate::{enhanced::word_segments, unescape_brackets, KnownTag, Lyrics, Rounding, TimeTag};
+
+/// Get the human-readable name of an ID tag label.
+fn label_name(label: &str) -> &str {
+    match KnownTag::from_label(label) {
+        Some(KnownTag::Ti) => "Title",
+        Some(KnownTag::Ar) => "Artist",
+        Some(KnownTag::Al) => "Album",
+        Some(KnownTag::Au) => "Author",
+        Some(KnownTag::By) => "Created by",
+        Some(KnownTag::Length) => "Length",
+        Some(KnownTag::Offset) => "Offset",
+        Some(KnownTag::Re) => "Editor",
+        Some(KnownTag::Ve) => "Version",
+        Some(KnownTag::La) => "Language",
+        None => label,
+    }
+}
+
+/// Write text with the characters which have meanings in Markdown escaped.
+fn write_markdown_text<W: Write>(f: &mut W, text: &str) -> Result<(), fmt::Error> {
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '#') {
+            f.write_char('\\')?;
+        }
+
+        f.write_char(c)?;
+    }
+
+    Ok(())
+}
+
+/// Write a time tag in the `m:ss.xx` format, or `h:mm:ss.xx` if it is an hour or longer.
+fn write_markdown_time<W: Write>(f: &mut W, time_tag: TimeTag) -> Result<(), fmt::Error> {
+    let timestamp = time_tag.round_to_hundredths(Rounding::HalfUp).get_timestamp();
+
+    if timestamp < 0 {
+        f.write_char('-')?;
+    }
+
+    let centiseconds = timestamp.unsigned_abs() / 10;
+
+    let hours = centiseconds / 360000;
+    let minutes = centiseconds / 6000 % 60;
+    let seconds = centiseconds / 100 % 60;
+
+    if hours > 0 {
+        f.write_fmt(format_args!(
+            "{}:{:02}:{:02}.{:02}",
+            hours,
+            minutes,
+            seconds,
+            centiseconds % 100
+        ))
+    } else {
+        f.write_fmt(format_args!("{}:{:02}.{:02}", minutes, seconds, centiseconds % 100))
+    }
+}
+
+impl Lyrics {
+    /// Export the lyrics to Markdown. The title becomes a heading and the other ID tags become a list, followed by a table of the timed lines and then the plain lines. Word time tags are removed, and timed lines without text, which only clear the screen, are skipped.
+    pub fn to_markdown(&self) -> String {
+        let mut s = String::new();
+
+        self.write_markdown(&mut s).unwrap();
+
+        s
+    }
+
+    fn write_markdown<W: Write>(&self, f: &mut W) -> Result<(), fmt::Error> {
+        let mut blocks = 0;
+
+        if let Some(title) = self.title() {
+            f.write_str("# ")?;
+            write_markdown_text(f, title)?;
+            f.write_char('\n')?;
+
+            blocks += 1;
+        }
+
+        let mut id_tags = self
+            .metadata
+            .iter()
+            .filter(|id_tag| KnownTag::from_label(id_tag.get_label()) != Some(KnownTag::Ti))
+            .peekable();
+
+        if id_tags.peek().is_some() {
+            if blocks > 0 {
+                f.write_char('\n')?;
+            }
+
+            for id_tag in id_tags {
+                f.write_str("- **")?;
+                write_markdown_text(f, label_name(id_tag.get_label()))?;
+                f.write_str(":** ")?;
+                write_markdown_text(f, id_tag.get_text())?;
+                f.write_char('\n')?;
+            }
+
+            blocks += 1;
+        }
+
+        let mut timed_lines = self
+            .timed_lines
+            .iter()
+            .map(|(time_tag, line)| {
+                let text: String =
+                    word_segments(line).into_iter().map(|segment| segment.text).collect();
+
+                (*time_tag, text)
+            })
+            .filter(|(_, text)| !text.trim().is_empty())
+            .peekable();
+
+        if timed_lines.peek().is_some() {
+            if blocks > 0 {
+                f.write_char('\n')?;
+            }
+
+            f.write_str("| Time | Lyrics |\n| ---: | :--- |\n")?;
+
+            for (time_tag, text) in timed_lines {
+                f.write_str("| ")?;
+                write_markdown_time(f, time_tag)?;
+                f.write_str(" | ")?;
+                write_markdown_text(f, unescape_brackets(&text).trim())?;
+                f.write_str(" |\n")?;
+            }
+
+            blocks += 1;
+        }
+
+        if !self.lines.is_empty() {
+            if blocks > 0 {
+                f.write_char('\n')?;
+            }
+
+            for (i, line) in self.lines.iter().enumerate() {
+                if i > 0 {
+                    // a hard line break
+                    f.write_str("  \n")?;
+                }
+
+                write_markdown_text(f, &unescape_brackets(line))?;
+            }
+
+            f.write_char('\n')?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,359 @@
+use std::slice::Iter;
+
+use unicase::UniCase;
+
+use crate::{IDTag, KnownTag, Lyrics, LyricsError};
+
+/// How to deal with an ID tag whose label already exists in the metadata.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Educe)]
+#[educe(Default)]
+pub enum DuplicateIDTagPolicy {
+    /// Keep all the ID tags with the same label.
+    #[educe(Default)]
+    Keep,
+    /// Keep only the first ID tag and ignore the later ones.
+    KeepFirst,
+    /// Keep only the last ID tag, replacing the earlier ones.
+    KeepLast,
+}
+
+/// The order of the ID tags in the metadata.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Educe)]
+#[educe(Default)]
+pub enum MetadataOrder {
+    /// Sort the ID tags by their labels.
+    #[educe(Default)]
+    Sorted,
+    /// Keep the order in which the ID tags are inserted, which is the order they appear in the source when parsing.
+    Insertion,
+}
+
+/// ID tags of lyrics. ID tags with the same label are allowed and kept in the order they are inserted.
+#[derive(Debug, Clone, Default)]
+pub struct Metadata {
+    id_tags:          Vec<IDTag>,
+    duplicate_policy: DuplicateIDTagPolicy,
+    order:            MetadataOrder,
+}
+
+impl Metadata {
+    #[inline]
+    pub fn new() -> Metadata {
+        Metadata::default()
+    }
+
+    /// Create an empty `Metadata` instance which deals with duplicate labels according to `policy`.
+    #[inline]
+    pub fn with_duplicate_policy(policy: DuplicateIDTagPolicy) -> Metadata {
+        Metadata {
+            duplicate_policy: policy,
+            ..Metadata::default()
+        }
+    }
+
+    /// Create an empty `Metadata` instance which keeps the ID tags in `order`.
+    #[inline]
+    pub fn with_order(order: MetadataOrder) -> Metadata {
+        Metadata {
+            order,
+            ..Metadata::default()
+        }
+    }
+
+    #[inline]
+    pub fn duplicate_policy(&self) -> DuplicateIDTagPolicy {
+        self.duplicate_policy
+    }
+
+    /// Change the policy for duplicate labels. The existing ID tags are deduplicated according to the new policy.
+    #[inline]
+    pub fn set_duplicate_policy(&mut self, policy: DuplicateIDTagPolicy) {
+        self.duplicate_policy = policy;
+
+        self.dedup(policy);
+    }
+
+    #[inline]
+    pub fn order(&self) -> MetadataOrder {
+        self.order
+    }
+
+    /// Change the order of the ID tags. The existing ID tags are sorted if the new order is `Sorted`, and are kept as they are if it is `Insertion`.
+    #[inline]
+    pub fn set_order(&mut self, order: MetadataOrder) {
+        self.order = order;
+
+        if order == MetadataOrder::Sorted {
+            self.id_tags.sort();
+        }
+    }
+
+    /// Remove the ID tags whose labels are duplicated according to `policy`.
+    pub fn dedup(&mut self, policy: DuplicateIDTagPolicy) {
+        let mut seen: Vec<UniCase<String>> = Vec::new();
+
+        let mut keep = |id_tag: &IDTag| {
+            let label = UniCase::new(id_tag.get_label().to_string());
+
+            if seen.contains(&label) {
+                false
+            } else {
+                seen.push(label);
+
+                true
+            }
+        };
+
+        match policy {
+            DuplicateIDTagPolicy::Keep => (),
+            DuplicateIDTagPolicy::KeepFirst => self.id_tags.retain(keep),
+            DuplicateIDTagPolicy::KeepLast => {
+                self.id_tags.reverse();
+                self.id_tags.retain(|id_tag| keep(id_tag));
+                self.id_tags.reverse();
+            },
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.id_tags.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.id_tags.is_empty()
+    }
+
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, IDTag> {
+        self.id_tags.iter()
+    }
+
+    /// Iterate the ID tags mutably. The labels must not be changed.
+    #[cfg(feature = "unicode-normalization")]
+    #[inline]
+    pub(crate) fn iter_mut(&mut self) -> std::slice::IterMut<'_, IDTag> {
+        self.id_tags.iter_mut()
+    }
+
+    /// Add an ID tag according to the duplicate policy. Returns `false` if the ID tag is ignored.
+    pub fn insert(&mut self, id_tag: IDTag) -> bool {
+        match self.duplicate_policy {
+            DuplicateIDTagPolicy::Keep => self.insert_inner(id_tag),
+            DuplicateIDTagPolicy::KeepFirst => {
+                if self.contains(id_tag.get_label()) {
+                    return false;
+                }
+
+                self.insert_inner(id_tag);
+            },
+            DuplicateIDTagPolicy::KeepLast => {
+                self.replace(id_tag);
+            },
+        }
+
+        true
+    }
+
+    /// Add an ID tag and remove all the other ID tags with the same label. The new ID tag takes the place of the first removed one, which is returned.
+    pub fn replace(&mut self, id_tag: IDTag) -> Option<IDTag> {
+        match self.id_tags.iter().position(|t| t == &id_tag) {
+            Some(index) => {
+                let old = std::mem::replace(&mut self.id_tags[index], id_tag);
+
+                let mut i = 0;
+
+                self.id_tags.retain(|t| {
+                    i += 1;
+
+                    i - 1 == index || t != &old
+                });
+
+                Some(old)
+            },
+            None => {
+                self.insert_inner(id_tag);
+
+                None
+            },
+        }
+    }
+
+    /// Get the first ID tag with the label.
+    #[inline]
+    pub fn get<L: AsRef<str>>(&self, label: L) -> Option<&IDTag> {
+        self.get_all(label).next()
+    }
+
+    /// Get all the ID tags with the label.
+    #[inline]
+    pub fn get_all<L: AsRef<str>>(&self, label: L) -> impl Iterator<Item = &IDTag> {
+        let label = UniCase::new(label.as_ref().trim().to_string());
+
+        self.id_tags.iter().filter(move |id_tag| UniCase::new(id_tag.get_label()) == label)
+    }
+
+    #[inline]
+    pub fn contains<L: AsRef<str>>(&self, label: L) -> bool {
+        self.get(label).is_some()
+    }
+
+    /// Remove all the ID tags with the label.
+    pub fn remove<L: AsRef<str>>(&mut self, label: L) -> Vec<IDTag> {
+        let label = UniCase::new(label.as_ref().trim());
+
+        let mut removed = Vec::new();
+        let mut kept = Vec::with_capacity(self.id_tags.len());
+
+        for id_tag in self.id_tags.drain(..) {
+            if UniCase::new(id_tag.get_label()) == label {
+                removed.push(id_tag);
+            } else {
+                kept.push(id_tag);
+            }
+        }
+
+        self.id_tags = kept;
+
+        removed
+    }
+
+    #[inline]
+    pub fn retain<F: FnMut(&IDTag) -> bool>(&mut self, f: F) {
+        self.id_tags.retain(f)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.id_tags.clear()
+    }
+
+    /// Add an ID tag at the position given by the order.
+    fn insert_inner(&mut self, id_tag: IDTag) {
+        match self.order {
+            MetadataOrder::Sorted => {
+                let index = self.id_tags.partition_point(|t| t <= &id_tag);
+
+                self.id_tags.insert(index, id_tag);
+            },
+            MetadataOrder::Insertion => self.id_tags.push(id_tag),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Metadata {
+    type IntoIter = Iter<'a, IDTag>;
+    type Item = &'a IDTag;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Extend<IDTag> for Metadata {
+    #[inline]
+    fn extend<I: IntoIterator<Item = IDTag>>(&mut self, iter: I) {
+        for id_tag in iter {
+            self.insert(id_tag);
+        }
+    }
+}
+
+impl FromIterator<IDTag> for Metadata {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = IDTag>>(iter: I) -> Self {
+        let mut metadata = Metadata::new();
+
+        metadata.extend(iter);
+
+        metadata
+    }
+}
+
+impl Lyrics {
+    /// Get the text of the ID tag whose label is `tag`.
+    #[inline]
+    pub fn get_tag(&self, tag: KnownTag) -> Option<&str> {
+        self.metadata.get(tag).map(|id_tag| id_tag.get_text())
+    }
+
+    /// Set the text of the ID tag whose label is `tag`, replacing the existing ones.
+    #[inline]
+    pub fn set_tag<T: Into<String>>(&mut self, tag: KnownTag, text: T) -> Result<(), LyricsError> {
+        let id_tag = IDTag::from_string(tag.as_str(), text)?;
+
+        self.metadata.replace(id_tag);
+
+        Ok(())
+    }
+
+    /// Remove the ID tags whose label is `tag`.
+    #[inline]
+    pub fn remove_tag(&mut self, tag: KnownTag) {
+        self.metadata.remove(tag);
+    }
+}
+
+impl Lyrics {
+    /// Get the title of the song from the `[ti:]` tag.
+    #[inline]
+    pub fn title(&self) -> Option<&str> {
+        self.get_tag(KnownTag::Ti)
+    }
+
+    /// Get the artist performing the song from the `[ar:]` tag.
+    #[inline]
+    pub fn artist(&self) -> Option<&str> {
+        self.get_tag(KnownTag::Ar)
+    }
+
+    /// Get the album from the `[al:]` tag.
+    #[inline]
+    pub fn album(&self) -> Option<&str> {
+        self.get_tag(KnownTag::Al)
+    }
+
+    /// Get the author of the song text from the `[au:]` tag.
+    #[inline]
+    pub fn author(&self) -> Option<&str> {
+        self.get_tag(KnownTag::Au)
+    }
+
+    /// Get the creator of the LRC file from the `[by:]` tag.
+    #[inline]
+    pub fn creator(&self) -> Option<&str> {
+        self.get_tag(KnownTag::By)
+    }
+
+    /// Set the `[ti:]` tag.
+    #[inline]
+    pub fn set_title<T: Into<String>>(&mut self, title: T) -> Result<(), LyricsError> {
+        self.set_tag(KnownTag::Ti, title)
+    }
+
+    /// Set the `[ar:]` tag.
+    #[inline]
+    pub fn set_artist<T: Into<String>>(&mut self, artist: T) -> Result<(), LyricsError> {
+        self.set_tag(KnownTag::Ar, artist)
+    }
+
+    /// Set the `[al:]` tag.
+    #[inline]
+    pub fn set_album<T: Into<String>>(&mut self, album: T) -> Result<(), LyricsError> {
+        self.set_tag(KnownTag::Al, album)
+    }
+
+    /// Set the `[au:]` tag.
+    #[inline]
+    pub fn set_author<T: Into<String>>(&mut self, author: T) -> Result<(), LyricsError> {
+        self.set_tag(KnownTag::Au, author)
+    }
+
+    /// Set the `[by:]` tag.
+    #[inline]
+    pub fn set_creator<T: Into<String>>(&mut self, creator: T) -> Result<(), LyricsError> {
+        self.set_tag(KnownTag::By, creator)
+    }
+}
@@ -0,0 +1,80 @@
+use serde_json::Value;
+
+use crate::{
+    convert::{parse_credit_line, LyricsFormat, Yrc},
+    split_lines, BilingualLyrics, Lyrics, LyricsError,
+};
+
+/// The lyrics of a song returned by the lyric API of NetEase Cloud Music, which is a JSON object like `{"lrc":{"lyric":"..."},"tlyric":{"lyric":"..."},"romalrc":{"lyric":"..."},"yrc":{"lyric":"..."}}`.
+#[derive(Debug, Clone, Default)]
+pub struct NeteaseLyrics {
+    /// The line-timed lyrics, from `lrc`.
+    pub lyrics:       Lyrics,
+    /// The word-timed lyrics, from `yrc`.
+    pub word_timed:   Option<Lyrics>,
+    /// The translated lyrics, from `tlyric`.
+    pub translation:  Option<Lyrics>,
+    /// The romanized lyrics, from `romalrc`.
+    pub romanization: Option<Lyrics>,
+}
+
+/// Parse an LRC body of NetEase Cloud Music, whose credit lines are in JSON.
+fn parse_lrc(s: &str) -> Result<Lyrics, LyricsError> {
+    let mut lyrics = Lyrics::new();
+
+    for line in split_lines(s) {
+        let line = line.trim();
+
+        if !parse_credit_line(&mut lyrics, line)? {
+            lyrics.parse_line(line)?;
+        }
+    }
+
+    Ok(lyrics)
+}
+
+/// Get the non-empty `lyric` string of a lyric body, like `{"lyric":"..."}`.
+#[inline]
+fn get_body<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value
+        .get(key)
+        .and_then(|body| body.get("lyric"))
+        .and_then(Value::as_str)
+        .filter(|s| !s.trim().is_empty())
+}
+
+impl NeteaseLyrics {
+    /// Parse the JSON payload of the lyric API of NetEase Cloud Music. Missing or empty lyric bodies are `None`, and `lyrics` is empty if the song has no line-timed lyrics.
+    pub fn from_json<S: AsRef<str>>(s: S) -> Result<NeteaseLyrics, LyricsError> {
+        let value: Value =
+            serde_json::from_str(s.as_ref()).map_err(|e| LyricsError::ParseError(e.to_string()))?;
+
+        if !value.is_object() {
+            return Err(LyricsError::FormatError("The payload is not a JSON object."));
+        }
+
+        let lyrics = match get_body(&value, "lrc") {
+            Some(body) => parse_lrc(body)?,
+            None => Lyrics::new(),
+        };
+
+        let word_timed = get_body(&value, "yrc").map(Yrc::parse).transpose()?;
+        let translation = get_body(&value, "tlyric").map(parse_lrc).transpose()?;
+        let romanization = get_body(&value, "romalrc").map(parse_lrc).transpose()?;
+
+        Ok(NeteaseLyrics {
+            lyrics,
+            word_timed,
+            translation,
+            romanization,
+        })
+    }
+
+    /// Pair the line-timed lyrics with the translation, if there is one.
+    #[inline]
+    pub fn bilingual(&self) -> Option<BilingualLyrics> {
+        self.translation
+            .as_ref()
+            .map(|translation| BilingualLyrics::new(self.lyrics.clone(), translation.clone()))
+    }
+}
@@ -0,0 +1,38 @@
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+use crate::{Comment, IDTag, Lyrics};
+
+impl Lyrics {
+    /// Convert the texts of lines, comments and ID tags into Unicode Normalization Form C.
+    pub fn normalize_nfc(&mut self) {
+        for (_, line) in self.timed_lines.iter_mut() {
+            if !is_nfc(line) {
+                *line = line.nfc().collect::<String>().into();
+            }
+        }
+
+        for line in self.lines.iter_mut() {
+            if !is_nfc(line) {
+                *line = line.nfc().collect();
+            }
+        }
+
+        for comment in self.comments.iter_mut() {
+            if !is_nfc(comment.text()) {
+                *comment =
+                    Comment::new(comment.line_number(), comment.text().nfc().collect::<String>());
+            }
+        }
+
+        for id_tag in self.metadata.iter_mut() {
+            if !is_nfc(id_tag.raw_text()) {
+                *id_tag = unsafe {
+                    IDTag::from_string_unchecked(
+                        id_tag.get_label(),
+                        id_tag.raw_text().nfc().collect::<String>(),
+                    )
+                };
+            }
+        }
+    }
+}
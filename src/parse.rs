@@ -0,0 +1,391 @@
+use std::fmt::{self, Display, Formatter};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{Comment, DuplicateIDTagPolicy, IDTag, LimitKind, LyricsError, MetadataOrder, TimeTag};
+
+static LINE_STARTS_WITH_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new("^\\[([^\x00-\x08\x0A-\x1F\x7F\\[\\]:]*):([^\x00-\x08\x0A-\x1F\x7F\\[\\]]*)\\]")
+        .unwrap()
+});
+
+static RELAXED_TIME_TAG_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^\[\s*([0-9]{1,10})\s*['′：:]\s*([0-9]{1,2})\s*(?:["″．.,:]\s*([0-9]{1,3}))?\s*\]"#,
+    )
+    .unwrap()
+});
+
+/// How strictly lyrics are parsed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Educe)]
+#[educe(Default)]
+pub enum Dialect {
+    /// Follow the common LRC format.
+    #[educe(Default)]
+    Standard,
+    /// Also accept time tags with unusual separators seen in old files, like `[00'12"34]`, `[00：12．34]` and `[00:12.345]`.
+    Relaxed,
+}
+
+/// How to deal with a time tag which appears more than once in the same line.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Educe)]
+#[educe(Default)]
+pub enum DuplicateTimeTagPolicy {
+    /// Add the line once for every occurrence of the time tag.
+    Keep,
+    /// Add the line only once and report a warning.
+    #[educe(Default)]
+    Merge,
+    /// Fail to parse the line.
+    Error,
+}
+
+/// Limits on the size of the input, which protect against pathological inputs. `None` means unlimited.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ParseLimits {
+    /// The maximum number of bytes of the input.
+    pub max_bytes:       Option<usize>,
+    /// The maximum number of lines.
+    pub max_lines:       Option<usize>,
+    /// The maximum number of bytes of a line.
+    pub max_line_length: Option<usize>,
+}
+
+impl ParseLimits {
+    /// Check the number of bytes of the input which have been read.
+    #[inline]
+    pub(crate) fn check_bytes(&self, len: usize) -> Result<(), LyricsError> {
+        match self.max_bytes {
+            Some(max_bytes) if len > max_bytes => Err(LyricsError::LimitExceeded(LimitKind::Bytes)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check a line with its line number, starting from 1.
+    #[inline]
+    pub(crate) fn check_line(&self, line_number: usize, line: &str) -> Result<(), LyricsError> {
+        if let Some(max_lines) = self.max_lines {
+            if line_number > max_lines {
+                return Err(LyricsError::LimitExceeded(LimitKind::Lines));
+            }
+        }
+
+        if let Some(max_line_length) = self.max_line_length {
+            if line.len() > max_line_length {
+                return Err(LyricsError::LimitExceeded(LimitKind::LineLength));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Options for parsing lyrics.
+#[derive(Debug, Clone, Educe)]
+#[educe(Default)]
+pub struct ParseOptions {
+    /// How to deal with a time tag which appears more than once in the same line.
+    pub duplicate_time_tags: DuplicateTimeTagPolicy,
+    /// How to deal with an ID tag whose label already exists in the metadata.
+    pub duplicate_id_tags:   DuplicateIDTagPolicy,
+    /// The order of the ID tags in the metadata.
+    pub metadata_order:      MetadataOrder,
+    /// Limits on the size of the input.
+    pub limits:              ParseLimits,
+    /// How strictly lyrics are parsed.
+    pub dialect:             Dialect,
+    /// Whether to skip lines which cannot be parsed, such as binary junk, and report them as warnings instead of failing.
+    pub skip_invalid_lines:  bool,
+    /// Whether to convert the texts into Unicode Normalization Form C.
+    #[cfg(feature = "unicode-normalization")]
+    pub nfc:                 bool,
+}
+
+/// Something suspicious but recoverable found while parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWarningKind {
+    /// The time tag appears more than once in the same line and the duplicates are ignored.
+    DuplicateTimeTag(TimeTag),
+    /// The line cannot be parsed and is skipped.
+    SkippedLine,
+}
+
+impl Display for ParseWarningKind {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            ParseWarningKind::DuplicateTimeTag(t) => {
+                f.write_fmt(format_args!("The time tag {} is duplicated.", t))
+            },
+            ParseWarningKind::SkippedLine => {
+                f.write_str("The line cannot be parsed and is skipped.")
+            },
+        }
+    }
+}
+
+/// A warning reported while parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// The line number, starting from 1.
+    pub line_number: usize,
+    pub kind:        ParseWarningKind,
+}
+
+impl Display for ParseWarning {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_fmt(format_args!("Line {}: {}", self.line_number, self.kind))
+    }
+}
+
+/// What was added to lyrics by parsing a line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineOutcome {
+    /// The number of timed lines added, one for each time tag.
+    pub timed_lines: usize,
+    /// Whether a line without time tags was added.
+    pub plain_line:  bool,
+    /// The number of ID tags added to the metadata. ID tags ignored because of the duplicate policy are not counted.
+    pub id_tags:     usize,
+    /// Whether a comment was added.
+    pub comment:     bool,
+}
+
+/// Remove the UTF-8 byte order mark at the beginning of `s`.
+#[inline]
+pub(crate) fn strip_bom(s: &str) -> &str {
+    s.strip_prefix('\u{FEFF}').unwrap_or(s)
+}
+
+/// Find the first `\n` or `\r` in `s`.
+#[cfg(not(feature = "fast-parse"))]
+#[inline]
+pub(crate) fn find_newline(s: &[u8]) -> Option<usize> {
+    s.iter().position(|&b| b == b'\n' || b == b'\r')
+}
+
+/// Find the first `\n` or `\r` in `s` with SIMD.
+#[cfg(feature = "fast-parse")]
+#[inline]
+pub(crate) fn find_newline(s: &[u8]) -> Option<usize> {
+    memchr::memchr2(b'\n', b'\r', s)
+}
+
+/// An iterator over the lines of a string separated by `\n`, `\r\n` or `\r`.
+#[derive(Debug)]
+pub(crate) struct Lines<'a> {
+    s:        &'a str,
+    finished: bool,
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if self.finished {
+            return None;
+        }
+
+        match find_newline(self.s.as_bytes()) {
+            Some(index) => {
+                let line = &self.s[..index];
+
+                let rest = &self.s[index..];
+
+                self.s = rest.strip_prefix("\r\n").unwrap_or(&rest[1..]);
+
+                Some(line)
+            },
+            None => {
+                self.finished = true;
+
+                Some(self.s)
+            },
+        }
+    }
+}
+
+/// Split `s` into lines by `\n`, `\r\n` or `\r`.
+#[inline]
+pub(crate) fn split_lines(s: &str) -> Lines<'_> {
+    Lines {
+        s,
+        finished: false,
+    }
+}
+
+/// Whether `s` contains `[`, which must be present for any tag.
+#[cfg(not(feature = "fast-parse"))]
+#[inline]
+pub(crate) fn may_contain_tag(s: &str) -> bool {
+    s.contains('[')
+}
+
+/// Whether `s` contains `[`, which must be present for any tag, searching it with SIMD.
+#[cfg(feature = "fast-parse")]
+#[inline]
+pub(crate) fn may_contain_tag(s: &str) -> bool {
+    memchr::memchr(b'[', s.as_bytes()).is_some()
+}
+
+/// Parse a time tag at the beginning of `line` which may use unusual separators, like `[00'12"34]`. Returns the time tag and the length of it.
+fn parse_relaxed_time_tag(line: &str) -> Option<(TimeTag, usize)> {
+    let c = RELAXED_TIME_TAG_RE.captures(line)?;
+
+    let minute = c.get(1).unwrap().as_str().parse::<i64>().ok()?;
+    let second = c.get(2).unwrap().as_str().parse::<i64>().ok()?;
+
+    if second >= 60 {
+        return None;
+    }
+
+    let millisecond = match c.get(3) {
+        Some(m) => {
+            let m = m.as_str();
+
+            m.parse::<i64>().ok()? * 10i64.pow(3u32.checked_sub(m.len() as u32)?)
+        },
+        None => 0,
+    };
+
+    Some((TimeTag::new(minute * 60000 + second * 1000 + millisecond), c.get(0).unwrap().end()))
+}
+
+/// Add a time tag of a line according to the duplicate policy.
+fn push_time_tag(
+    time_tags: &mut Vec<TimeTag>,
+    time_tag: TimeTag,
+    line_number: usize,
+    options: &ParseOptions,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<(), LyricsError> {
+    if time_tags.contains(&time_tag) {
+        match options.duplicate_time_tags {
+            DuplicateTimeTagPolicy::Keep => time_tags.push(time_tag),
+            DuplicateTimeTagPolicy::Merge => {
+                warnings.push(ParseWarning {
+                    line_number,
+                    kind: ParseWarningKind::DuplicateTimeTag(time_tag),
+                });
+            },
+            DuplicateTimeTagPolicy::Error => {
+                return Err(LyricsError::ParseError(format!(
+                    "The time tag {} is duplicated.",
+                    time_tag
+                )));
+            },
+        }
+    } else {
+        time_tags.push(time_tag);
+    }
+
+    Ok(())
+}
+
+/// The tags and the text of a line.
+pub(crate) struct ParsedLine<'a> {
+    pub(crate) time_tags: Vec<TimeTag>,
+    pub(crate) id_tags:   Vec<IDTag>,
+    pub(crate) comment:   Option<Comment>,
+    pub(crate) text:      &'a str,
+}
+
+impl<'a> ParsedLine<'a> {
+    /// Parse a line. `line_number` is `None` if the line does not come from a source, and the warnings of such a line are reported at line 0.
+    pub(crate) fn parse(
+        line: &'a str,
+        line_number: Option<usize>,
+        options: &ParseOptions,
+        warnings: &mut Vec<ParseWarning>,
+    ) -> Result<ParsedLine<'a>, LyricsError> {
+        let mut time_tags: Vec<TimeTag> = Vec::new();
+        let mut id_tags: Vec<IDTag> = Vec::new();
+        let mut comment: Option<Comment> = None;
+
+        let warning_line_number = line_number.unwrap_or(0);
+
+        let mut line = line.trim();
+
+        while line.starts_with('[') {
+            if options.dialect == Dialect::Relaxed {
+                if let Some((time_tag, tag_len)) = parse_relaxed_time_tag(line) {
+                    push_time_tag(
+                        &mut time_tags,
+                        time_tag,
+                        warning_line_number,
+                        options,
+                        warnings,
+                    )?;
+
+                    line = line[tag_len..].trim_start();
+
+                    continue;
+                }
+            }
+
+            let c = match LINE_STARTS_WITH_RE.captures(line) {
+                Some(c) => c,
+                None => break,
+            };
+
+            let tag = c.get(0).unwrap().as_str();
+            let tag_len = tag.len();
+
+            match TimeTag::from_str(tag) {
+                Ok(time_tag) => {
+                    push_time_tag(&mut time_tags, time_tag, warning_line_number, options, warnings)?
+                },
+                Err(_) => {
+                    let label = c.get(1).unwrap().as_str().trim();
+
+                    let raw_text = c.get(2).unwrap().as_str();
+                    let text = raw_text.trim();
+
+                    if label.is_empty() {
+                        // A comment tag, usually in the format [:], makes the rest of the line a comment, including the text inside the brackets.
+                        let rest = line[tag_len..].trim();
+
+                        let comment_text = match (text.is_empty(), rest.is_empty()) {
+                            (_, true) => String::from(text),
+                            (true, false) => String::from(rest),
+                            (false, false) => format!("{} {}", text, rest),
+                        };
+
+                        comment = Some(Comment::new(line_number, comment_text));
+
+                        line = "";
+                        break;
+                    }
+
+                    id_tags.push(unsafe { IDTag::from_string_unchecked(label, raw_text) });
+                },
+            }
+
+            line = line[tag_len..].trim_start();
+        }
+
+        Ok(ParsedLine {
+            time_tags,
+            id_tags,
+            comment,
+            text: line,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn split() {
+        for s in ["", "\n", "a", "a\n", "\na\n\nb"] {
+            assert_eq!(s.split('\n').collect::<Vec<_>>(), split_lines(s).collect::<Vec<_>>());
+        }
+
+        assert_eq!(vec!["a", "b", "", "c", "d"], split_lines("a\r\nb\r\rc\nd").collect::<Vec<_>>());
+        assert_eq!(vec!["a", "", "b", ""], split_lines("a\n\rb\r").collect::<Vec<_>>());
+    }
+}
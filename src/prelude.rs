@@ -0,0 +1,18 @@
+//! Re-exports of the commonly used types and traits.
+//!
+//! ```rust
+//! use lrc::prelude::*;
+//!
+//! let lyrics =
+//!     Lyrics::from_str("[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe")
+//!         .unwrap();
+//!
+//! assert_eq!(TimeTag::new(12000), lyrics.get_timed_lines()[0].0);
+//! ```
+
+pub use crate::{
+    convert::LyricsFormat, BilingualLyrics, Boundary, Comment, CommentPlacement, CursorEvent,
+    DuplicateIDTagPolicy, DuplicateTimeTagPolicy, IDTag, IDTagLabel, KnownTag, LengthFormat,
+    LineText, Lyrics, LyricsCursor, LyricsError, Metadata, MetadataOrder, NegativeTimePolicy,
+    Newline, ParseOptions, SerializeError, SerializeOptions, TextMatchOptions, TimeTag, Timestamp,
+};
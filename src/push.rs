@@ -0,0 +1,149 @@
+use std::str;
+
+use crate::{find_newline, Lyrics, LyricsError, ParseOptions, ParseWarning};
+
+/// A parser which is fed with chunks of input and parses every complete line as soon as it arrives.
+#[derive(Debug, Clone)]
+pub struct LyricsPushParser {
+    lyrics:      Lyrics,
+    options:     ParseOptions,
+    warnings:    Vec<ParseWarning>,
+    buffer:      Vec<u8>,
+    bytes:       usize,
+    line_number: usize,
+}
+
+impl Default for LyricsPushParser {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LyricsPushParser {
+    /// Create a `LyricsPushParser` instance.
+    #[inline]
+    pub fn new() -> LyricsPushParser {
+        Self::with_options(ParseOptions::default())
+    }
+
+    /// Create a `LyricsPushParser` instance with options.
+    #[inline]
+    pub fn with_options(options: ParseOptions) -> LyricsPushParser {
+        LyricsPushParser {
+            lyrics: Lyrics::with_parse_options(&options),
+            options,
+            warnings: Vec::new(),
+            buffer: Vec::new(),
+            bytes: 0,
+            line_number: 0,
+        }
+    }
+
+    /// Get the lyrics parsed from the complete lines so far.
+    #[inline]
+    pub fn lyrics(&self) -> &Lyrics {
+        &self.lyrics
+    }
+
+    /// Get the warnings reported so far.
+    #[inline]
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Get the number of lines which have been parsed.
+    #[inline]
+    pub fn line_count(&self) -> usize {
+        self.line_number
+    }
+
+    /// Feed a chunk of input. The complete lines in the buffered input are parsed and the number of them is returned. An incomplete line is kept until more input or `finish` is given.
+    pub fn feed<B: AsRef<[u8]>>(&mut self, bytes: B) -> Result<usize, LyricsError> {
+        let bytes = bytes.as_ref();
+
+        self.bytes += bytes.len();
+
+        self.options.limits.check_bytes(self.bytes)?;
+
+        // a `\r` at the end of the previous chunk may be followed by a `\n`
+        let search_from = self.buffer.len().saturating_sub(1);
+
+        self.buffer.extend_from_slice(bytes);
+
+        self.parse_buffer(search_from, false)
+    }
+
+    /// Parse the rest of the buffered input as the last line and return the lyrics.
+    #[inline]
+    pub fn finish(self) -> Result<Lyrics, LyricsError> {
+        self.finish_with_warnings().map(|(lyrics, _)| lyrics)
+    }
+
+    /// Parse the rest of the buffered input as the last line and return the lyrics along with the warnings.
+    pub fn finish_with_warnings(mut self) -> Result<(Lyrics, Vec<ParseWarning>), LyricsError> {
+        self.parse_buffer(0, true)?;
+
+        let buffer = std::mem::take(&mut self.buffer);
+
+        self.parse_line(&buffer)?;
+
+        self.lyrics.finish_parsing(&self.options);
+
+        Ok((self.lyrics, self.warnings))
+    }
+
+    fn parse_buffer(&mut self, search_from: usize, finished: bool) -> Result<usize, LyricsError> {
+        let buffer = std::mem::take(&mut self.buffer);
+
+        let mut start = 0;
+        let mut search_from = search_from;
+        let mut count = 0;
+
+        let result = loop {
+            let end = match find_newline(&buffer[search_from..]) {
+                Some(index) => search_from + index,
+                None => break Ok(count),
+            };
+
+            let next = if buffer[end] == b'\r' {
+                match buffer.get(end + 1) {
+                    Some(b'\n') => end + 2,
+                    Some(_) => end + 1,
+                    None if finished => end + 1,
+                    None => break Ok(count),
+                }
+            } else {
+                end + 1
+            };
+
+            if let Err(error) = self.parse_line(&buffer[start..end]) {
+                break Err(error);
+            }
+
+            start = next;
+            search_from = next;
+            count += 1;
+        };
+
+        self.buffer = buffer;
+        self.buffer.drain(..start);
+
+        result
+    }
+
+    fn parse_line(&mut self, line: &[u8]) -> Result<(), LyricsError> {
+        let line = if self.line_number == 0 {
+            line.strip_prefix(b"\xEF\xBB\xBF".as_ref()).unwrap_or(line)
+        } else {
+            line
+        };
+
+        let line = str::from_utf8(line)
+            .map_err(|_| LyricsError::FormatError("The line is not valid UTF-8."))?;
+
+        self.line_number += 1;
+
+        self.lyrics.parse_source_line(line, self.line_number, &self.options, &mut self.warnings)
+    }
+}
@@ -0,0 +1,93 @@
+use std::{
+    io::{self, BufRead, Read},
+    str,
+};
+
+use crate::{split_lines, Lyrics, LyricsError, ParseOptions, ParseWarning};
+
+impl Lyrics {
+    /// Create a `Lyrics` instance by reading lines from `reader`, without reading the whole input into memory first.
+    #[inline]
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Lyrics, LyricsError> {
+        Self::from_reader_with_options(reader, &ParseOptions::default())
+    }
+
+    /// Create a `Lyrics` instance with options by reading lines from `reader`, without reading the whole input into memory first.
+    #[inline]
+    pub fn from_reader_with_options<R: BufRead>(
+        reader: R,
+        options: &ParseOptions,
+    ) -> Result<Lyrics, LyricsError> {
+        Self::from_reader_with_warnings(reader, options).map(|(lyrics, _)| lyrics)
+    }
+
+    /// Create a `Lyrics` instance with options by reading lines from `reader`. The warnings reported while parsing are returned along with the lyrics.
+    pub fn from_reader_with_warnings<R: BufRead>(
+        mut reader: R,
+        options: &ParseOptions,
+    ) -> Result<(Lyrics, Vec<ParseWarning>), LyricsError> {
+        let mut lyrics = Lyrics::with_parse_options(options);
+        let mut warnings = Vec::new();
+
+        let mut buffer = Vec::new();
+        let mut bytes = 0;
+        let mut line_number = 0;
+        let mut ends_with_newline = true;
+
+        loop {
+            buffer.clear();
+
+            // read at most one byte more than the limit allows, so that a long chunk without `\n` is not buffered entirely
+            let max_len = match options.limits.max_bytes {
+                Some(max_bytes) => max_bytes.saturating_sub(bytes) as u64 + 1,
+                None => u64::MAX,
+            };
+
+            let len = reader.by_ref().take(max_len).read_until(b'\n', &mut buffer)?;
+
+            if len == 0 {
+                break;
+            }
+
+            bytes += len;
+
+            options.limits.check_bytes(bytes)?;
+
+            let mut chunk = str::from_utf8(&buffer).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+            })?;
+
+            if line_number == 0 {
+                chunk = chunk.strip_prefix('\u{FEFF}').unwrap_or(chunk);
+            }
+
+            // a lone `\r` inside the chunk also ends a line
+            let chunk = match chunk.strip_suffix('\n') {
+                Some(chunk) => {
+                    ends_with_newline = true;
+
+                    chunk.strip_suffix('\r').unwrap_or(chunk)
+                },
+                None => {
+                    ends_with_newline = false;
+
+                    chunk
+                },
+            };
+
+            for line in split_lines(chunk) {
+                line_number += 1;
+
+                lyrics.parse_source_line(line, line_number, options, &mut warnings)?;
+            }
+        }
+
+        if ends_with_newline {
+            lyrics.parse_source_line("", line_number + 1, options, &mut warnings)?;
+        }
+
+        lyrics.finish_parsing(options);
+
+        Ok((lyrics, warnings))
+    }
+}
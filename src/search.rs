@@ -0,0 +1,97 @@
+#[cfg(feature = "regex-search")]
+use std::ops::Range;
+
+#[cfg(feature = "regex-search")]
+use regex::Regex;
+
+use crate::{enhanced::plain_line_text, Lyrics, TextMatchOptions, TimeTag};
+
+/// A line found by a search.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LineMatch<'a> {
+    /// A timed line.
+    Timed { index: usize, time_tag: TimeTag, text: &'a str },
+    /// A plain line.
+    Plain { index: usize, text: &'a str },
+}
+
+/// A line found by `Lyrics::search_regex`, with the byte ranges of the matches in the text of the line.
+#[cfg(feature = "regex-search")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegexLineMatch<'a> {
+    pub line:   LineMatch<'a>,
+    pub ranges: Vec<Range<usize>>,
+}
+
+impl Lyrics {
+    /// Find the timed lines and then the plain lines which contain `text`, case-insensitively with Unicode rules. Word time tags and escapes of brackets in the lines are ignored. An empty `text` matches no lines.
+    #[inline]
+    pub fn search<S: AsRef<str>>(&self, text: S) -> Vec<LineMatch<'_>> {
+        self.search_with_options(text, &TextMatchOptions {
+            case_insensitive: true,
+            unicode_case_folding: true,
+            ..TextMatchOptions::exact()
+        })
+    }
+
+    /// Find the timed lines and then the plain lines which contain `text` under `options`. Word time tags and escapes of brackets in the lines are ignored. A `text` which is empty after normalization matches no lines.
+    pub fn search_with_options<S: AsRef<str>>(
+        &self,
+        text: S,
+        options: &TextMatchOptions,
+    ) -> Vec<LineMatch<'_>> {
+        let needle = options.normalize(text.as_ref());
+
+        if needle.is_empty() {
+            return Vec::new();
+        }
+
+        let contains = |line: &str| options.normalize(&plain_line_text(line)).contains(&*needle);
+
+        let timed_lines =
+            self.timed_lines.iter().enumerate().filter(|(_, (_, line))| contains(line)).map(
+                |(index, (time_tag, line))| LineMatch::Timed {
+                    index,
+                    time_tag: *time_tag,
+                    text: line,
+                },
+            );
+
+        let lines = self.lines.iter().enumerate().filter(|(_, line)| contains(line)).map(
+            |(index, line)| LineMatch::Plain {
+                index,
+                text: line,
+            },
+        );
+
+        timed_lines.chain(lines).collect()
+    }
+
+    /// Find the timed lines and then the plain lines which match `regex`, along with the ranges of all matches. Unlike `search`, the regex is matched against the raw texts of the lines, including word time tags and escapes.
+    #[cfg(feature = "regex-search")]
+    pub fn search_regex(&self, regex: &Regex) -> Vec<RegexLineMatch<'_>> {
+        let ranges = |text: &str| {
+            regex.find_iter(text).map(|m| m.start()..m.end()).collect::<Vec<Range<usize>>>()
+        };
+
+        let timed_lines =
+            self.timed_lines.iter().enumerate().map(|(index, (time_tag, line))| RegexLineMatch {
+                line:   LineMatch::Timed {
+                    index,
+                    time_tag: *time_tag,
+                    text: line,
+                },
+                ranges: ranges(line),
+            });
+
+        let lines = self.lines.iter().enumerate().map(|(index, line)| RegexLineMatch {
+            line:   LineMatch::Plain {
+                index,
+                text: line,
+            },
+            ranges: ranges(line),
+        });
+
+        timed_lines.chain(lines).filter(|line_match| !line_match.ranges.is_empty()).collect()
+    }
+}
@@ -0,0 +1,135 @@
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{IDTag, Lyrics, TimeTag, Timestamp};
+
+impl Serialize for Timestamp {
+    /// Serialize as milliseconds.
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.get_timestamp())
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        i64::deserialize(deserializer).map(Timestamp::new)
+    }
+}
+
+impl Serialize for TimeTag {
+    /// Serialize as milliseconds.
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.get_timestamp())
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeTag {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        i64::deserialize(deserializer).map(TimeTag::new)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename = "IDTag")]
+struct IDTagRef<'a> {
+    label: &'a str,
+    text:  &'a str,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "IDTag")]
+struct IDTagData {
+    label: String,
+    #[serde(default)]
+    text:  String,
+}
+
+impl Serialize for IDTag {
+    /// Serialize as a struct with `label` and `text`.
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        IDTagRef {
+            label: self.get_label(), text: self.get_text()
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for IDTag {
+    /// Deserialize from a struct with `label` and `text`, which are validated like `IDTag::from_string`.
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = IDTagData::deserialize(deserializer)?;
+
+        IDTag::from_string(data.label, data.text).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename = "Lyrics")]
+struct LyricsRef<'a> {
+    metadata:    Vec<&'a IDTag>,
+    comments:    Vec<&'a str>,
+    timed_lines: Vec<(TimeTag, &'a str)>,
+    lines:       &'a [String],
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Lyrics")]
+struct LyricsData {
+    #[serde(default)]
+    metadata:    Vec<IDTag>,
+    #[serde(default)]
+    comments:    Vec<String>,
+    #[serde(default)]
+    timed_lines: Vec<(TimeTag, String)>,
+    #[serde(default)]
+    lines:       Vec<String>,
+}
+
+impl Serialize for Lyrics {
+    /// Serialize as a struct with `metadata`, `comments`, `timed_lines` and `lines`. Timed lines are pairs of milliseconds and texts. Which time tags were written on the same line in the source is not kept.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        LyricsRef {
+            metadata:    self.metadata.iter().collect(),
+            comments:    self.comments.iter().map(|comment| comment.text()).collect(),
+            timed_lines: self
+                .timed_lines
+                .iter()
+                .map(|(time_tag, line)| (*time_tag, line.as_ref()))
+                .collect(),
+            lines:       &self.lines,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Lyrics {
+    /// Deserialize from a struct with `metadata`, `comments`, `timed_lines` and `lines`, which are validated like the `add_*` methods. Missing fields are empty.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = LyricsData::deserialize(deserializer)?;
+
+        let mut lyrics = Lyrics::new();
+
+        for id_tag in data.metadata {
+            lyrics.metadata.insert(id_tag);
+        }
+
+        for comment in data.comments {
+            lyrics.add_comment(comment).map_err(D::Error::custom)?;
+        }
+
+        for (time_tag, line) in data.timed_lines {
+            lyrics.add_timed_line(time_tag, line).map_err(D::Error::custom)?;
+        }
+
+        for line in data.lines {
+            lyrics.add_line(line).map_err(D::Error::custom)?;
+        }
+
+        Ok(lyrics)
+    }
+}
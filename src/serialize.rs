@@ -0,0 +1,403 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+};
+
+use crate::{
+    enhanced::{plain_line_text, WORD_TIME_TAG_RE},
+    length::write_length,
+    parse_length, unescape_brackets, IDTag, LengthFormat, Lyrics, Rounding, SerializeError,
+    TimeFormat, TimeTag,
+};
+
+/// The largest timestamp which can be written in the `mm:ss.xx` format.
+const MAX_TIMESTAMP: i64 = 99 * 60000 + 59 * 1000 + 990;
+
+/// The largest timestamp which can be written in the `h:mm:ss.xx` format, whose hours have at most seven digits when parsed.
+const MAX_HOURS_TIMESTAMP: i64 = 9_999_999 * 3_600_000 + MAX_TIMESTAMP % 3_600_000;
+
+/// The line break written between lines.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Educe)]
+#[educe(Default)]
+pub enum Newline {
+    /// `\n`
+    #[educe(Default)]
+    Lf,
+    /// `\r\n`
+    CrLf,
+}
+
+impl Newline {
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Newline::Lf => "\n",
+            Newline::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Where to write comments. The comments are always written together in their original order, so a comment between timed lines in the source does not stay there. Use `LyricsDocument` to keep every item at its position.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Educe)]
+#[educe(Default)]
+pub enum CommentPlacement {
+    /// Before the metadata, at the beginning of the output.
+    BeforeMetadata,
+    /// After the metadata and before the lines.
+    #[educe(Default)]
+    AfterMetadata,
+    /// After the lines, at the end of the output.
+    End,
+}
+
+/// Options for serializing lyrics.
+#[derive(Debug, Clone, Educe)]
+#[educe(Default)]
+pub struct SerializeOptions {
+    /// The line break written between lines.
+    pub newline:               Newline,
+    /// Whether to end the output with a line break.
+    pub trailing_newline:      bool,
+    /// Whether to put a space after the colon of ID tags, like `[ti: Title]` instead of `[ti:Title]`.
+    #[educe(Default = true)]
+    pub id_tag_padding:        bool,
+    /// Whether to begin the output with a UTF-8 byte order mark, which some old players require.
+    pub bom:                   bool,
+    /// How to write the value of the `[length:]` tag.
+    pub length_format:         LengthFormat,
+    /// Where to write comments.
+    pub comment_placement:     CommentPlacement,
+    /// Whether to write timed lines with the same text as one line with multiple time tags, like `[00:12.00][01:15.00]text`.
+    pub merge_identical_lines: bool,
+    /// Whether to write time tags which were added together for the same line, like `[00:12.00][01:15.00]text` in the source, as one line again. Groups broken by later edits, like removing or rewriting some of their lines, are written as separate lines.
+    pub preserve_grouping:     bool,
+    /// How to round the milliseconds of time tags to hundredths of a second.
+    pub rounding:              Rounding,
+    /// How to write time tags of an hour or longer.
+    pub time_format:           TimeFormat,
+    /// A function called with every timed line. If it returns a string, the string is written instead of the line, including its time tag, and the line is not merged or grouped with others.
+    pub line_hook:             Option<fn(&TimeTag, &str) -> Option<String>>,
+}
+
+/// Write a time tag rounded and formatted with `options`.
+#[inline]
+fn write_time_tag<W: Write>(
+    f: &mut W,
+    time_tag: TimeTag,
+    options: &SerializeOptions,
+) -> Result<(), fmt::Error> {
+    f.write_fmt(format_args!(
+        "[{}]",
+        time_tag.timestamp().display(options.rounding).with_format(options.time_format)
+    ))
+}
+
+impl Lyrics {
+    /// Serialize the lyrics with options.
+    #[inline]
+    pub fn to_string_with_options(&self, options: &SerializeOptions) -> String {
+        let mut s = String::new();
+
+        self.write_with_options(&mut s, options).unwrap();
+
+        s
+    }
+
+    /// Serialize the lyrics with options into `f`.
+    pub fn write_with_options<W: Write>(
+        &self,
+        f: &mut W,
+        options: &SerializeOptions,
+    ) -> Result<(), fmt::Error> {
+        let newline = options.newline.as_str();
+
+        if options.bom {
+            f.write_char('\u{FEFF}')?;
+        }
+
+        let mut has_block = false;
+
+        let mut begin_block = |f: &mut W| -> Result<(), fmt::Error> {
+            if has_block {
+                f.write_str(newline)?;
+                f.write_str(newline)?;
+            }
+
+            has_block = true;
+
+            Ok(())
+        };
+
+        let write_comments =
+            |f: &mut W, begin_block: &mut dyn FnMut(&mut W) -> Result<(), fmt::Error>| {
+                if self.comments.is_empty() {
+                    return Ok(());
+                }
+
+                begin_block(f)?;
+
+                let mut iter = self.comments.iter();
+
+                f.write_fmt(format_args!("{}", iter.next().unwrap()))?;
+
+                for comment in iter {
+                    f.write_str(newline)?;
+                    f.write_fmt(format_args!("{}", comment))?;
+                }
+
+                Ok(())
+            };
+
+        if options.comment_placement == CommentPlacement::BeforeMetadata {
+            write_comments(f, &mut begin_block)?;
+        }
+
+        if !self.metadata.is_empty() {
+            begin_block(f)?;
+
+            let padding = if options.id_tag_padding { " " } else { "" };
+
+            let write_id_tag = |f: &mut W, id_tag: &IDTag| {
+                let label = id_tag.get_label();
+
+                f.write_fmt(format_args!("[{}:{}", label, padding))?;
+
+                let length = if label.eq_ignore_ascii_case("length") {
+                    parse_length(id_tag.get_text())
+                } else {
+                    None
+                };
+
+                let written = match length {
+                    Some(length) => write_length(f, length, options.length_format)?,
+                    None => false,
+                };
+
+                if !written {
+                    f.write_str(id_tag.get_text())?;
+                }
+
+                f.write_char(']')
+            };
+
+            let mut iter = self.metadata.iter();
+
+            write_id_tag(f, iter.next().unwrap())?;
+
+            for id_tag in iter {
+                f.write_str(newline)?;
+                write_id_tag(f, id_tag)?;
+            }
+        }
+
+        if options.comment_placement == CommentPlacement::AfterMetadata {
+            write_comments(f, &mut begin_block)?;
+        }
+
+        if !self.timed_lines.is_empty() {
+            begin_block(f)?;
+
+            let overrides: Vec<Option<String>> = match options.line_hook {
+                Some(line_hook) => self
+                    .timed_lines
+                    .iter()
+                    .map(|(time_tag, line)| line_hook(time_tag, line))
+                    .collect(),
+                None => vec![None; self.timed_lines.len()],
+            };
+
+            if options.merge_identical_lines {
+                // an overriding string is kept as a group without time tags
+                let mut groups: Vec<(Vec<TimeTag>, &str)> = Vec::new();
+
+                for ((time_tag, line), line_override) in self.timed_lines.iter().zip(&overrides) {
+                    if let Some(line_override) = line_override {
+                        groups.push((Vec::new(), line_override));
+
+                        continue;
+                    }
+
+                    let line: &str = line;
+
+                    match groups.iter_mut().find(|(t, l)| !t.is_empty() && *l == line) {
+                        Some((time_tags, _)) => time_tags.push(*time_tag),
+                        None => groups.push((vec![*time_tag], line)),
+                    }
+                }
+
+                for (i, (time_tags, line)) in groups.into_iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(newline)?;
+                    }
+
+                    for time_tag in time_tags {
+                        write_time_tag(f, time_tag, options)?;
+                    }
+
+                    f.write_str(line)?;
+                }
+            } else if options.preserve_grouping {
+                // the indices of the timed lines of each group, in chronological order
+                let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+
+                for (i, group) in self.line_groups.iter().enumerate() {
+                    if let Some(group) = group {
+                        members.entry(*group).or_default().push(i);
+                    }
+                }
+
+                // overridden lines are never pulled into a group
+                let mut written: Vec<bool> = overrides.iter().map(Option::is_some).collect();
+
+                for (i, (_, line)) in self.timed_lines.iter().enumerate() {
+                    if let Some(line_override) = &overrides[i] {
+                        if i > 0 {
+                            f.write_str(newline)?;
+                        }
+
+                        f.write_str(line_override)?;
+
+                        continue;
+                    }
+
+                    if written[i] {
+                        continue;
+                    }
+
+                    if i > 0 {
+                        f.write_str(newline)?;
+                    }
+
+                    // a group is broken if any of its lines is overridden, written already or has a different text
+                    let indices = match self.line_groups[i].and_then(|group| members.get(&group)) {
+                        Some(indices)
+                            if indices
+                                .iter()
+                                .all(|&j| !written[j] && self.timed_lines[j].1 == *line) =>
+                        {
+                            indices.as_slice()
+                        },
+                        _ => std::slice::from_ref(&i),
+                    };
+
+                    for &index in indices {
+                        written[index] = true;
+
+                        write_time_tag(f, self.timed_lines[index].0, options)?;
+                    }
+
+                    f.write_str(line)?;
+                }
+            } else {
+                for (i, ((time_tag, line), line_override)) in
+                    self.timed_lines.iter().zip(overrides).enumerate()
+                {
+                    if i > 0 {
+                        f.write_str(newline)?;
+                    }
+
+                    match line_override {
+                        Some(line_override) => f.write_str(&line_override)?,
+                        None => {
+                            write_time_tag(f, *time_tag, options)?;
+                            f.write_str(line)?;
+                        },
+                    }
+                }
+            }
+        }
+
+        if !self.lines.is_empty() {
+            let mut buffer = String::new();
+
+            let mut iter = self.lines.iter();
+
+            buffer.push_str(iter.next().unwrap());
+
+            for line in iter {
+                buffer.push_str(newline);
+                buffer.push_str(line);
+            }
+
+            let s = buffer.trim();
+
+            if !s.is_empty() {
+                begin_block(f)?;
+
+                f.write_str(s)?;
+            }
+        }
+
+        if options.comment_placement == CommentPlacement::End {
+            write_comments(f, &mut begin_block)?;
+        }
+
+        if options.trailing_newline && has_block {
+            f.write_str(newline)?;
+        }
+
+        Ok(())
+    }
+
+    /// Serialize the lyrics, but return an error instead of silently degrading when the content cannot be represented losslessly in core LRC.
+    #[inline]
+    pub fn to_string_strict(&self) -> Result<String, SerializeError> {
+        self.to_string_strict_with_options(&SerializeOptions::default())
+    }
+
+    /// Serialize the lyrics with options, but return an error instead of silently degrading when the content cannot be represented losslessly in the dialect chosen by `options`. Time tags are checked after being rounded with `options.rounding`, and may be an hour or longer if `options.time_format` allows.
+    pub fn to_string_strict_with_options(
+        &self,
+        options: &SerializeOptions,
+    ) -> Result<String, SerializeError> {
+        let max_timestamp = match options.time_format {
+            TimeFormat::MinutesSeconds => MAX_TIMESTAMP,
+            TimeFormat::HoursMinutesSeconds => MAX_HOURS_TIMESTAMP,
+        };
+
+        for (i, (time_tag, line)) in self.timed_lines.iter().enumerate() {
+            let timestamp = time_tag.get_timestamp();
+
+            if timestamp < 0 {
+                return Err(SerializeError::NegativeTime(*time_tag));
+            }
+
+            if time_tag.timestamp().round_to_hundredths(options.rounding).get_timestamp()
+                > max_timestamp
+            {
+                return Err(SerializeError::TimeOverflow(*time_tag));
+            }
+
+            if timestamp % 10 != 0 {
+                return Err(SerializeError::PrecisionLoss(*time_tag));
+            }
+
+            if WORD_TIME_TAG_RE.is_match(line) {
+                return Err(SerializeError::WordTimings(i));
+            }
+        }
+
+        Ok(self.to_string_with_options(options))
+    }
+
+    /// Get the text of the lyrics without any tags, which is the timed lines in chronological order followed by the plain lines, one per line. Word time tags and escapes are removed, and timed lines without text, which only clear the screen, are skipped.
+    pub fn to_plain_text(&self) -> String {
+        let mut s = String::new();
+
+        let timed_lines = self.timed_lines.iter().map(|(_, line)| plain_line_text(line));
+
+        for line in timed_lines.filter(|line| !line.trim().is_empty()) {
+            s.push_str(&line);
+            s.push('\n');
+        }
+
+        for line in self.lines.iter() {
+            s.push_str(&unescape_brackets(line));
+            s.push('\n');
+        }
+
+        s.pop();
+
+        s
+    }
+}
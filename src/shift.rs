@@ -0,0 +1,102 @@
+use crate::{IDTag, KnownTag, Lyrics, LyricsError, TimeTag, Timestamp};
+
+/// How to deal with time tags which would become negative after being moved.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Educe)]
+#[educe(Default)]
+pub enum NegativeTimePolicy {
+    /// Keep negative time tags.
+    #[educe(Default)]
+    Keep,
+    /// Clamp negative time tags to `00:00.00`.
+    Clamp,
+    /// Remove the lines whose time tags would become negative.
+    Remove,
+    /// Return an error and leave the lyrics unchanged.
+    Error,
+}
+
+impl Lyrics {
+    /// Move all timed lines by `offset` milliseconds. Negative time tags are kept.
+    #[inline]
+    pub fn shift_time<N: Into<i64>>(&mut self, offset: N) {
+        self.shift_time_with_policy(offset, NegativeTimePolicy::Keep).unwrap()
+    }
+
+    /// Move all timed lines by `offset` milliseconds, handling negative time tags according to `policy`.
+    pub fn shift_time_with_policy<N: Into<i64>>(
+        &mut self,
+        offset: N,
+        policy: NegativeTimePolicy,
+    ) -> Result<(), LyricsError> {
+        let offset = Timestamp::new(offset);
+
+        let shift = |time_tag: TimeTag| time_tag.saturating_add(offset);
+
+        if policy == NegativeTimePolicy::Error {
+            if let Some((time_tag, _)) = self.timed_lines.first() {
+                let time_tag = shift(*time_tag);
+
+                if time_tag.get_timestamp() < 0 {
+                    return Err(LyricsError::NegativeTime(time_tag));
+                }
+            }
+        }
+
+        for (time_tag, _) in self.timed_lines.iter_mut() {
+            *time_tag = shift(*time_tag);
+        }
+
+        match policy {
+            NegativeTimePolicy::Clamp => {
+                for (time_tag, _) in self.timed_lines.iter_mut() {
+                    if time_tag.get_timestamp() < 0 {
+                        *time_tag = TimeTag::new(0);
+                    }
+                }
+            },
+            NegativeTimePolicy::Remove => {
+                self.retain_timed_lines(|(time_tag, _)| time_tag.get_timestamp() >= 0);
+            },
+            NegativeTimePolicy::Keep | NegativeTimePolicy::Error => (),
+        }
+
+        Ok(())
+    }
+
+    /// Make `new_zero` the beginning of the lyrics by subtracting it from all time tags, handling the time tags before it according to `policy`.
+    #[inline]
+    pub fn rebase(
+        &mut self,
+        new_zero: TimeTag,
+        policy: NegativeTimePolicy,
+    ) -> Result<(), LyricsError> {
+        self.shift_time_with_policy(new_zero.get_timestamp().saturating_neg(), policy)
+    }
+}
+
+impl Lyrics {
+    /// Get the value of the `[offset:]` tag in milliseconds. A positive offset makes lines appear sooner.
+    pub fn offset(&self) -> Option<i64> {
+        self.get_tag(KnownTag::Offset)
+            .and_then(|text| text.strip_prefix('+').unwrap_or(text).parse::<i64>().ok())
+    }
+
+    /// Set the `[offset:]` tag in milliseconds. A positive offset makes lines appear sooner.
+    #[inline]
+    pub fn set_offset<N: Into<i64>>(&mut self, offset: N) {
+        let offset = offset.into();
+
+        let text = if offset > 0 { format!("+{}", offset) } else { offset.to_string() };
+
+        self.metadata.replace(unsafe { IDTag::from_string_unchecked("offset", text) });
+    }
+
+    /// Move all timed lines by the `[offset:]` tag and remove the tag.
+    pub fn apply_offset(&mut self) {
+        if let Some(offset) = self.offset() {
+            self.shift_time(offset.saturating_neg());
+        }
+
+        self.remove_tag(KnownTag::Offset);
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt::{self, Write};
+
+use crate::{enhanced::plain_line_text, Lyrics};
+
+/// Options for exporting lyrics to SubRip.
+#[derive(Debug, Clone, Educe)]
+#[educe(Default)]
+pub struct SrtOptions {
+    /// How long the last timed line is shown in milliseconds, if the lyrics has no `[length:]` tag after it.
+    #[educe(Default = 5000)]
+    pub last_line_duration: i64,
+}
+
+impl Lyrics {
+    /// Get when the timed line at `index` ends, which is when the next one begins. The last one ends at the `[length:]` tag if possible, or `last_line_duration` milliseconds after it begins.
+    pub(crate) fn line_end_time(&self, index: usize, last_line_duration: i64) -> i64 {
+        let start = self.timed_lines[index].0.get_timestamp();
+
+        match self.timed_lines.get(index + 1) {
+            Some((next, _)) => next.get_timestamp(),
+            None => match self.length() {
+                Some(length) if length.get_timestamp() > start => length.get_timestamp(),
+                _ => start.saturating_add(last_line_duration),
+            },
+        }
+    }
+
+    /// Export the timed lines to SubRip (SRT).
+    #[inline]
+    pub fn to_srt(&self) -> String {
+        self.to_srt_with_options(&SrtOptions::default())
+    }
+
+    /// Export the timed lines to SubRip (SRT) with options. Each line ends when the next one begins. Word time tags and escapes of brackets are removed, and lines with empty text are not written but still end the previous line.
+    pub fn to_srt_with_options(&self, options: &SrtOptions) -> String {
+        let mut s = String::new();
+
+        let mut number = 0;
+
+        for (start, end, line) in self.timed_line_spans(options.last_line_duration) {
+            let line = plain_line_text(line);
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            number += 1;
+
+            s.write_fmt(format_args!("{}\n", number)).unwrap();
+            write_srt_time(&mut s, start.get_timestamp()).unwrap();
+            s.push_str(" --> ");
+            write_srt_time(&mut s, end.get_timestamp()).unwrap();
+            s.write_fmt(format_args!("\n{}\n\n", line)).unwrap();
+        }
+
+        s
+    }
+}
+
+/// Write a timestamp in the `hh:mm:ss,mmm` format. Negative timestamps are written as zero.
+fn write_srt_time<W: Write>(f: &mut W, timestamp: i64) -> Result<(), fmt::Error> {
+    let timestamp = timestamp.max(0);
+
+    f.write_fmt(format_args!(
+        "{:02}:{:02}:{:02},{:03}",
+        timestamp / 3600000,
+        timestamp / 60000 % 60,
+        timestamp / 1000 % 60,
+        timestamp % 1000
+    ))
+}
@@ -0,0 +1,55 @@
+use std::convert::TryFrom;
+
+use subparse::{
+    timetypes::{TimePoint, TimeSpan},
+    SrtFile, SubtitleFile,
+};
+
+use crate::{enhanced::plain_line_text, Lyrics, LyricsError, SrtOptions, TimeTag};
+
+impl TryFrom<&SubtitleFile> for Lyrics {
+    type Error = LyricsError;
+
+    /// Convert the text entries of a subtitle file into timed lines at their start times. The lines of an entry are joined with spaces.
+    fn try_from(file: &SubtitleFile) -> Result<Self, Self::Error> {
+        let entries =
+            file.get_subtitle_entries().map_err(|e| LyricsError::ParseError(e.to_string()))?;
+
+        let mut lyrics = Lyrics::new();
+
+        for entry in entries {
+            if let Some(line) = entry.line {
+                let line = line.lines().map(|l| l.trim()).collect::<Vec<_>>().join(" ");
+
+                lyrics.add_timed_line(TimeTag::new(entry.timespan.start.msecs()), line)?;
+            }
+        }
+
+        Ok(lyrics)
+    }
+}
+
+impl TryFrom<&Lyrics> for SubtitleFile {
+    type Error = LyricsError;
+
+    /// Convert timed lines into a SubRip subtitle file, like `Lyrics::to_srt`. Each line ends when the next one begins, and the last one ends at the `[length:]` tag if possible. Lines with empty text are not converted but still end the previous line.
+    fn try_from(lyrics: &Lyrics) -> Result<Self, Self::Error> {
+        let entries = lyrics
+            .timed_line_spans(SrtOptions::default().last_line_duration)
+            .map(|(start, end, line)| {
+                (
+                    TimeSpan::new(
+                        TimePoint::from_msecs(start.get_timestamp()),
+                        TimePoint::from_msecs(end.get_timestamp()),
+                    ),
+                    plain_line_text(line),
+                )
+            })
+            .filter(|(_, line)| !line.trim().is_empty())
+            .collect();
+
+        let file = SrtFile::create(entries).map_err(|e| LyricsError::ParseError(e.to_string()))?;
+
+        Ok(SubtitleFile::SubRipFile(file))
+    }
+}
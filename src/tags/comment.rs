@@ -0,0 +1,43 @@
+use std::fmt::{self, Display, Formatter};
+
+/// Comments used in LRC which are in the format **[:] text**.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Comment {
+    line_number: Option<usize>,
+    text:        String,
+}
+
+impl Comment {
+    #[inline]
+    pub(crate) fn new<S: Into<String>>(line_number: Option<usize>, text: S) -> Comment {
+        Comment {
+            line_number,
+            text: text.into(),
+        }
+    }
+}
+
+impl Comment {
+    /// Get the number of the line (starting from 1) where this comment was parsed from. `None` if the comment was added programmatically. The line number is for reference only, and is not used to place the comment when the lyrics are serialized.
+    #[inline]
+    pub fn line_number(&self) -> Option<usize> {
+        self.line_number
+    }
+
+    /// Get the text of this comment.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Display for Comment {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        if self.text.is_empty() {
+            f.write_str("[:]")
+        } else {
+            f.write_fmt(format_args!("[:] {}", self.text))
+        }
+    }
+}
@@ -7,21 +7,109 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use unicase::UniCase;
 
-use crate::{IDTagErrorKind, LyricsError};
+use crate::{IDTagErrorKind, IDTagLabel, KnownTag, LyricsError};
 
 static ID_LABEL_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new("^[^\x00-\x08\x0A-\x1F\x7F\\[\\]:]+$").unwrap());
 static ID_TEXT_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new("^[^\x00-\x08\x0A-\x1F\x7F\\[\\]]*$").unwrap());
 
+/// Whether `label` can be used as the label of an `IDTag`. Leading and trailing whitespaces are ignored.
+#[inline]
+pub fn is_valid_tag_label<S: AsRef<str>>(label: S) -> bool {
+    ID_LABEL_RE.is_match(label.as_ref().trim())
+}
+
 /// Tags used in LRC which are in the format **[label: text]**.
+///
+/// The label and the text are trimmed once when the tag is created. The original text, including its surrounding whitespaces, is kept and can be got by the `raw_text` method.
 #[derive(Debug, Clone, Eq)]
 pub struct IDTag {
-    label: UniCase<String>,
-    text:  String,
+    label:      UniCase<String>,
+    text:       String,
+    text_start: usize,
+    text_end:   usize,
+}
+
+/// How strictly an `IDTagBuilder` checks the label and the text.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Educe)]
+#[educe(Default)]
+pub enum ValidationLevel {
+    /// Only accept the labels in `KnownTag`.
+    KnownOnly,
+    /// Accept any label and text which can be written in LRC.
+    #[educe(Default)]
+    AnyValid,
+}
+
+/// A builder of `IDTag`.
+#[derive(Debug, Clone, Default)]
+pub struct IDTagBuilder {
+    label:      String,
+    text:       String,
+    validation: ValidationLevel,
+}
+
+impl IDTagBuilder {
+    /// Set the label, which can be a `KnownTag` or a string.
+    #[inline]
+    pub fn label<L: AsRef<str>>(mut self, label: L) -> IDTagBuilder {
+        self.label = label.as_ref().to_string();
+
+        self
+    }
+
+    /// Set the text.
+    #[inline]
+    pub fn text<T: Into<String>>(mut self, text: T) -> IDTagBuilder {
+        self.text = text.into();
+
+        self
+    }
+
+    /// Set how strictly the label and the text are checked.
+    #[inline]
+    pub fn validation(mut self, validation: ValidationLevel) -> IDTagBuilder {
+        self.validation = validation;
+
+        self
+    }
+
+    /// Build the `IDTag`.
+    pub fn build(self) -> Result<IDTag, LyricsError> {
+        let label = self.label;
+        let text = self.text;
+
+        match self.validation {
+            ValidationLevel::KnownOnly => {
+                if KnownTag::from_label(&label).is_none() {
+                    return Err(LyricsError::IDTagError(IDTagErrorKind::Label));
+                }
+
+                IDTag::from_string(label, text)
+            },
+            ValidationLevel::AnyValid => IDTag::from_string(label, text),
+        }
+    }
+
+    /// Build the `IDTag` without checking the label and the text.
+    ///
+    /// # Safety
+    ///
+    /// The label and the text must be valid for an ID tag, as `IDTag::from_string` checks. Otherwise, the tag may produce broken LRC.
+    #[inline]
+    pub unsafe fn build_unchecked(self) -> IDTag {
+        IDTag::from_string_unchecked(self.label, self.text)
+    }
 }
 
 impl IDTag {
+    /// Create a builder of `IDTag`.
+    #[inline]
+    pub fn builder() -> IDTagBuilder {
+        IDTagBuilder::default()
+    }
+
     /// Create an `IDTag` instance from strings.
     #[inline]
     pub fn from_string<L: Into<String>, T: Into<String>>(
@@ -30,7 +118,7 @@ impl IDTag {
     ) -> Result<IDTag, LyricsError> {
         let label = label.into();
 
-        if !ID_LABEL_RE.is_match(label.trim()) {
+        if !is_valid_tag_label(&label) {
             return Err(LyricsError::IDTagError(IDTagErrorKind::Label));
         }
 
@@ -40,10 +128,7 @@ impl IDTag {
             return Err(LyricsError::IDTagError(IDTagErrorKind::Text));
         }
 
-        Ok(IDTag {
-            label: UniCase::new(label),
-            text,
-        })
+        Ok(unsafe { IDTag::from_string_unchecked(label, text) })
     }
 
     /// Create an `IDTag` instance from strings without checking.
@@ -53,16 +138,51 @@ impl IDTag {
         label: L,
         text: T,
     ) -> IDTag {
-        let label = label.into();
+        let mut label = label.into();
         let text = text.into();
 
+        if label.trim().len() != label.len() {
+            label = label.trim().to_string();
+        }
+
+        let text_start = text.len() - text.trim_start().len();
+        let text_end = text_start + text.trim().len();
+
         IDTag {
             label: UniCase::new(label),
             text,
+            text_start,
+            text_end,
         }
     }
 }
 
+impl IDTag {
+    /// Get the label.
+    #[inline]
+    pub fn get_label(&self) -> &str {
+        self.label.as_str()
+    }
+
+    /// Get the label as an `IDTagLabel`, which can be matched against the standard labels.
+    #[inline]
+    pub fn label(&self) -> IDTagLabel {
+        IDTagLabel::from_label(self.get_label())
+    }
+
+    /// Get the trimmed text.
+    #[inline]
+    pub fn get_text(&self) -> &str {
+        &self.text[self.text_start..self.text_end]
+    }
+
+    /// Get the original text without trimming.
+    #[inline]
+    pub fn raw_text(&self) -> &str {
+        self.text.as_str()
+    }
+}
+
 impl PartialEq for IDTag {
     #[inline]
     fn eq(&self, other: &IDTag) -> bool {
@@ -73,7 +193,7 @@ impl PartialEq for IDTag {
 impl PartialOrd for IDTag {
     #[inline]
     fn partial_cmp(&self, other: &IDTag) -> Option<Ordering> {
-        self.label.partial_cmp(&other.label)
+        Some(self.cmp(other))
     }
 }
 
@@ -87,6 +207,6 @@ impl Ord for IDTag {
 impl Display for IDTag {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        f.write_fmt(format_args!("[{}: {}]", self.label.trim(), self.text.trim()))
+        f.write_fmt(format_args!("[{}: {}]", self.label, self.get_text()))
     }
 }
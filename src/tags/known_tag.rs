@@ -0,0 +1,140 @@
+use std::fmt::{self, Display, Formatter};
+
+/// Standard labels of ID tags.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum KnownTag {
+    /// `ti`, the title of the song.
+    Ti,
+    /// `ar`, the artist performing the song.
+    Ar,
+    /// `al`, the album the song is on.
+    Al,
+    /// `au`, the author of the song.
+    Au,
+    /// `by`, the creator of the LRC file.
+    By,
+    /// `length`, the length of the song.
+    Length,
+    /// `offset`, a global offset in milliseconds applied to all time tags.
+    Offset,
+    /// `re`, the player or editor that created the LRC file.
+    Re,
+    /// `ve`, the version of the program.
+    Ve,
+    /// `la`, the language of the lyrics.
+    La,
+}
+
+impl KnownTag {
+    /// All known labels.
+    pub const ALL: [KnownTag; 10] = [
+        KnownTag::Ti,
+        KnownTag::Ar,
+        KnownTag::Al,
+        KnownTag::Au,
+        KnownTag::By,
+        KnownTag::Length,
+        KnownTag::Offset,
+        KnownTag::Re,
+        KnownTag::Ve,
+        KnownTag::La,
+    ];
+
+    /// Get the label text.
+    #[inline]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            KnownTag::Ti => "ti",
+            KnownTag::Ar => "ar",
+            KnownTag::Al => "al",
+            KnownTag::Au => "au",
+            KnownTag::By => "by",
+            KnownTag::Length => "length",
+            KnownTag::Offset => "offset",
+            KnownTag::Re => "re",
+            KnownTag::Ve => "ve",
+            KnownTag::La => "la",
+        }
+    }
+
+    /// Find the known label which matches `label` case-insensitively.
+    #[inline]
+    pub fn from_label<S: AsRef<str>>(label: S) -> Option<KnownTag> {
+        let label = label.as_ref().trim();
+
+        KnownTag::ALL.iter().copied().find(|known| known.as_str().eq_ignore_ascii_case(label))
+    }
+}
+
+impl AsRef<str> for KnownTag {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for KnownTag {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The label of an ID tag, which is either a standard label or another one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IDTagLabel {
+    Known(KnownTag),
+    Other(String),
+}
+
+impl IDTagLabel {
+    /// Classify a label text.
+    #[inline]
+    pub fn from_label<S: AsRef<str>>(label: S) -> IDTagLabel {
+        let label = label.as_ref();
+
+        match KnownTag::from_label(label) {
+            Some(known) => IDTagLabel::Known(known),
+            None => IDTagLabel::Other(label.trim().to_string()),
+        }
+    }
+
+    /// Get the label text.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        match self {
+            IDTagLabel::Known(known) => known.as_str(),
+            IDTagLabel::Other(label) => label.as_str(),
+        }
+    }
+
+    /// Get the standard label if this is one.
+    #[inline]
+    pub fn known(&self) -> Option<KnownTag> {
+        match self {
+            IDTagLabel::Known(known) => Some(*known),
+            IDTagLabel::Other(_) => None,
+        }
+    }
+}
+
+impl From<KnownTag> for IDTagLabel {
+    #[inline]
+    fn from(known: KnownTag) -> Self {
+        IDTagLabel::Known(known)
+    }
+}
+
+impl AsRef<str> for IDTagLabel {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for IDTagLabel {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(self.as_str())
+    }
+}
@@ -1,5 +1,9 @@
+mod comment;
 mod id_tag;
+mod known_tag;
 mod time_tag;
 
+pub use comment::*;
 pub use id_tag::*;
+pub use known_tag::*;
 pub use time_tag::*;
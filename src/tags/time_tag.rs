@@ -1,9 +1,15 @@
 use std::{
+    cmp::Ordering,
     fmt::{self, Display, Formatter},
+    ops::{Add, AddAssign, Sub, SubAssign},
     str::FromStr,
+    time::Duration,
 };
 
-use crate::{timestamp::Timestamp, LyricsError};
+use crate::{
+    timestamp::{SliceWriter, Timestamp},
+    LyricsError, Rounding,
+};
 
 /// Tags used in LRC which are in the format **[mm:ss.xx]** or **[mm:ss]** to represent time.
 #[derive(Debug, PartialOrd, PartialEq, Ord, Eq, Hash, Clone, Copy)]
@@ -36,6 +42,74 @@ impl TimeTag {
     pub fn get_timestamp(self) -> i64 {
         self.0.get_timestamp()
     }
+
+    /// Get how long it is from this time tag to `other`, which is negative if `other` is earlier. The same as `other - self`.
+    #[inline]
+    pub fn duration_until(self, other: TimeTag) -> Timestamp {
+        other - self
+    }
+
+    /// Whether this time tag and `other` are at most `tolerance` milliseconds apart. A negative tolerance is treated as zero.
+    #[inline]
+    pub fn approx_eq<N: Into<i64>>(self, other: TimeTag, tolerance: N) -> bool {
+        let tolerance = tolerance.into().max(0) as u64;
+
+        self.get_timestamp().abs_diff(other.get_timestamp()) <= tolerance
+    }
+
+    /// Compare this time tag with `other`, treating them as equal if they are at most `tolerance` milliseconds apart.
+    #[inline]
+    pub fn cmp_with_tolerance<N: Into<i64>>(self, other: TimeTag, tolerance: N) -> Ordering {
+        if self.approx_eq(other, tolerance) {
+            Ordering::Equal
+        } else {
+            self.cmp(&other)
+        }
+    }
+
+    /// Shift the time tag by `rhs`, returning `None` if the milliseconds overflow.
+    #[inline]
+    pub fn checked_add(self, rhs: Timestamp) -> Option<TimeTag> {
+        self.0.checked_add(rhs).map(TimeTag)
+    }
+
+    /// Shift the time tag back by `rhs`, returning `None` if the milliseconds overflow.
+    #[inline]
+    pub fn checked_sub(self, rhs: Timestamp) -> Option<TimeTag> {
+        self.0.checked_sub(rhs).map(TimeTag)
+    }
+
+    /// Shift the time tag by `rhs`, saturating at the bounds of `i64` milliseconds.
+    #[inline]
+    pub fn saturating_add(self, rhs: Timestamp) -> TimeTag {
+        TimeTag(self.0.saturating_add(rhs))
+    }
+
+    /// Shift the time tag back by `rhs`, saturating at the bounds of `i64` milliseconds.
+    #[inline]
+    pub fn saturating_sub(self, rhs: Timestamp) -> TimeTag {
+        TimeTag(self.0.saturating_sub(rhs))
+    }
+
+    /// Round the time tag to hundredths of a second, the precision of the `[mm:ss.xx]` format.
+    #[inline]
+    pub fn round_to_hundredths(self, rounding: Rounding) -> TimeTag {
+        TimeTag(self.0.round_to_hundredths(rounding))
+    }
+}
+
+impl TimeTag {
+    /// Get the timestamp.
+    #[inline]
+    pub(crate) fn timestamp(self) -> Timestamp {
+        self.0
+    }
+
+    /// Write the time tag in the `[mm:ss.xx]` format into `buffer` without allocating memory. Returns the number of written bytes.
+    #[inline]
+    pub fn format_into(self, buffer: &mut [u8]) -> Result<usize, LyricsError> {
+        SliceWriter::new(buffer).write_display(self)
+    }
 }
 
 impl Display for TimeTag {
@@ -60,3 +134,125 @@ impl From<TimeTag> for i64 {
         tt.0.into()
     }
 }
+
+impl TryFrom<TimeTag> for Duration {
+    type Error = LyricsError;
+
+    /// Fail if the time tag is negative.
+    #[inline]
+    fn try_from(tt: TimeTag) -> Result<Duration, LyricsError> {
+        Duration::try_from(tt.0)
+    }
+}
+
+impl Add<Timestamp> for TimeTag {
+    type Output = TimeTag;
+
+    /// Shift the time tag later.
+    #[inline]
+    fn add(self, rhs: Timestamp) -> TimeTag {
+        TimeTag(self.0 + rhs)
+    }
+}
+
+impl Add<i64> for TimeTag {
+    type Output = TimeTag;
+
+    /// Shift the time tag later by milliseconds.
+    #[inline]
+    fn add(self, rhs: i64) -> TimeTag {
+        TimeTag(self.0 + rhs)
+    }
+}
+
+impl Sub<Timestamp> for TimeTag {
+    type Output = TimeTag;
+
+    /// Shift the time tag earlier.
+    #[inline]
+    fn sub(self, rhs: Timestamp) -> TimeTag {
+        TimeTag(self.0 - rhs)
+    }
+}
+
+impl Sub<i64> for TimeTag {
+    type Output = TimeTag;
+
+    /// Shift the time tag earlier by milliseconds.
+    #[inline]
+    fn sub(self, rhs: i64) -> TimeTag {
+        TimeTag(self.0 - rhs)
+    }
+}
+
+impl Sub for TimeTag {
+    type Output = Timestamp;
+
+    /// Get how long it is from `rhs` to this time tag.
+    #[inline]
+    fn sub(self, rhs: TimeTag) -> Timestamp {
+        self.0 - rhs.0
+    }
+}
+
+impl AddAssign<Timestamp> for TimeTag {
+    #[inline]
+    fn add_assign(&mut self, rhs: Timestamp) {
+        self.0 += rhs;
+    }
+}
+
+impl AddAssign<i64> for TimeTag {
+    #[inline]
+    fn add_assign(&mut self, rhs: i64) {
+        self.0 += rhs;
+    }
+}
+
+impl SubAssign<Timestamp> for TimeTag {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Timestamp) {
+        self.0 -= rhs;
+    }
+}
+
+impl SubAssign<i64> for TimeTag {
+    #[inline]
+    fn sub_assign(&mut self, rhs: i64) {
+        self.0 -= rhs;
+    }
+}
+
+impl Add<Duration> for TimeTag {
+    type Output = TimeTag;
+
+    /// Shift the time tag later.
+    #[inline]
+    fn add(self, rhs: Duration) -> TimeTag {
+        TimeTag(self.0 + rhs)
+    }
+}
+
+impl Sub<Duration> for TimeTag {
+    type Output = TimeTag;
+
+    /// Shift the time tag earlier.
+    #[inline]
+    fn sub(self, rhs: Duration) -> TimeTag {
+        TimeTag(self.0 - rhs)
+    }
+}
+
+impl AddAssign<Duration> for TimeTag {
+    #[inline]
+    fn add_assign(&mut self, rhs: Duration) {
+        self.0 += rhs;
+    }
+}
+
+impl SubAssign<Duration> for TimeTag {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Duration) {
+        self.0 -= rhs;
+    }
+}
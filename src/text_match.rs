@@ -0,0 +1,103 @@
+use std::borrow::Cow;
+
+use crate::Lyrics;
+
+/// Options for comparing texts of lines.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct TextMatchOptions {
+    /// Compare ASCII letters case-insensitively.
+    pub case_insensitive:     bool,
+    /// Compare all letters case-insensitively by converting them to lowercase with Unicode rules.
+    pub unicode_case_folding: bool,
+    /// Ignore characters which are neither alphanumeric nor whitespace.
+    pub ignore_punctuation:   bool,
+    /// Treat a run of whitespaces as a single space and ignore leading and trailing whitespaces.
+    pub ignore_whitespace:    bool,
+}
+
+impl TextMatchOptions {
+    /// Options which compare texts exactly.
+    #[inline]
+    pub fn exact() -> TextMatchOptions {
+        TextMatchOptions::default()
+    }
+
+    /// Options which enable all kinds of normalization.
+    #[inline]
+    pub fn loose() -> TextMatchOptions {
+        TextMatchOptions {
+            case_insensitive:     true,
+            unicode_case_folding: true,
+            ignore_punctuation:   true,
+            ignore_whitespace:    true,
+        }
+    }
+
+    /// Normalize `text` so that texts matched by these options become equal.
+    pub fn normalize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if *self == TextMatchOptions::exact() {
+            return Cow::Borrowed(text);
+        }
+
+        let mut s = String::with_capacity(text.len());
+        let mut pending_space = false;
+
+        for c in text.chars() {
+            if self.ignore_punctuation && !c.is_alphanumeric() && !c.is_whitespace() {
+                continue;
+            }
+
+            if self.ignore_whitespace && c.is_whitespace() {
+                pending_space = !s.is_empty();
+
+                continue;
+            }
+
+            if pending_space {
+                s.push(' ');
+
+                pending_space = false;
+            }
+
+            if self.unicode_case_folding {
+                s.extend(c.to_lowercase());
+            } else if self.case_insensitive {
+                s.push(c.to_ascii_lowercase());
+            } else {
+                s.push(c);
+            }
+        }
+
+        Cow::Owned(s)
+    }
+
+    /// Whether `a` and `b` are equal under these options.
+    #[inline]
+    pub fn texts_eq(&self, a: &str, b: &str) -> bool {
+        self.normalize(a) == self.normalize(b)
+    }
+
+    /// Whether `haystack` contains `needle` under these options.
+    #[inline]
+    pub fn contains(&self, haystack: &str, needle: &str) -> bool {
+        self.normalize(haystack).contains(self.normalize(needle).as_ref())
+    }
+}
+
+impl Lyrics {
+    /// Find the timed lines whose texts are the same under `options`. Each group contains the indices of at least two timed lines.
+    pub fn duplicate_texts(&self, options: &TextMatchOptions) -> Vec<Vec<usize>> {
+        let mut groups: Vec<(Cow<str>, Vec<usize>)> = Vec::new();
+
+        for (i, (_, line)) in self.timed_lines.iter().enumerate() {
+            let text = options.normalize(line.as_ref());
+
+            match groups.iter_mut().find(|(t, _)| *t == text) {
+                Some((_, indices)) => indices.push(i),
+                None => groups.push((text, vec![i])),
+            }
+        }
+
+        groups.into_iter().map(|(_, indices)| indices).filter(|indices| indices.len() > 1).collect()
+    }
+}
@@ -0,0 +1,42 @@
+//! Helpers for time tags of timed lines.
+
+use crate::TimeTag;
+
+/// Iterate the gaps in milliseconds between adjacent time tags.
+#[inline]
+fn gaps<T>(timed_lines: &[(TimeTag, T)]) -> impl Iterator<Item = i64> + '_ {
+    timed_lines.windows(2).map(|w| w[1].0.get_timestamp().saturating_sub(w[0].0.get_timestamp()))
+}
+
+/// Get the smallest gap in milliseconds between adjacent time tags. `None` if there are fewer than two timed lines.
+#[inline]
+pub fn min_gap<T>(timed_lines: &[(TimeTag, T)]) -> Option<i64> {
+    gaps(timed_lines).min()
+}
+
+/// Get the largest gap in milliseconds between adjacent time tags. `None` if there are fewer than two timed lines.
+#[inline]
+pub fn max_gap<T>(timed_lines: &[(TimeTag, T)]) -> Option<i64> {
+    gaps(timed_lines).max()
+}
+
+/// Get the mean gap in milliseconds between adjacent time tags. `None` if there are fewer than two timed lines.
+#[inline]
+pub fn mean_gap<T>(timed_lines: &[(TimeTag, T)]) -> Option<f64> {
+    let len = timed_lines.len();
+
+    if len < 2 {
+        return None;
+    }
+
+    let total =
+        timed_lines[len - 1].0.get_timestamp() as f64 - timed_lines[0].0.get_timestamp() as f64;
+
+    Some(total / (len - 1) as f64)
+}
+
+/// Whether the time tags are in ascending order.
+#[inline]
+pub fn is_sorted<T>(timed_lines: &[(TimeTag, T)]) -> bool {
+    timed_lines.windows(2).all(|w| w[0].0 <= w[1].0)
+}
@@ -0,0 +1,20 @@
+use ::time::Duration;
+
+use crate::Timestamp;
+
+impl From<Timestamp> for Duration {
+    #[inline]
+    fn from(t: Timestamp) -> Duration {
+        Duration::milliseconds(t.get_timestamp())
+    }
+}
+
+impl From<Duration> for Timestamp {
+    /// The part smaller than a millisecond is truncated, and a duration too long for `i64` milliseconds saturates.
+    #[inline]
+    fn from(duration: Duration) -> Timestamp {
+        let milliseconds = duration.whole_milliseconds();
+
+        Timestamp::new(milliseconds.clamp(i64::MIN.into(), i64::MAX.into()) as i64)
+    }
+}
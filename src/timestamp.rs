@@ -3,7 +3,9 @@ pub struct Timestamp(i64);
 
 use std::{
     fmt::{self, Display, Formatter, Write},
+    ops::{Add, AddAssign, Neg, Sub, SubAssign},
     str::FromStr,
+    time::Duration,
 };
 
 use once_cell::sync::Lazy;
@@ -12,7 +14,10 @@ use regex::Regex;
 use crate::LyricsError;
 
 static TIMESTAMP_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^(-)?(\d{1,10}):(-)?(\d{1,2})(\.(-)?(\d{1,2}))?$").unwrap());
+    Lazy::new(|| Regex::new(r"^(-)?([0-9]{1,10}):(-)?([0-9]{1,2})(\.(-)?([0-9]{1,2}))?$").unwrap());
+
+static HOURS_TIMESTAMP_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(-)?([0-9]{1,7}):([0-9]{1,2}):([0-9]{1,2})\.([0-9]{2})$").unwrap());
 
 impl Timestamp {
     /// Create a timestamp with a number in milliseconds.
@@ -21,9 +26,16 @@ impl Timestamp {
         Timestamp(timestamp.into())
     }
 
-    /// Create a timestamp with a string.
+    /// Create a timestamp with a string in the `mm:ss.xx` format, or the `h:mm:ss.xx` format written with `TimeFormat::HoursMinutesSeconds`.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str<S: AsRef<str>>(timestamp: S) -> Result<Timestamp, LyricsError> {
-        let c = match TIMESTAMP_RE.captures(timestamp.as_ref()) {
+        let timestamp = timestamp.as_ref();
+
+        if let Some(c) = HOURS_TIMESTAMP_RE.captures(timestamp) {
+            return Self::from_hours_captures(&c);
+        }
+
+        let c = match TIMESTAMP_RE.captures(timestamp) {
             Some(c) => c,
             None => {
                 return Err(LyricsError::ParseError(String::from(
@@ -111,6 +123,31 @@ impl Timestamp {
 
         Ok(Timestamp::new(millisecond))
     }
+
+    /// Create a timestamp with the captures of `HOURS_TIMESTAMP_RE`.
+    fn from_hours_captures(c: &regex::Captures) -> Result<Timestamp, LyricsError> {
+        let number = |i: usize| c.get(i).unwrap().as_str().parse::<i64>().unwrap();
+
+        let (hour, minute, second) = (number(2), number(3), number(4));
+
+        if minute >= 60 {
+            return Err(LyricsError::ParseError(String::from(
+                "The format of the string is not incorrect. The number of minutes must be smaller \
+                 than 60.",
+            )));
+        }
+
+        if second >= 60 {
+            return Err(LyricsError::ParseError(String::from(
+                "The format of the string is not incorrect. The number of seconds must be smaller \
+                 than 60.",
+            )));
+        }
+
+        let millisecond = hour * 3600000 + minute * 60000 + second * 1000 + number(5) * 10;
+
+        Ok(Timestamp::new(if c.get(1).is_some() { -millisecond } else { millisecond }))
+    }
 }
 
 impl Timestamp {
@@ -119,27 +156,250 @@ impl Timestamp {
     pub fn get_timestamp(self) -> i64 {
         self.0
     }
+
+    /// Create a timestamp with minutes, seconds and hundredths of a second, like `mm:ss.xx`. The seconds must be smaller than 60, and the hundredths must be smaller than 100.
+    pub fn from_components(
+        minutes: u32,
+        seconds: u8,
+        hundredths: u8,
+    ) -> Result<Timestamp, LyricsError> {
+        if seconds >= 60 {
+            return Err(LyricsError::FormatError("The number of seconds must be smaller than 60."));
+        }
+
+        if hundredths >= 100 {
+            return Err(LyricsError::FormatError(
+                "The number of hundredths of a second must be smaller than 100.",
+            ));
+        }
+
+        Ok(Timestamp(minutes as i64 * 60000 + seconds as i64 * 1000 + hundredths as i64 * 10))
+    }
+
+    /// Whether the timestamp is negative. The components are of the absolute value.
+    #[inline]
+    pub fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    /// Get the whole minutes.
+    #[inline]
+    pub fn minutes(self) -> u64 {
+        self.0.unsigned_abs() / 60000
+    }
+
+    /// Get the whole seconds after the minutes, in `[0, 60)`.
+    #[inline]
+    pub fn seconds(self) -> u8 {
+        (self.0.unsigned_abs() / 1000 % 60) as u8
+    }
+
+    /// Get the whole hundredths of a second after the seconds, in `[0, 100)`. The milliseconds are truncated.
+    #[inline]
+    pub fn hundredths(self) -> u8 {
+        (self.subsec_millis() / 10) as u8
+    }
+
+    /// Get the milliseconds after the seconds, in `[0, 1000)`.
+    #[inline]
+    pub fn subsec_millis(self) -> u16 {
+        (self.0.unsigned_abs() % 1000) as u16
+    }
+
+    /// Add `rhs`, returning `None` if the milliseconds overflow.
+    #[inline]
+    pub fn checked_add(self, rhs: Timestamp) -> Option<Timestamp> {
+        self.0.checked_add(rhs.0).map(Timestamp)
+    }
+
+    /// Subtract `rhs`, returning `None` if the milliseconds overflow.
+    #[inline]
+    pub fn checked_sub(self, rhs: Timestamp) -> Option<Timestamp> {
+        self.0.checked_sub(rhs.0).map(Timestamp)
+    }
+
+    /// Add `rhs`, saturating at the bounds of `i64` milliseconds.
+    #[inline]
+    pub fn saturating_add(self, rhs: Timestamp) -> Timestamp {
+        Timestamp(self.0.saturating_add(rhs.0))
+    }
+
+    /// Subtract `rhs`, saturating at the bounds of `i64` milliseconds.
+    #[inline]
+    pub fn saturating_sub(self, rhs: Timestamp) -> Timestamp {
+        Timestamp(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Create a timestamp with a `Duration`. The part smaller than a millisecond is truncated, and a duration too long for `i64` milliseconds saturates.
+    #[inline]
+    pub fn from_duration(duration: Duration) -> Timestamp {
+        Timestamp(i64::try_from(duration.as_millis()).unwrap_or(i64::MAX))
+    }
 }
 
-impl Display for Timestamp {
+/// How to round milliseconds to hundredths of a second.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Educe)]
+#[educe(Default)]
+pub enum Rounding {
+    /// Round half away from zero, like `00:00.005` to `00:00.01`.
+    #[educe(Default)]
+    HalfUp,
+    /// Round half to even, like `00:00.005` to `00:00.00` and `00:00.015` to `00:00.02`, so that repeated conversions do not drift in one direction.
+    HalfEven,
+    /// Drop the milliseconds, like `00:00.019` to `00:00.01`.
+    Truncate,
+}
+
+impl Timestamp {
+    /// Round the timestamp to hundredths of a second. Negative timestamps are rounded symmetrically to positive ones.
+    pub fn round_to_hundredths(self, rounding: Rounding) -> Timestamp {
+        let magnitude = self.0.unsigned_abs();
+
+        let hundredths = magnitude / 10;
+        let remainder = magnitude % 10;
+
+        let round_up = match rounding {
+            Rounding::HalfUp => remainder >= 5,
+            Rounding::HalfEven => remainder > 5 || (remainder == 5 && hundredths % 2 == 1),
+            Rounding::Truncate => false,
+        };
+
+        let hundredths = if round_up { hundredths + 1 } else { hundredths };
+
+        let magnitude = i64::try_from(hundredths * 10).unwrap_or(i64::MAX / 10 * 10);
+
+        if self.0 < 0 {
+            Timestamp(-magnitude)
+        } else {
+            Timestamp(magnitude)
+        }
+    }
+}
+
+/// A `fmt::Write` implementation which writes into a fixed buffer.
+pub(crate) struct SliceWriter<'a> {
+    buffer: &'a mut [u8],
+    len:    usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    #[inline]
+    pub(crate) fn new(buffer: &'a mut [u8]) -> SliceWriter<'a> {
+        SliceWriter {
+            buffer,
+            len: 0,
+        }
+    }
+
+    /// Write `value` with its `Display` implementation and return the number of written bytes.
+    #[inline]
+    pub(crate) fn write_display<T: Display>(mut self, value: T) -> Result<usize, LyricsError> {
+        self.write_fmt(format_args!("{}", value))
+            .map_err(|_| LyricsError::FormatError("The buffer is too small."))?;
+
+        Ok(self.len)
+    }
+}
+
+impl<'a> Write for SliceWriter<'a> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
+        let end = self.len + s.len();
+
+        if end > self.buffer.len() {
+            return Err(fmt::Error);
+        }
+
+        self.buffer[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+
+        Ok(())
+    }
+}
+
+impl Timestamp {
+    /// Write the timestamp in the `mm:ss.xx` format into `buffer` without allocating memory. Returns the number of written bytes.
+    #[inline]
+    pub fn format_into(self, buffer: &mut [u8]) -> Result<usize, LyricsError> {
+        SliceWriter::new(buffer).write_display(self)
+    }
+}
+
+/// How to write timestamps of an hour or longer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Educe)]
+#[educe(Default)]
+pub enum TimeFormat {
+    /// `mm:ss.xx`, letting the minutes go beyond 59, like `205:45.68`.
+    #[educe(Default)]
+    MinutesSeconds,
+    /// `h:mm:ss.xx` for an hour or longer, like `3:25:45.68`, and `mm:ss.xx` otherwise.
+    HoursMinutesSeconds,
+}
+
+/// A `Display` adapter of `Timestamp` which rounds the milliseconds to hundredths of a second in a specified way. Created by `Timestamp::display`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TimestampDisplay {
+    timestamp: Timestamp,
+    rounding:  Rounding,
+    format:    TimeFormat,
+}
+
+impl TimestampDisplay {
+    /// Set how to write the timestamp if it is an hour or longer.
+    #[inline]
+    pub fn with_format(mut self, format: TimeFormat) -> TimestampDisplay {
+        self.format = format;
+
+        self
+    }
+}
+
+impl Display for TimestampDisplay {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        let mut timestamp = self.0;
+        let timestamp = self.timestamp.round_to_hundredths(self.rounding).0;
 
         if timestamp < 0 {
             f.write_char('-')?;
-            timestamp *= -1;
         }
 
-        let minute = timestamp / 60000;
-        let second = (timestamp % 60000) / 1000;
-        let millisecond = timestamp % 1000;
+        let timestamp = timestamp.unsigned_abs();
+
+        if self.format == TimeFormat::HoursMinutesSeconds && timestamp >= 3600000 {
+            f.write_fmt(format_args!(
+                "{}:{:02}:{:02}.{:02}",
+                timestamp / 3600000,
+                timestamp % 3600000 / 60000,
+                timestamp % 60000 / 1000,
+                timestamp % 1000 / 10
+            ))
+        } else {
+            f.write_fmt(format_args!(
+                "{:02}:{:02}.{:02}",
+                timestamp / 60000,
+                timestamp % 60000 / 1000,
+                timestamp % 1000 / 10
+            ))
+        }
+    }
+}
+
+impl Timestamp {
+    /// Get a `Display` adapter which writes the timestamp in the `mm:ss.xx` format, rounding the milliseconds with `rounding`. Rounding up carries into the seconds and minutes, like `00:00.995` to `00:01.00`.
+    #[inline]
+    pub fn display(self, rounding: Rounding) -> TimestampDisplay {
+        TimestampDisplay {
+            timestamp: self,
+            rounding,
+            format: TimeFormat::MinutesSeconds,
+        }
+    }
+}
 
-        f.write_fmt(format_args!(
-            "{:02}:{:02}.{:02}",
-            minute,
-            second,
-            f64::round(millisecond as f64 / 10f64)
-        ))
+impl Display for Timestamp {
+    /// Write the timestamp in the `mm:ss.xx` format, rounding half away from zero.
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        Display::fmt(&self.display(Rounding::HalfUp), f)
     }
 }
 
@@ -159,6 +419,132 @@ impl From<Timestamp> for i64 {
     }
 }
 
+impl From<Duration> for Timestamp {
+    #[inline]
+    fn from(duration: Duration) -> Timestamp {
+        Timestamp::from_duration(duration)
+    }
+}
+
+impl TryFrom<Timestamp> for Duration {
+    type Error = LyricsError;
+
+    /// Fail if the timestamp is negative.
+    #[inline]
+    fn try_from(t: Timestamp) -> Result<Duration, LyricsError> {
+        u64::try_from(t.0)
+            .map(Duration::from_millis)
+            .map_err(|_| LyricsError::FormatError("The timestamp is negative."))
+    }
+}
+
+impl Add for Timestamp {
+    type Output = Timestamp;
+
+    #[inline]
+    fn add(self, rhs: Timestamp) -> Timestamp {
+        Timestamp(self.0 + rhs.0)
+    }
+}
+
+impl Add<i64> for Timestamp {
+    type Output = Timestamp;
+
+    /// Add milliseconds.
+    #[inline]
+    fn add(self, rhs: i64) -> Timestamp {
+        Timestamp(self.0 + rhs)
+    }
+}
+
+impl Sub for Timestamp {
+    type Output = Timestamp;
+
+    #[inline]
+    fn sub(self, rhs: Timestamp) -> Timestamp {
+        Timestamp(self.0 - rhs.0)
+    }
+}
+
+impl Sub<i64> for Timestamp {
+    type Output = Timestamp;
+
+    /// Subtract milliseconds.
+    #[inline]
+    fn sub(self, rhs: i64) -> Timestamp {
+        Timestamp(self.0 - rhs)
+    }
+}
+
+impl Add<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    #[inline]
+    fn add(self, rhs: Duration) -> Timestamp {
+        self + Timestamp::from_duration(rhs)
+    }
+}
+
+impl Sub<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    #[inline]
+    fn sub(self, rhs: Duration) -> Timestamp {
+        self - Timestamp::from_duration(rhs)
+    }
+}
+
+impl Neg for Timestamp {
+    type Output = Timestamp;
+
+    #[inline]
+    fn neg(self) -> Timestamp {
+        Timestamp(-self.0)
+    }
+}
+
+impl AddAssign for Timestamp {
+    #[inline]
+    fn add_assign(&mut self, rhs: Timestamp) {
+        self.0 += rhs.0;
+    }
+}
+
+impl AddAssign<i64> for Timestamp {
+    #[inline]
+    fn add_assign(&mut self, rhs: i64) {
+        self.0 += rhs;
+    }
+}
+
+impl SubAssign for Timestamp {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Timestamp) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl SubAssign<i64> for Timestamp {
+    #[inline]
+    fn sub_assign(&mut self, rhs: i64) {
+        self.0 -= rhs;
+    }
+}
+
+impl AddAssign<Duration> for Timestamp {
+    #[inline]
+    fn add_assign(&mut self, rhs: Duration) {
+        *self += Timestamp::from_duration(rhs);
+    }
+}
+
+impl SubAssign<Duration> for Timestamp {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self -= Timestamp::from_duration(rhs);
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -184,6 +570,67 @@ mod test {
         assert_eq!("205:45.68", t.to_string());
     }
 
+    #[test]
+    fn display_carry() {
+        assert_eq!("00:01.00", Timestamp::new(995).to_string());
+        assert_eq!("00:01.00", Timestamp::new(999).to_string());
+        assert_eq!("01:00.00", Timestamp::new(59999).to_string());
+        assert_eq!("-01:00.00", Timestamp::new(-59995).to_string());
+        assert_eq!("-00:00.01", Timestamp::new(-5).to_string());
+        assert_eq!("00:00.00", Timestamp::new(-4).to_string());
+
+        assert_eq!("00:00.99", Timestamp::new(999).display(Rounding::Truncate).to_string());
+        assert_eq!("00:59.99", Timestamp::new(59999).display(Rounding::Truncate).to_string());
+        assert_eq!("00:00.02", Timestamp::new(25).display(Rounding::HalfEven).to_string());
+        assert_eq!("00:00.04", Timestamp::new(35).display(Rounding::HalfEven).to_string());
+    }
+
+    #[test]
+    fn format_into() {
+        let mut buffer = [0u8; 16];
+
+        let len = Timestamp::new(1234567).format_into(&mut buffer).unwrap();
+        assert_eq!(b"20:34.57", &buffer[..len]);
+
+        let len = Timestamp::new(-1234567).format_into(&mut buffer).unwrap();
+        assert_eq!(b"-20:34.57", &buffer[..len]);
+
+        assert!(Timestamp::new(1234567).format_into(&mut buffer[..7]).is_err());
+    }
+
+    #[test]
+    fn round_to_hundredths() {
+        let round = |timestamp: i64, rounding| {
+            Timestamp::new(timestamp).round_to_hundredths(rounding).get_timestamp()
+        };
+
+        assert_eq!(10, round(5, Rounding::HalfUp));
+        assert_eq!(0, round(5, Rounding::HalfEven));
+        assert_eq!(20, round(15, Rounding::HalfEven));
+        assert_eq!(20, round(16, Rounding::HalfEven));
+        assert_eq!(10, round(19, Rounding::Truncate));
+        assert_eq!(1000, round(995, Rounding::HalfUp));
+        assert_eq!(-10, round(-5, Rounding::HalfUp));
+        assert_eq!(-10, round(-19, Rounding::Truncate));
+        assert_eq!(1230, round(1230, Rounding::HalfEven));
+    }
+
+    #[test]
+    fn display_hours() {
+        let display = |timestamp: i64| {
+            Timestamp::new(timestamp)
+                .display(Rounding::HalfUp)
+                .with_format(TimeFormat::HoursMinutesSeconds)
+                .to_string()
+        };
+
+        assert_eq!("205:45.68", Timestamp::new(12345678).to_string());
+        assert_eq!("3:25:45.68", display(12345678));
+        assert_eq!("59:59.99", display(3599990));
+        assert_eq!("1:00:00.00", display(3599995));
+        assert_eq!("-1:00:00.00", display(-3600000));
+    }
+
     #[test]
     fn negative_timestamp() {
         let t = Timestamp::new(-1234567);
@@ -213,6 +660,13 @@ mod test {
         assert_eq!("-00:00.12", t.to_string());
     }
 
+    #[test]
+    fn parse_hours() {
+        assert_eq!(12345680, Timestamp::from_str("3:25:45.68").unwrap().get_timestamp());
+        assert_eq!(3600000, Timestamp::from_str("1:00:00.00").unwrap().get_timestamp());
+        assert_eq!(-3723500, Timestamp::from_str("-1:02:03.50").unwrap().get_timestamp());
+    }
+
     #[test]
     fn parse_errors() {
         assert!(Timestamp::from_str("abc").is_err());
@@ -226,5 +680,12 @@ mod test {
         assert!(Timestamp::from_str("12:34.-56").is_err());
         assert!(Timestamp::from_str("00:34.-56").is_err());
         assert!(Timestamp::from_str("12:00.-56").is_err());
+
+        assert!(Timestamp::from_str("1:60:00.00").is_err());
+        assert!(Timestamp::from_str("1:00:60.00").is_err());
+        assert!(Timestamp::from_str("1:00:00.5").is_err());
+
+        assert!(Timestamp::from_str("１２:34.56").is_err());
+        assert!(Timestamp::from_str("12:34.５6").is_err());
     }
 }
@@ -0,0 +1,17 @@
+use crate::Lyrics;
+
+impl Lyrics {
+    /// Apply the edits made by `f` atomically. The edits are made on a copy of this lyrics, which replaces this lyrics only if `f` returns `Ok`, so this lyrics is left unchanged if `f` returns an error or panics.
+    pub fn transaction<T, E, F: FnOnce(&mut Lyrics) -> Result<T, E>>(
+        &mut self,
+        f: F,
+    ) -> Result<T, E> {
+        let mut tx = self.clone();
+
+        let value = f(&mut tx)?;
+
+        *self = tx;
+
+        Ok(value)
+    }
+}
@@ -0,0 +1,278 @@
+use std::fmt::{self, Write};
+
+use roxmltree::{Document, Node};
+
+use crate::{
+    enhanced::{push_word_time_tag, word_end_time, word_segments},
+    escape_brackets, unescape_brackets, KnownTag, Lyrics, LyricsError, TimeTag,
+};
+
+/// The namespace of TTML.
+const TTML_NAMESPACE: &str = "http://www.w3.org/ns/ttml";
+
+/// How long the last timed line is shown if the lyrics has no `[length:]` tag after it, in milliseconds.
+const LAST_LINE_DURATION: i64 = 5000;
+
+/// The namespace of TTML metadata elements, like `<ttm:title>`.
+const TTML_METADATA_NAMESPACE: &str = "http://www.w3.org/ns/ttml#metadata";
+
+/// Parse a TTML time expression, like `12.345`, `1:02.345`, `00:01:02.345`, `12.3s` or `345ms`, into milliseconds.
+fn parse_time_expression(s: &str) -> Option<i64> {
+    let s = s.trim();
+
+    let seconds = |s: &str| -> Option<i64> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+            return None;
+        }
+
+        s.parse::<f64>().ok().map(|n| (n * 1000.0).round() as i64)
+    };
+
+    if let Some(n) = s.strip_suffix("ms") {
+        return seconds(n).map(|n| (n as f64 / 1000.0).round() as i64);
+    }
+
+    for (suffix, scale) in [("h", 3600), ("m", 60), ("s", 1)] {
+        if let Some(n) = s.strip_suffix(suffix) {
+            return seconds(n).and_then(|n| n.checked_mul(scale));
+        }
+    }
+
+    let mut parts = s.rsplit(':');
+
+    let mut timestamp = seconds(parts.next()?)?;
+
+    for scale in [60000, 3600000] {
+        match parts.next() {
+            Some(part) => {
+                timestamp = timestamp.checked_add(part.parse::<i64>().ok()?.checked_mul(scale)?)?
+            },
+            None => return Some(timestamp),
+        }
+    }
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(timestamp)
+}
+
+#[inline]
+fn get_time(node: Node, name: &str) -> Result<Option<i64>, LyricsError> {
+    match node.attribute(name) {
+        Some(value) => parse_time_expression(value).map(Some).ok_or_else(|| {
+            LyricsError::ParseError(format!("The time expression {:?} is invalid.", value))
+        }),
+        None => Ok(None),
+    }
+}
+
+/// Append `text` to `s` with every run of whitespaces collapsed into a space.
+fn push_collapsed(s: &mut String, text: &str) {
+    for (i, word) in text.split(char::is_whitespace).enumerate() {
+        if i > 0 && !s.ends_with(' ') {
+            s.push(' ');
+        }
+
+        s.push_str(word);
+    }
+}
+
+/// Get the text of a `<p>` element. The texts of `<span>` elements which have their own begin times are led by word time tags of Enhanced LRC.
+fn paragraph_text(p: Node) -> Result<String, LyricsError> {
+    let mut s = String::new();
+
+    let mut last_span = None;
+    let mut last_end = None;
+
+    for node in p.descendants() {
+        if node.is_element() && node.tag_name().name() == "br" {
+            s.push(' ');
+
+            continue;
+        }
+
+        if !node.is_text() {
+            continue;
+        }
+
+        let text = node.text().unwrap_or("");
+
+        let span = node.ancestors().take_while(|n| *n != p).find(|n| {
+            n.is_element() && n.tag_name().name() == "span" && n.attribute("begin").is_some()
+        });
+
+        if let Some(span) = span {
+            if last_span != Some(span) {
+                push_word_time_tag(&mut s, get_time(span, "begin")?.unwrap());
+
+                last_span = Some(span);
+                last_end = get_time(span, "end")?;
+            }
+        } else if text.trim().is_empty() {
+            // whitespaces between spans
+        } else {
+            last_span = None;
+        }
+
+        push_collapsed(&mut s, &escape_brackets(text));
+    }
+
+    let mut s = s.trim().to_string();
+
+    if last_span.is_some() {
+        if let Some(end) = last_end.or(get_time(p, "end")?) {
+            push_word_time_tag(&mut s, end);
+        }
+    }
+
+    Ok(s)
+}
+
+impl Lyrics {
+    /// Create a `Lyrics` instance from Timed Text Markup Language (TTML), like the syllable-timed lyrics of Apple Music. Every `<p>` element becomes a timed line at its begin time, or a plain line if it has no begin time. The `<span>` elements with begin times become word time tags of Enhanced LRC. The times are treated as absolute, as Apple Music does.
+    pub fn from_ttml<S: AsRef<str>>(s: S) -> Result<Lyrics, LyricsError> {
+        let document =
+            Document::parse(s.as_ref()).map_err(|e| LyricsError::ParseError(e.to_string()))?;
+
+        let root = document.root_element();
+
+        let mut lyrics = Lyrics::new();
+
+        if let Some(language) = root.attribute(("http://www.w3.org/XML/1998/namespace", "lang")) {
+            lyrics.set_tag(KnownTag::La, language)?;
+        }
+
+        let title = root.descendants().find(|n| {
+            n.tag_name().namespace() == Some(TTML_METADATA_NAMESPACE)
+                && n.tag_name().name() == "title"
+        });
+
+        if let Some(title) = title.and_then(|n| n.text()) {
+            let title = title.trim();
+
+            if !title.is_empty() {
+                lyrics.set_title(title)?;
+            }
+        }
+
+        for p in root.descendants().filter(|n| n.is_element() && n.tag_name().name() == "p") {
+            let text = paragraph_text(p)?;
+
+            match get_time(p, "begin")? {
+                Some(begin) => lyrics.add_timed_line(TimeTag::new(begin), text)?,
+                None => lyrics.add_line(text)?,
+            }
+        }
+
+        Ok(lyrics)
+    }
+}
+
+/// Write a timestamp in the `hh:mm:ss.fff` clock time format of TTML. Negative timestamps are written as zero.
+fn write_time_expression<W: Write>(f: &mut W, timestamp: i64) -> Result<(), fmt::Error> {
+    let timestamp = timestamp.max(0);
+
+    f.write_fmt(format_args!(
+        "{:02}:{:02}:{:02}.{:03}",
+        timestamp / 3600000,
+        timestamp / 60000 % 60,
+        timestamp / 1000 % 60,
+        timestamp % 1000
+    ))
+}
+
+/// Write text with the XML special characters escaped.
+fn write_xml_text<W: Write>(f: &mut W, text: &str) -> Result<(), fmt::Error> {
+    for c in text.chars() {
+        match c {
+            '&' => f.write_str("&amp;")?,
+            '<' => f.write_str("&lt;")?,
+            '>' => f.write_str("&gt;")?,
+            '"' => f.write_str("&quot;")?,
+            _ => f.write_char(c)?,
+        }
+    }
+
+    Ok(())
+}
+
+impl Lyrics {
+    /// Export the timed lines to Timed Text Markup Language (TTML). Every timed line becomes a `<p>` element which ends when the next line begins, and the word time tags of Enhanced LRC become `<span>` elements. Lines with empty text and lines without time tags are not written.
+    pub fn to_ttml(&self) -> String {
+        let mut s = String::new();
+
+        self.write_ttml(&mut s).unwrap();
+
+        s
+    }
+
+    fn write_ttml<W: Write>(&self, f: &mut W) -> Result<(), fmt::Error> {
+        f.write_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n")?;
+        f.write_fmt(format_args!(
+            "<tt xmlns=\"{}\" xmlns:ttm=\"{}\" xml:lang=\"",
+            TTML_NAMESPACE, TTML_METADATA_NAMESPACE
+        ))?;
+        write_xml_text(f, self.get_tag(KnownTag::La).unwrap_or(""))?;
+        f.write_str("\">\n")?;
+
+        if let Some(title) = self.title() {
+            f.write_str("  <head>\n    <metadata>\n      <ttm:title>")?;
+            write_xml_text(f, title)?;
+            f.write_str("</ttm:title>\n    </metadata>\n  </head>\n")?;
+        }
+
+        f.write_str("  <body>\n    <div>\n")?;
+
+        for (i, (time_tag, line)) in self.timed_lines.iter().enumerate() {
+            let segments = word_segments(line);
+
+            if segments.iter().all(|segment| segment.text.trim().is_empty()) {
+                continue;
+            }
+
+            let start = time_tag.get_timestamp();
+            let end = word_end_time(&segments, start)
+                .unwrap_or_else(|| self.line_end_time(i, LAST_LINE_DURATION));
+
+            f.write_str("      <p begin=\"")?;
+            write_time_expression(f, start)?;
+            f.write_str("\" end=\"")?;
+            write_time_expression(f, end)?;
+            f.write_str("\">")?;
+
+            for (j, segment) in segments.iter().enumerate() {
+                if segment.text.is_empty() {
+                    continue;
+                }
+
+                let text = unescape_brackets(segment.text);
+
+                match segment.start {
+                    Some(segment_start) => {
+                        let segment_end =
+                            segments.get(j + 1).and_then(|segment| segment.start).unwrap_or(end);
+
+                        // keep the spaces between words out of the spans
+                        let trimmed = text.trim_end();
+
+                        f.write_str("<span begin=\"")?;
+                        write_time_expression(f, segment_start)?;
+                        f.write_str("\" end=\"")?;
+                        write_time_expression(f, segment_end)?;
+                        f.write_str("\">")?;
+                        write_xml_text(f, trimmed)?;
+                        f.write_str("</span>")?;
+                        write_xml_text(f, &text[trimmed.len()..])?;
+                    },
+                    None => write_xml_text(f, &text)?,
+                }
+            }
+
+            f.write_str("</p>\n")?;
+        }
+
+        f.write_str("    </div>\n  </body>\n</tt>\n")
+    }
+}
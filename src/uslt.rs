@@ -0,0 +1,174 @@
+use crate::{escape_brackets, unescape_brackets, Lyrics, LyricsError};
+
+/// The language code of ID3 for an unknown language.
+const UNKNOWN_LANGUAGE: &str = "XXX";
+
+/// An unsynchronized lyrics (USLT) frame of ID3v2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Uslt {
+    /// The ISO 639-2 language code, like `eng`.
+    pub language:    String,
+    /// The content descriptor.
+    pub description: String,
+    /// The lyrics, whose lines are separated by newlines.
+    pub text:        String,
+}
+
+/// Decode a string of a frame in the text encoding of ID3v2.
+fn decode_text(encoding: u8, data: &[u8]) -> Result<String, LyricsError> {
+    let utf16 = |data: &[u8], big_endian: bool| -> Result<String, LyricsError> {
+        let units: Vec<u16> = data
+            .chunks_exact(2)
+            .map(|b| {
+                if big_endian {
+                    u16::from_be_bytes([b[0], b[1]])
+                } else {
+                    u16::from_le_bytes([b[0], b[1]])
+                }
+            })
+            .collect();
+
+        String::from_utf16(&units)
+            .map_err(|_| LyricsError::FormatError("The text of the frame is not valid UTF-16."))
+    };
+
+    match encoding {
+        0 => Ok(data.iter().map(|&b| b as char).collect()),
+        1 => match data {
+            [0xFF, 0xFE, data @ ..] => utf16(data, false),
+            [0xFE, 0xFF, data @ ..] => utf16(data, true),
+            [] => Ok(String::new()),
+            _ => Err(LyricsError::FormatError("The text of the frame has no byte order mark.")),
+        },
+        2 => utf16(data, true),
+        3 => String::from_utf8(data.to_vec())
+            .map_err(|_| LyricsError::FormatError("The text of the frame is not valid UTF-8.")),
+        _ => Err(LyricsError::FormatError("The text encoding of the frame is unknown.")),
+    }
+}
+
+/// Split a terminated string from the beginning of `data`. The terminator is two zero bytes for UTF-16, and one zero byte for the other encodings.
+fn split_terminated(encoding: u8, data: &[u8]) -> (&[u8], &[u8]) {
+    if matches!(encoding, 1 | 2) {
+        let mut i = 0;
+
+        while i + 1 < data.len() {
+            if data[i] == 0 && data[i + 1] == 0 {
+                return (&data[..i], &data[i + 2..]);
+            }
+
+            i += 2;
+        }
+    } else if let Some(i) = data.iter().position(|&b| b == 0) {
+        return (&data[..i], &data[i + 1..]);
+    }
+
+    (data, &[])
+}
+
+/// Append `s` to `data` in UTF-16 with a little-endian byte order mark.
+fn push_utf16(data: &mut Vec<u8>, s: &str) {
+    data.extend_from_slice(&[0xFF, 0xFE]);
+
+    for unit in s.encode_utf16() {
+        data.extend_from_slice(&unit.to_le_bytes());
+    }
+}
+
+impl Uslt {
+    /// Parse the data of a USLT frame, which is the content after the frame header.
+    pub fn from_frame_data(data: &[u8]) -> Result<Uslt, LyricsError> {
+        if data.len() < 4 {
+            return Err(LyricsError::FormatError("The USLT frame is too short."));
+        }
+
+        let encoding = data[0];
+        let language = data[1..4].iter().map(|&b| b as char).collect();
+
+        let (description, text) = split_terminated(encoding, &data[4..]);
+
+        // some taggers terminate the text too
+        let (text, _) = split_terminated(encoding, text);
+
+        Ok(Uslt {
+            language,
+            description: decode_text(encoding, description)?,
+            text: decode_text(encoding, text)?,
+        })
+    }
+
+    /// Serialize into the data of a USLT frame. The texts are encoded in UTF-16 with a byte order mark, which both ID3v2.3 and ID3v2.4 support. A language code which is not three ASCII letters is written as `XXX`.
+    pub fn to_frame_data(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(8 + (self.description.len() + self.text.len()) * 2);
+
+        data.push(1);
+
+        if self.language.len() == 3 && self.language.bytes().all(|b| b.is_ascii_alphabetic()) {
+            data.extend_from_slice(self.language.as_bytes());
+        } else {
+            data.extend_from_slice(UNKNOWN_LANGUAGE.as_bytes());
+        }
+
+        push_utf16(&mut data, &self.description);
+        data.extend_from_slice(&[0, 0]);
+        push_utf16(&mut data, &self.text);
+
+        data
+    }
+}
+
+/// Options for converting lyrics to a USLT frame.
+#[derive(Debug, Clone)]
+pub struct UsltOptions {
+    /// The ISO 639-2 language code.
+    pub language:            String,
+    /// The content descriptor.
+    pub description:         String,
+    /// Whether to put the texts of the timed lines before the plain lines, for targets which only support USLT.
+    pub flatten_timed_lines: bool,
+}
+
+impl Default for UsltOptions {
+    #[inline]
+    fn default() -> Self {
+        UsltOptions {
+            language:            String::from(UNKNOWN_LANGUAGE),
+            description:         String::new(),
+            flatten_timed_lines: false,
+        }
+    }
+}
+
+impl Lyrics {
+    /// Convert the plain lines to a USLT frame.
+    #[inline]
+    pub fn to_uslt(&self) -> Uslt {
+        self.to_uslt_with_options(&UsltOptions::default())
+    }
+
+    /// Convert the plain lines to a USLT frame with options. If `flatten_timed_lines` is set, the text is the same as `to_plain_text`.
+    pub fn to_uslt_with_options(&self, options: &UsltOptions) -> Uslt {
+        let text = if options.flatten_timed_lines {
+            self.to_plain_text()
+        } else {
+            self.lines.iter().map(|line| unescape_brackets(line)).collect::<Vec<_>>().join("\n")
+        };
+
+        Uslt {
+            language: options.language.clone(),
+            description: options.description.clone(),
+            text,
+        }
+    }
+
+    /// Create a `Lyrics` instance whose plain lines are the lines of a USLT frame.
+    pub fn from_uslt(uslt: &Uslt) -> Result<Lyrics, LyricsError> {
+        let mut lyrics = Lyrics::new();
+
+        for line in uslt.text.lines() {
+            lyrics.add_line(escape_brackets(line))?;
+        }
+
+        Ok(lyrics)
+    }
+}
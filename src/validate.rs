@@ -0,0 +1,137 @@
+use std::fmt::{self, Display, Formatter};
+
+use unicase::UniCase;
+
+use crate::{parse_length, KnownTag, Lyrics, TimeTag, Timestamp};
+
+/// A problem found by validating lyrics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// The text of the `[length:]` tag cannot be parsed.
+    InvalidLength(String),
+    /// The timed line begins after the end of the track declared by the `[length:]` tag.
+    ExceedsLength { time_tag: TimeTag, length: Timestamp },
+}
+
+impl Display for ValidationIssue {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            ValidationIssue::InvalidLength(text) => {
+                f.write_fmt(format_args!("The length {:?} is invalid.", text))
+            },
+            ValidationIssue::ExceedsLength {
+                time_tag,
+                length,
+            } => f.write_fmt(format_args!(
+                "The time tag {} exceeds the length {}.",
+                time_tag, length
+            )),
+        }
+    }
+}
+
+/// A problem of an ID tag found by linting the metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetadataIssue {
+    /// The label is not one of the standard labels.
+    UnknownLabel(String),
+    /// The text of the ID tag is empty.
+    EmptyText(String),
+    /// More than one ID tag has the label.
+    DuplicateLabel(String),
+    /// The text of the `[offset:]` tag is not an integer.
+    InvalidOffset(String),
+    /// The text of the `[length:]` tag cannot be parsed.
+    InvalidLength(String),
+}
+
+impl Display for MetadataIssue {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            MetadataIssue::UnknownLabel(label) => {
+                f.write_fmt(format_args!("The label {:?} is not standard.", label))
+            },
+            MetadataIssue::EmptyText(label) => {
+                f.write_fmt(format_args!("The text of {:?} is empty.", label))
+            },
+            MetadataIssue::DuplicateLabel(label) => {
+                f.write_fmt(format_args!("The label {:?} is duplicated.", label))
+            },
+            MetadataIssue::InvalidOffset(text) => {
+                f.write_fmt(format_args!("The offset {:?} is invalid.", text))
+            },
+            MetadataIssue::InvalidLength(text) => {
+                f.write_fmt(format_args!("The length {:?} is invalid.", text))
+            },
+        }
+    }
+}
+
+impl Lyrics {
+    /// Check this lyrics for problems which do not prevent it from being parsed or written.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        if let Some(text) = self.get_tag(KnownTag::Length) {
+            match self.length() {
+                Some(length) => {
+                    if let Some((time_tag, _)) = self.timed_lines.last() {
+                        if time_tag.get_timestamp() > length.get_timestamp() {
+                            issues.push(ValidationIssue::ExceedsLength {
+                                time_tag: *time_tag,
+                                length,
+                            });
+                        }
+                    }
+                },
+                None => issues.push(ValidationIssue::InvalidLength(text.to_string())),
+            }
+        }
+
+        issues
+    }
+
+    /// Check the metadata for nonstandard labels, empty texts, duplicate labels and malformed values.
+    pub fn lint_metadata(&self) -> Vec<MetadataIssue> {
+        let mut issues = Vec::new();
+        let mut seen: Vec<UniCase<&str>> = Vec::new();
+
+        for id_tag in self.metadata.iter() {
+            let label = id_tag.get_label();
+            let text = id_tag.get_text();
+
+            if seen.contains(&UniCase::new(label)) {
+                if !issues.contains(&MetadataIssue::DuplicateLabel(label.to_string())) {
+                    issues.push(MetadataIssue::DuplicateLabel(label.to_string()));
+                }
+            } else {
+                seen.push(UniCase::new(label));
+            }
+
+            if text.is_empty() {
+                issues.push(MetadataIssue::EmptyText(label.to_string()));
+
+                continue;
+            }
+
+            match KnownTag::from_label(label) {
+                Some(KnownTag::Offset) => {
+                    if text.strip_prefix('+').unwrap_or(text).parse::<i64>().is_err() {
+                        issues.push(MetadataIssue::InvalidOffset(text.to_string()));
+                    }
+                },
+                Some(KnownTag::Length) => {
+                    if parse_length(text).is_none() {
+                        issues.push(MetadataIssue::InvalidLength(text.to_string()));
+                    }
+                },
+                Some(_) => (),
+                None => issues.push(MetadataIssue::UnknownLabel(label.to_string())),
+            }
+        }
+
+        issues
+    }
+}
@@ -0,0 +1,51 @@
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+use crate::{Lyrics, SerializeOptions};
+
+/// A `fmt::Write` implementation which writes into an `io::Write` and keeps the I/O error.
+struct IoWriter<'a, W: Write> {
+    writer: &'a mut W,
+    error:  Option<io::Error>,
+}
+
+impl<'a, W: Write> fmt::Write for IoWriter<'a, W> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
+        self.writer.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+
+            fmt::Error
+        })
+    }
+}
+
+impl Lyrics {
+    /// Serialize the lyrics into `writer` without building the whole output in memory.
+    #[inline]
+    pub fn to_writer<W: Write>(&self, writer: &mut W) -> Result<(), io::Error> {
+        self.to_writer_with_options(writer, &SerializeOptions::default())
+    }
+
+    /// Serialize the lyrics with options into `writer` without building the whole output in memory.
+    pub fn to_writer_with_options<W: Write>(
+        &self,
+        writer: &mut W,
+        options: &SerializeOptions,
+    ) -> Result<(), io::Error> {
+        let mut w = IoWriter {
+            writer,
+            error: None,
+        };
+
+        if self.write_with_options(&mut w, options).is_err() {
+            return Err(w
+                .error
+                .unwrap_or_else(|| io::Error::new(io::ErrorKind::Other, "formatter error")));
+        }
+
+        Ok(())
+    }
+}
@@ -1,10 +1,19 @@
-use lrc::{IDTag, Lyrics, TimeTag};
+use lrc::{
+    convert::Lrc, AlignedLine, BilingualLyrics, Boundary, CommentPlacement, CursorEvent,
+    DegradedLine, Dialect, Distribution, DuplicateIDTagPolicy, DuplicateTimeTagPolicy, FoundLine,
+    IDTag, IDTagLabel, KnownTag, LengthFormat, LimitKind, LineMatch, LineOutcome, Lyrics,
+    LyricsDocument, LyricsError, LyricsEvent, LyricsEventParser, LyricsItem, LyricsPushParser,
+    MetadataIssue, MetadataOrder, NegativeTimePolicy, Newline, ParseLimits, ParseOptions,
+    ParseWarning, ParseWarningKind, Rounding, SerializeError, SerializeOptions, SrtOptions,
+    SuspiciousCharKind, TextIssue, TextLocation, TextMatchOptions, TimeFormat, TimeTag, Timestamp,
+    Uslt, UsltOptions, ValidationIssue, ValidationLevel,
+};
 
 #[test]
 fn create() {
     let mut lyrics: Lyrics = Lyrics::new();
 
-    let metadata = &mut lyrics.metadata;
+    let metadata = lyrics.get_metadata_mut();
     metadata.insert(IDTag::from_string("ar", "Chubby Checker oppure  Beatles, The").unwrap());
     metadata.insert(IDTag::from_string("al", "Hits Of The 60's - Vol. 2 – Oldies").unwrap());
     metadata.insert(IDTag::from_string("ti", "Let's Twist Again").unwrap());
@@ -152,6 +161,9 @@ Plain line 2",
 [length: 2:23]
 [ti: Let's Twist Again]
 
+[:] This is a comment.
+[:] This is a comment.
+
 [00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
 [00:15.30]Some more lyrics ...
 [01:15.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
@@ -175,5 +187,3400 @@ Plain line 2",
 
     let lyrics = Lyrics::from_str(r"[00:12.00][:]Naku Penda Piya-Naku Taka Piya-Mpenziwe").unwrap();
 
-    assert_eq!(r"[00:12.00]", lyrics.to_string());
+    assert_eq!(
+        r"[:] Naku Penda Piya-Naku Taka Piya-Mpenziwe
+
+[00:12.00]",
+        lyrics.to_string()
+    );
+}
+
+#[test]
+fn comments() {
+    let mut lyrics = Lyrics::from_str(
+        r"[ti:Let's Twist Again]
+[:] Synced by someone.
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[:Another comment]",
+    )
+    .unwrap();
+
+    let comments = lyrics.get_comments();
+
+    assert_eq!(2, comments.len());
+    assert_eq!(Some(2), comments[0].line_number());
+    assert_eq!("Synced by someone.", comments[0].text());
+    assert_eq!(Some(4), comments[1].line_number());
+    assert_eq!("Another comment", comments[1].text());
+
+    lyrics.add_comment("Edited by someone else.").unwrap();
+    assert!(lyrics.add_comment("Two\nlines").is_err());
+
+    assert_eq!(None, lyrics.get_comments()[2].line_number());
+
+    let note = Lyrics::from_str("[: note] rest").unwrap();
+
+    assert_eq!("note rest", note.get_comments()[0].text());
+    assert_eq!("[:] note rest", note.to_string());
+
+    assert_eq!(
+        r"[ti: Let's Twist Again]
+
+[:] Synced by someone.
+[:] Another comment
+[:] Edited by someone else.
+
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+        lyrics.to_string()
+    );
+
+    let s = "[00:12.00]Naku Penda Piya\n[:] Chorus\n[00:15.30]Naku Taka Piya";
+
+    assert_eq!(
+        "[:] Chorus\n\n[00:12.00]Naku Penda Piya\n[00:15.30]Naku Taka Piya",
+        Lyrics::from_str(s).unwrap().to_string()
+    );
+    assert_eq!(s, LyricsDocument::from_str(s).unwrap().to_string());
+}
+
+#[test]
+fn shift_time() {
+    let mut lyrics = Lyrics::from_str(
+        r"[00:01.00]Line 1
+[00:05.00]Line 2
+[00:10.00]Line 3",
+    )
+    .unwrap();
+
+    lyrics.shift_time(-2000);
+
+    assert_eq!(
+        r"[-00:01.00]Line 1
+[00:03.00]Line 2
+[00:08.00]Line 3",
+        lyrics.to_string()
+    );
+
+    let mut lyrics = Lyrics::from_str(
+        r"[00:01.00]Line 1
+[00:05.00]Line 2
+[00:10.00]Line 3",
+    )
+    .unwrap();
+
+    assert!(lyrics.rebase(TimeTag::new(4000), NegativeTimePolicy::Error).is_err());
+    assert_eq!(3, lyrics.get_timed_lines().len());
+
+    let mut clamped = lyrics.clone();
+    clamped.rebase(TimeTag::new(4000), NegativeTimePolicy::Clamp).unwrap();
+
+    assert_eq!(
+        r"[00:00.00]Line 1
+[00:01.00]Line 2
+[00:06.00]Line 3",
+        clamped.to_string()
+    );
+
+    lyrics.rebase(TimeTag::new(4000), NegativeTimePolicy::Remove).unwrap();
+
+    assert_eq!(
+        r"[00:01.00]Line 2
+[00:06.00]Line 3",
+        lyrics.to_string()
+    );
+}
+
+#[test]
+fn parse_partial() {
+    let (lyrics, errors) = Lyrics::from_str_partial(
+        "[ti:Let's Twist Again]
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:13.00]Broken \x01 line
+[00:15.30]Some more [ar: Someone] lyrics ...",
+    );
+
+    assert_eq!(
+        r"[ti: Let's Twist Again]
+
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+        lyrics.to_string()
+    );
+
+    assert_eq!(2, errors.len());
+    assert_eq!(3, errors[0].line_number);
+    assert_eq!(4, errors[1].line_number);
+}
+
+#[test]
+fn to_string_strict() {
+    let lyrics = Lyrics::from_str(
+        r"[ti:Let's Twist Again]
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+    )
+    .unwrap();
+
+    assert_eq!(lyrics.to_string(), lyrics.to_string_strict().unwrap());
+
+    let mut lyrics = Lyrics::new();
+    lyrics.add_timed_line(TimeTag::new(-1000), "Negative").unwrap();
+    assert_eq!(Err(SerializeError::NegativeTime(TimeTag::new(-1000))), lyrics.to_string_strict());
+
+    let mut lyrics = Lyrics::new();
+    lyrics.add_timed_line(TimeTag::new(100 * 60000), "Too long").unwrap();
+    assert_eq!(
+        Err(SerializeError::TimeOverflow(TimeTag::new(100 * 60000))),
+        lyrics.to_string_strict()
+    );
+
+    let mut lyrics = Lyrics::new();
+    lyrics.add_timed_line(TimeTag::new(1234), "Milliseconds").unwrap();
+    assert_eq!(Err(SerializeError::PrecisionLoss(TimeTag::new(1234))), lyrics.to_string_strict());
+
+    let mut lyrics = Lyrics::new();
+    lyrics.add_timed_line(TimeTag::new(1000), "<00:01.00>Word <00:01.50>timed").unwrap();
+    assert_eq!(Err(SerializeError::WordTimings(0)), lyrics.to_string_strict());
+
+    let mut lyrics = Lyrics::new();
+    lyrics.add_timed_line(TimeTag::new(2 * 3600000), "Two hours").unwrap();
+
+    let options = SerializeOptions {
+        time_format: TimeFormat::HoursMinutesSeconds,
+        ..SerializeOptions::default()
+    };
+
+    assert!(lyrics.to_string_strict().is_err());
+    assert_eq!(
+        Ok(String::from("[2:00:00.00]Two hours")),
+        lyrics.to_string_strict_with_options(&options)
+    );
+
+    // 99:59.995, which is written as 100:00.00 if rounded half up
+    let time_tag = TimeTag::new(99 * 60000 + 59 * 1000 + 995);
+
+    let mut lyrics = Lyrics::new();
+    lyrics.add_timed_line(time_tag, "Last").unwrap();
+
+    let options = SerializeOptions {
+        rounding: Rounding::HalfUp,
+        ..SerializeOptions::default()
+    };
+
+    assert_eq!(
+        Err(SerializeError::TimeOverflow(time_tag)),
+        lyrics.to_string_strict_with_options(&options)
+    );
+
+    let options = SerializeOptions {
+        rounding: Rounding::Truncate,
+        ..SerializeOptions::default()
+    };
+
+    assert_eq!(
+        Err(SerializeError::PrecisionLoss(time_tag)),
+        lyrics.to_string_strict_with_options(&options)
+    );
+}
+
+#[test]
+fn verify_roundtrip() {
+    let mut lyrics = Lyrics::from_str(
+        r"[ti:Let's Twist Again]
+[:] Synced by someone.
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+Plain line",
+    )
+    .unwrap();
+
+    assert!(lrc::convert::verify_roundtrip::<Lrc>(&lyrics).unwrap().is_lossless());
+
+    lyrics.add_timed_line(TimeTag::new(15304), "Some more lyrics").unwrap();
+
+    let report = lrc::convert::verify_roundtrip::<Lrc>(&lyrics).unwrap();
+
+    assert!(!report.is_lossless());
+    assert_eq!(vec![(TimeTag::new(15304), TimeTag::new(15300))], report.changed_times);
+}
+
+#[test]
+fn duplicate_time_tags() {
+    let s = "[00:12.00][00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe";
+
+    let (lyrics, warnings) = Lyrics::from_str_with_warnings(s, &ParseOptions::default()).unwrap();
+
+    assert_eq!(1, lyrics.get_timed_lines().len());
+    assert_eq!(
+        vec![ParseWarning {
+            line_number: 1,
+            kind:        ParseWarningKind::DuplicateTimeTag(TimeTag::new(12000)),
+        }],
+        warnings
+    );
+
+    let options = ParseOptions {
+        duplicate_time_tags: DuplicateTimeTagPolicy::Keep,
+        ..ParseOptions::default()
+    };
+
+    assert_eq!(2, Lyrics::from_str_with_options(s, &options).unwrap().get_timed_lines().len());
+
+    let options = ParseOptions {
+        duplicate_time_tags: DuplicateTimeTagPolicy::Error,
+        ..ParseOptions::default()
+    };
+
+    assert!(Lyrics::from_str_with_options(s, &options).is_err());
+}
+
+#[test]
+fn document_order() {
+    let s = r"[ti:Let's Twist Again]
+Intro
+[:] Verse 1
+[00:12.00][01:15.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+
+[00:15.30]Some more lyrics ...";
+
+    let document = LyricsDocument::from_str(s).unwrap();
+
+    assert!(matches!(document.items[0], LyricsItem::Metadata(_)));
+    assert!(matches!(&document.items[1], LyricsItem::Plain(line) if line == "Intro"));
+    assert!(
+        matches!(&document.items[2], LyricsItem::Comment(comment) if comment.text() == "Verse 1")
+    );
+    assert!(matches!(&document.items[3], LyricsItem::Timed(time_tags, _) if time_tags.len() == 2));
+
+    assert_eq!(
+        r"[ti: Let's Twist Again]
+Intro
+[:] Verse 1
+[00:12.00][01:15.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+
+[00:15.30]Some more lyrics ...",
+        document.to_string()
+    );
+
+    let lyrics = document.to_lyrics().unwrap();
+
+    assert_eq!(3, lyrics.get_timed_lines().len());
+    assert_eq!(1, lyrics.get_comments().len());
+
+    let s = "[00:01.00][00:01.00]a\n[00:02.00]Some [ar:more] lyrics\n[00:03.00]b";
+
+    assert!(LyricsDocument::from_str(s).is_err());
+
+    let (document, warnings) = LyricsDocument::from_str_with_warnings(s, &ParseOptions {
+        skip_invalid_lines: true,
+        duplicate_time_tags: DuplicateTimeTagPolicy::Merge,
+        ..ParseOptions::default()
+    })
+    .unwrap();
+
+    assert_eq!("[00:01.00]a\n[00:03.00]b", document.to_string());
+    assert_eq!(
+        vec![
+            ParseWarning {
+                line_number: 1,
+                kind:        ParseWarningKind::DuplicateTimeTag(TimeTag::new(1000)),
+            },
+            ParseWarning {
+                line_number: 2, kind: ParseWarningKind::SkippedLine
+            },
+        ],
+        warnings
+    );
+}
+
+#[test]
+fn cursor() {
+    let lyrics = Lyrics::from_str(
+        r"[00:01.00]Line 1
+[00:03.00]Line 2
+[00:20.00]Line 3",
+    )
+    .unwrap();
+
+    let mut cursor = lyrics.cursor();
+    cursor.set_clear_after(Some(5000));
+
+    assert_eq!(Some(CursorEvent::Clear), cursor.update(0));
+    assert_eq!(None, cursor.update(500));
+    assert!(matches!(
+        cursor.update(1000),
+        Some(CursorEvent::Line {
+            index: 0,
+            ..
+        })
+    ));
+    assert!(matches!(
+        cursor.update(3000),
+        Some(CursorEvent::Line {
+            index: 1,
+            text: "Line 2",
+            ..
+        })
+    ));
+    assert_eq!(None, cursor.update(7999));
+    assert_eq!(Some(CursorEvent::Clear), cursor.update(8000));
+    assert!(matches!(
+        cursor.update(20000),
+        Some(CursorEvent::Line {
+            index: 2,
+            ..
+        })
+    ));
+
+    let events: Vec<(i64, bool)> = lyrics
+        .cursor_events(Some(5000))
+        .map(|(time_tag, event)| (time_tag.get_timestamp(), event == CursorEvent::Clear))
+        .collect();
+
+    assert_eq!(
+        vec![(1000, false), (3000, false), (8000, true), (20000, false), (25000, true)],
+        events
+    );
+}
+
+#[test]
+fn newline_style() {
+    let lyrics = Lyrics::from_str(
+        r"[ti:Let's Twist Again]
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30]Some more lyrics ...",
+    )
+    .unwrap();
+
+    let options = SerializeOptions {
+        newline: Newline::CrLf,
+        trailing_newline: true,
+        ..SerializeOptions::default()
+    };
+
+    assert_eq!(
+        "[ti: Let's Twist Again]\r\n\r\n[00:12.00]Naku Penda Piya-Naku Taka \
+         Piya-Mpenziwe\r\n[00:15.30]Some more lyrics ...\r\n",
+        lyrics.to_string_with_options(&options)
+    );
+
+    assert_eq!("", Lyrics::new().to_string_with_options(&options));
+}
+
+#[test]
+fn find_timestamp_exclusive() {
+    let lyrics = Lyrics::from_str(
+        r"[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30]Some more lyrics",
+    )
+    .unwrap();
+
+    assert_eq!(Some(1), lyrics.find_timed_line_index_with_boundary(15300, Boundary::Inclusive));
+    assert_eq!(Some(0), lyrics.find_timed_line_index_with_boundary(15300, Boundary::Exclusive));
+    assert_eq!(None, lyrics.find_timed_line_index_with_boundary(12000, Boundary::Exclusive));
+    assert_eq!(Some(1), lyrics.find_timed_line_index_with_boundary(15301, Boundary::Exclusive));
+}
+
+#[test]
+fn intersect_and_subtract_times() {
+    let source = Lyrics::from_str(
+        r"[00:01.00]Line 1
+[00:05.00]Line 2
+[00:10.00]Line 3",
+    )
+    .unwrap();
+
+    let edited = Lyrics::from_str(
+        r"[00:01.05]Line 1
+[00:07.00]Line 2
+[00:10.00]Line 3",
+    )
+    .unwrap();
+
+    let common: Vec<i64> =
+        edited.intersect_times(&source, 100).iter().map(|(t, _)| t.get_timestamp()).collect();
+    assert_eq!(vec![1050, 10000], common);
+
+    let changed: Vec<&str> =
+        edited.subtract_times(&source, 100).iter().map(|(_, line)| line.as_ref()).collect();
+    assert_eq!(vec!["Line 2"], changed);
+
+    assert_eq!(1, edited.intersect_times(&source, 0).len());
+}
+
+#[test]
+fn id_tag_padding() {
+    let lyrics = Lyrics::from_str("[ti: Let's Twist Again ]\n[00:12.00]Naku Penda").unwrap();
+
+    let options = SerializeOptions {
+        id_tag_padding: false,
+        ..SerializeOptions::default()
+    };
+
+    assert_eq!(
+        "[ti:Let's Twist Again]\n\n[00:12.00]Naku Penda",
+        lyrics.to_string_with_options(&options)
+    );
+}
+
+#[test]
+fn bom() {
+    let lyrics = Lyrics::from_str("\u{FEFF}[ti:Let's Twist Again]\n[00:12.00]Naku Penda").unwrap();
+
+    assert_eq!("[ti: Let's Twist Again]\n\n[00:12.00]Naku Penda", lyrics.to_string());
+
+    let options = SerializeOptions {
+        bom: true,
+        ..SerializeOptions::default()
+    };
+
+    assert_eq!(
+        "\u{FEFF}[ti: Let's Twist Again]\n\n[00:12.00]Naku Penda",
+        lyrics.to_string_with_options(&options)
+    );
+}
+
+#[test]
+fn length() {
+    let mut lyrics = Lyrics::from_str("[length: 1:02:23]\n[00:12.00]Naku Penda").unwrap();
+
+    assert_eq!(Some(Timestamp::new(3743000)), lyrics.length());
+
+    let options = SerializeOptions {
+        length_format: LengthFormat::MinutesSeconds,
+        ..SerializeOptions::default()
+    };
+
+    assert_eq!("[length: 62:23]\n\n[00:12.00]Naku Penda", lyrics.to_string_with_options(&options));
+
+    lyrics.set_length(Timestamp::new(143000));
+
+    assert_eq!("[length: 02:23]\n\n[00:12.00]Naku Penda", lyrics.to_string());
+}
+
+#[test]
+fn formats() {
+    let formats = lrc::formats();
+
+    let lrc = formats.iter().find(|f| f.name == "LRC").unwrap();
+
+    assert_eq!(&["lrc"], lrc.extensions);
+    assert!(lrc.capabilities.metadata);
+    assert!(lrc.capabilities.import);
+
+    let srt = formats.iter().find(|f| f.name == "SRT").unwrap();
+
+    assert!(srt.capabilities.end_times);
+    assert!(!srt.capabilities.import);
+
+    assert!(formats.iter().any(|f| f.name == "CSV" && f.capabilities.import));
+
+    #[cfg(feature = "ass")]
+    assert!(formats.iter().any(|f| f.name == "ASS" && f.capabilities.word_timing));
+
+    let lyrics = Lyrics::from_str("[00:12.00]Naku Penda Piya\n[00:15.30]Some more lyrics").unwrap();
+
+    assert_eq!(lyrics.to_srt(), lrc::convert::Srt::serialize(&lyrics));
+    assert!(lrc::convert::verify_roundtrip::<lrc::convert::Csv>(&lyrics).unwrap().is_lossless());
+}
+
+#[cfg(feature = "encoding")]
+#[test]
+fn from_bytes() {
+    let (bytes, ..) =
+        lrc::encoding_rs::GBK.encode("[ti:让我们再次扭动]\n[00:12.00]我爱你，我想你，我的爱人");
+
+    let (lyrics, encoding) = Lyrics::from_bytes(bytes).unwrap();
+
+    assert_eq!(lrc::encoding_rs::GBK, encoding);
+    assert_eq!("[ti: 让我们再次扭动]\n\n[00:12.00]我爱你，我想你，我的爱人", lyrics.to_string());
+
+    let (lyrics, encoding) = Lyrics::from_bytes("\u{FEFF}[00:12.00]Naku Penda").unwrap();
+
+    assert_eq!(lrc::encoding_rs::UTF_8, encoding);
+    assert_eq!("[00:12.00]Naku Penda", lyrics.to_string());
+}
+
+#[test]
+fn id_tag_builder() {
+    let id_tag = IDTag::builder().label(KnownTag::Ar).text(" Chubby Checker ").build().unwrap();
+
+    assert_eq!("ar", id_tag.get_label());
+    assert_eq!("Chubby Checker", id_tag.get_text());
+
+    assert!(IDTag::builder().label("custom").text("x").build().is_ok());
+    assert!(IDTag::builder()
+        .label("custom")
+        .text("x")
+        .validation(ValidationLevel::KnownOnly)
+        .build()
+        .is_err());
+    assert!(IDTag::builder().label("AR").validation(ValidationLevel::KnownOnly).build().is_ok());
+    assert!(IDTag::builder().label("a]b").build().is_err());
+    assert_eq!("a]b", unsafe { IDTag::builder().label("a]b").build_unchecked() }.get_label());
+}
+
+#[cfg(feature = "encoding")]
+#[test]
+fn to_bytes() {
+    let lyrics = Lyrics::from_str("[00:12.00]いつまでも").unwrap();
+
+    let bytes = lyrics.to_bytes(lrc::encoding_rs::SHIFT_JIS).unwrap();
+
+    assert_eq!(
+        lyrics.to_string(),
+        Lyrics::from_bytes_with_encoding(bytes, lrc::encoding_rs::SHIFT_JIS).unwrap().to_string()
+    );
+
+    let lyrics = Lyrics::from_str("[00:12.00]Naku Penda 😀").unwrap();
+
+    assert_eq!(
+        Err(SerializeError::Unencodable("Shift_JIS")),
+        lyrics.to_bytes(lrc::encoding_rs::SHIFT_JIS)
+    );
+
+    let options = SerializeOptions {
+        bom: true,
+        ..SerializeOptions::default()
+    };
+
+    assert_eq!(
+        b"\xEF\xBB\xBF[00:12.00]Naku Penda \xF0\x9F\x98\x80".to_vec(),
+        lyrics.to_bytes_with_options(lrc::encoding_rs::UTF_8, &options).unwrap()
+    );
+
+    let bytes = lyrics.to_bytes(lrc::encoding_rs::UTF_16LE).unwrap();
+
+    assert_eq!(b"\xFF\xFE[\x000\x00", &bytes[..6]);
+
+    let (decoded, encoding) = Lyrics::from_bytes(&bytes).unwrap();
+
+    assert_eq!(lrc::encoding_rs::UTF_16LE, encoding);
+    assert_eq!(lyrics.to_string(), decoded.to_string());
+
+    let bytes = lyrics.to_bytes(lrc::encoding_rs::UTF_16BE).unwrap();
+
+    assert_eq!(b"\xFE\xFF\x00[\x000", &bytes[..6]);
+    assert_eq!(lyrics.to_string(), Lyrics::from_bytes(&bytes).unwrap().0.to_string());
+
+    assert_eq!(
+        Err(SerializeError::UnsupportedEncoding("replacement")),
+        lyrics.to_bytes(lrc::encoding_rs::REPLACEMENT)
+    );
+
+    let path = std::env::temp_dir().join(format!("lrc-test-encoding-{}.lrc", std::process::id()));
+
+    match lyrics.to_file_with_encoding(
+        &path,
+        lrc::encoding_rs::SHIFT_JIS,
+        &SerializeOptions::default(),
+    ) {
+        Err(LyricsError::FileError(_, error)) => assert_eq!(
+            LyricsError::SerializeError(SerializeError::Unencodable("Shift_JIS")),
+            *error
+        ),
+        result => panic!("{:?}", result),
+    }
+
+    assert!(!path.exists());
+}
+
+#[test]
+fn offset() {
+    let mut lyrics = Lyrics::from_str(
+        r"[offset:+500]
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+    )
+    .unwrap();
+
+    assert_eq!(Some(500), lyrics.offset());
+
+    lyrics.apply_offset();
+
+    assert_eq!(None, lyrics.offset());
+    assert_eq!("[00:11.50]Naku Penda Piya-Naku Taka Piya-Mpenziwe", lyrics.to_string());
+
+    lyrics.set_offset(-250);
+
+    assert_eq!(Some(-250), lyrics.offset());
+    assert_eq!(
+        "[offset: -250]\n\n[00:11.50]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+        lyrics.to_string()
+    );
+
+    lyrics.apply_offset();
+
+    assert_eq!("[00:11.75]Naku Penda Piya-Naku Taka Piya-Mpenziwe", lyrics.to_string());
+}
+
+#[test]
+fn id_tag_trim() {
+    let id_tag = IDTag::from_string(" ti ", "  Let's Twist Again ").unwrap();
+
+    assert_eq!("ti", id_tag.get_label());
+    assert_eq!("Let's Twist Again", id_tag.get_text());
+    assert_eq!("  Let's Twist Again ", id_tag.raw_text());
+    assert_eq!("[ti: Let's Twist Again]", id_tag.to_string());
+    assert_eq!(IDTag::from_string("TI", "").unwrap(), id_tag);
+
+    let lyrics = Lyrics::from_str("[ti:  Let's Twist Again ]").unwrap();
+
+    assert_eq!("  Let's Twist Again ", lyrics.get_metadata().iter().next().unwrap().raw_text());
+}
+
+#[test]
+fn fingerprint() {
+    let a = Lyrics::from_str(
+        r"[ti:Let's Twist Again]
+[ar:Chubby Checker]
+[00:12.00]Line A
+[00:12.00]Line B
+[00:15.30]Some more lyrics",
+    )
+    .unwrap();
+
+    let b = Lyrics::from_str(
+        r"[AR: Chubby Checker]
+[00:15.30]Some more lyrics
+[00:12.00]Line B
+[ti:Let's Twist Again]
+[00:12.00]Line A",
+    )
+    .unwrap();
+
+    assert_ne!(a.to_string(), b.to_string());
+    assert_eq!(a.to_canonical_string(), b.to_canonical_string());
+    assert_eq!(a.fingerprint(), b.fingerprint());
+
+    assert_eq!(
+        r"#metadata
+[ar:Chubby Checker]
+[ti:Let's Twist Again]
+#comments
+#timed
+[00:12.000]Line A
+[00:12.000]Line B
+[00:15.300]Some more lyrics
+#plain",
+        a.to_canonical_string()
+    );
+
+    assert_eq!(0xB915_A764_7EC6_B376, a.fingerprint());
+    assert_ne!(a.fingerprint(), Lyrics::new().fingerprint());
+}
+
+#[test]
+fn id_tag_label() {
+    let lyrics = Lyrics::from_str(
+        r"[TI:Let's Twist Again]
+[encoding:utf-8]",
+    )
+    .unwrap();
+
+    for id_tag in lyrics.get_metadata().iter() {
+        match id_tag.label() {
+            IDTagLabel::Known(KnownTag::Ti) => assert_eq!("Let's Twist Again", id_tag.get_text()),
+            IDTagLabel::Known(_) => unreachable!(),
+            IDTagLabel::Other(label) => assert_eq!("encoding", label),
+        }
+    }
+
+    assert_eq!(IDTagLabel::Known(KnownTag::Length), IDTagLabel::from_label(" Length "));
+    assert_eq!("ar", IDTagLabel::from(KnownTag::Ar).as_str());
+}
+
+#[test]
+fn bilingual() {
+    let original = Lyrics::from_str(
+        r"[ti:Twinkle, Twinkle, Little Star]
+[00:01.00]Twinkle, twinkle, little star
+[00:05.00]How I wonder what you are",
+    )
+    .unwrap();
+
+    let translation = Lyrics::from_str(
+        r"[00:01.00]一閃一閃亮晶晶
+[00:05.00]滿天都是小星星",
+    )
+    .unwrap();
+
+    let bilingual = BilingualLyrics::new(original, translation);
+
+    let interleaved = bilingual.to_interleaved();
+
+    assert_eq!(
+        r"[ti: Twinkle, Twinkle, Little Star]
+
+[00:01.00]Twinkle, twinkle, little star
+[00:01.00]一閃一閃亮晶晶
+[00:05.00]How I wonder what you are
+[00:05.00]滿天都是小星星",
+        interleaved.to_string()
+    );
+
+    let bilingual = BilingualLyrics::from_interleaved(&interleaved);
+
+    let pairs = bilingual.pairs();
+
+    assert_eq!(2, pairs.len());
+    assert_eq!("How I wonder what you are", pairs[1].1);
+    assert_eq!(Some("滿天都是小星星"), pairs[1].2);
+}
+
+#[test]
+fn metadata_accessors() {
+    let mut lyrics = Lyrics::from_str(
+        r"[ti:Let's Twist Again]
+[AR:Chubby Checker]
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+    )
+    .unwrap();
+
+    assert_eq!(Some("Let's Twist Again"), lyrics.title());
+    assert_eq!(Some("Chubby Checker"), lyrics.artist());
+    assert_eq!(None, lyrics.album());
+
+    lyrics.set_artist("Chubby Checker & Friends").unwrap();
+    lyrics.set_album("Hits Of The 60's").unwrap();
+
+    assert_eq!(Some("Chubby Checker & Friends"), lyrics.artist());
+    assert_eq!(Some("Hits Of The 60's"), lyrics.get_tag(KnownTag::Al));
+    assert!(lyrics.set_title("Let's\nTwist").is_err());
+
+    lyrics.remove_tag(KnownTag::Ti);
+
+    assert_eq!(
+        r"[al: Hits Of The 60's]
+[ar: Chubby Checker & Friends]
+
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+        lyrics.to_string()
+    );
+}
+
+#[test]
+fn duplicate_id_tags() {
+    let s = r"[ar:Chubby Checker]
+[ti:Let's Twist Again]
+[ar:Bobby Rydell]
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe";
+
+    let lyrics = Lyrics::from_str(s).unwrap();
+
+    let artists: Vec<&str> =
+        lyrics.get_metadata().get_all("ar").map(|id_tag| id_tag.get_text()).collect();
+
+    assert_eq!(vec!["Chubby Checker", "Bobby Rydell"], artists);
+
+    assert_eq!(
+        r"[ar: Chubby Checker]
+[ar: Bobby Rydell]
+[ti: Let's Twist Again]
+
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+        lyrics.to_string()
+    );
+
+    let options = ParseOptions {
+        duplicate_id_tags: DuplicateIDTagPolicy::KeepFirst,
+        ..ParseOptions::default()
+    };
+
+    let lyrics = Lyrics::from_str_with_options(s, &options).unwrap();
+
+    assert_eq!(2, lyrics.get_metadata().len());
+    assert_eq!(Some("Chubby Checker"), lyrics.artist());
+
+    let options = ParseOptions {
+        duplicate_id_tags: DuplicateIDTagPolicy::KeepLast,
+        ..ParseOptions::default()
+    };
+
+    let mut lyrics = Lyrics::from_str_with_options(s, &options).unwrap();
+
+    assert_eq!(Some("Bobby Rydell"), lyrics.artist());
+
+    lyrics.get_metadata_mut().set_duplicate_policy(DuplicateIDTagPolicy::Keep);
+    lyrics.get_metadata_mut().insert(IDTag::from_string("AR", "Chubby Checker").unwrap());
+
+    assert_eq!(2, lyrics.get_metadata().get_all("ar").count());
+
+    lyrics.get_metadata_mut().dedup(DuplicateIDTagPolicy::KeepFirst);
+
+    assert_eq!(Some("Bobby Rydell"), lyrics.artist());
+    assert_eq!(1, lyrics.get_metadata_mut().remove("ar").len());
+    assert!(!lyrics.get_metadata().contains("ar"));
+}
+
+#[cfg(feature = "compact-lines")]
+#[test]
+fn compact_lines() {
+    let lyrics = Lyrics::from_str(
+        "[00:12.00]一閃一閃亮晶晶\n[00:15.30]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+    )
+    .unwrap();
+
+    let timed_lines = lyrics.get_timed_lines();
+
+    assert!(!timed_lines[0].1.is_heap_allocated());
+    assert!(timed_lines[1].1.is_heap_allocated());
+}
+
+#[test]
+fn metadata_order() {
+    let s = r"[ti:Let's Twist Again]
+[ar:Chubby Checker]
+[al:Hits Of The 60's]
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe";
+
+    let options = ParseOptions {
+        metadata_order: MetadataOrder::Insertion,
+        ..ParseOptions::default()
+    };
+
+    let mut lyrics = Lyrics::from_str_with_options(s, &options).unwrap();
+
+    lyrics.set_artist("Chubby Checker & Friends").unwrap();
+    lyrics.get_metadata_mut().insert(IDTag::from_string("by", "magiclen").unwrap());
+
+    assert_eq!(
+        r"[ti: Let's Twist Again]
+[ar: Chubby Checker & Friends]
+[al: Hits Of The 60's]
+[by: magiclen]
+
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+        lyrics.to_string()
+    );
+
+    lyrics.get_metadata_mut().set_order(MetadataOrder::Sorted);
+
+    assert_eq!(
+        r"[al: Hits Of The 60's]
+[ar: Chubby Checker & Friends]
+[by: magiclen]
+[ti: Let's Twist Again]
+
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+        lyrics.to_string()
+    );
+}
+
+#[test]
+fn parse_line() {
+    let mut lyrics = Lyrics::new();
+
+    assert_eq!(
+        LineOutcome {
+            id_tags: 1,
+            ..LineOutcome::default()
+        },
+        lyrics.parse_line("[ti:Let's Twist Again]").unwrap()
+    );
+
+    assert_eq!(
+        LineOutcome {
+            timed_lines: 2,
+            ..LineOutcome::default()
+        },
+        lyrics.parse_line("[00:12.00][01:15.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe").unwrap()
+    );
+
+    assert_eq!(
+        LineOutcome {
+            plain_line: true,
+            comment: true,
+            ..LineOutcome::default()
+        },
+        lyrics.parse_line("[:]Chorus").unwrap()
+    );
+
+    assert!(lyrics.parse_line("[00:15.30]Some more lyrics\n[00:18.00]").is_err());
+    assert!(lyrics.parse_line("[00:15.30]Some [ar:more] lyrics").is_err());
+
+    assert_eq!(None, lyrics.get_comments()[0].line_number());
+
+    assert_eq!(
+        r"[ti: Let's Twist Again]
+
+[:] Chorus
+
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[01:15.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+        lyrics.to_string()
+    );
+}
+
+#[test]
+fn validate() {
+    let mut lyrics = Lyrics::from_str(
+        r"[length:2:23]
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[02:30.00]Some more lyrics",
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec![ValidationIssue::ExceedsLength {
+            time_tag: TimeTag::from_str("02:30.00").unwrap(),
+            length:   Timestamp::new(143000),
+        }],
+        lyrics.validate()
+    );
+
+    lyrics.set_length(Timestamp::new(180000));
+
+    assert!(lyrics.validate().is_empty());
+
+    lyrics.set_tag(KnownTag::Length, "about three minutes").unwrap();
+
+    assert_eq!(
+        vec![ValidationIssue::InvalidLength("about three minutes".to_string())],
+        lyrics.validate()
+    );
+}
+
+#[test]
+fn transaction() {
+    let s = r"[ti: Let's Twist Again]
+
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30]Some more lyrics";
+
+    let mut lyrics = Lyrics::from_str(s).unwrap();
+
+    let result = lyrics.transaction(|tx| {
+        tx.shift_time(-12000);
+        tx.set_artist("Chubby Checker")?;
+
+        tx.shift_time_with_policy(-1000, NegativeTimePolicy::Error)
+    });
+
+    assert_eq!(Err(LyricsError::NegativeTime(TimeTag::new(-1000))), result);
+    assert_eq!(s, lyrics.to_string());
+
+    lyrics
+        .transaction(|tx| {
+            tx.shift_time(-12000);
+            tx.set_artist("Chubby Checker")
+        })
+        .unwrap();
+
+    assert_eq!(
+        r"[ar: Chubby Checker]
+[ti: Let's Twist Again]
+
+[00:00.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:03.30]Some more lyrics",
+        lyrics.to_string()
+    );
+}
+
+#[test]
+fn text_match() {
+    let options = TextMatchOptions::loose();
+
+    assert!(options.texts_eq("  Naku Penda,   Piya! ", "naku penda piya"));
+    assert!(options.texts_eq("ÄPFEL", "äpfel"));
+    assert!(options.contains("Twinkle, twinkle, little star", "TWINKLE LITTLE"));
+
+    let options = TextMatchOptions {
+        case_insensitive: true,
+        ..TextMatchOptions::default()
+    };
+
+    assert!(options.texts_eq("Twinkle", "twinkle"));
+    assert!(!options.texts_eq("ÄPFEL", "äpfel"));
+    assert!(!TextMatchOptions::exact().texts_eq("Twinkle", "twinkle"));
+
+    let lyrics = Lyrics::from_str(
+        r"[00:01.00]Twinkle, twinkle, little star
+[00:05.00]How I wonder what you are
+[00:09.00]twinkle twinkle little star!",
+    )
+    .unwrap();
+
+    assert!(lyrics.duplicate_texts(&TextMatchOptions::exact()).is_empty());
+    assert_eq!(vec![vec![0, 2]], lyrics.duplicate_texts(&TextMatchOptions::loose()));
+
+    let other = Lyrics::from_str(
+        r"[00:02.00]TWINKLE TWINKLE LITTLE STAR
+[00:06.00]Up above the world so high",
+    )
+    .unwrap();
+
+    assert_eq!(0.0, lyrics.similarity(&other, &TextMatchOptions::exact()));
+    assert_eq!(0.4, lyrics.similarity(&other, &TextMatchOptions::loose()));
+    assert_eq!(1.0, lyrics.similarity(&lyrics, &TextMatchOptions::exact()));
+    assert_eq!(1.0, Lyrics::new().similarity(&Lyrics::new(), &TextMatchOptions::exact()));
+
+    let interleaved = Lyrics::from_str(
+        r"[00:01.00]Naku Penda Piya
+[00:01.00]naku penda piya!
+[00:05.00]Naku Taka Piya
+[00:05.00]I want you",
+    )
+    .unwrap();
+
+    let bilingual = BilingualLyrics::pair_translations(&interleaved, &TextMatchOptions::loose());
+
+    assert_eq!(3, bilingual.original.get_timed_lines().len());
+    assert_eq!(
+        vec![(TimeTag::new(5000), "Naku Taka Piya", Some("I want you"))],
+        bilingual.pairs()[2..]
+    );
+    assert_eq!(
+        2,
+        BilingualLyrics::pair_translations(&interleaved, &TextMatchOptions::exact())
+            .translation
+            .get_timed_lines()
+            .len()
+    );
+}
+
+#[test]
+fn lint_metadata() {
+    let lyrics = Lyrics::from_str(
+        r"[ar:Chubby Checker]
+[AR:Bobby Rydell]
+[al:]
+[offset:half a second]
+[length:2:23]
+[mood:happy]
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            MetadataIssue::EmptyText("al".to_string()),
+            MetadataIssue::DuplicateLabel("AR".to_string()),
+            MetadataIssue::UnknownLabel("mood".to_string()),
+            MetadataIssue::InvalidOffset("half a second".to_string()),
+        ],
+        lyrics.lint_metadata()
+    );
+}
+
+#[test]
+fn comment_placement() {
+    let mut lyrics = Lyrics::new();
+
+    lyrics.set_title("Let's Twist Again").unwrap();
+    lyrics.add_comment("synced by magiclen").unwrap();
+    lyrics
+        .add_timed_line(
+            TimeTag::from_str("00:12.00").unwrap(),
+            "Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+        )
+        .unwrap();
+
+    assert_eq!(
+        r"[ti: Let's Twist Again]
+
+[:] synced by magiclen
+
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+        lyrics.to_string()
+    );
+
+    let options = SerializeOptions {
+        comment_placement: CommentPlacement::BeforeMetadata,
+        ..SerializeOptions::default()
+    };
+
+    assert_eq!(
+        r"[:] synced by magiclen
+
+[ti: Let's Twist Again]
+
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+        lyrics.to_string_with_options(&options)
+    );
+
+    let options = SerializeOptions {
+        comment_placement: CommentPlacement::End,
+        ..SerializeOptions::default()
+    };
+
+    assert_eq!(
+        r"[ti: Let's Twist Again]
+
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+
+[:] synced by magiclen",
+        lyrics.to_string_with_options(&options)
+    );
+}
+
+#[test]
+fn to_core_string() {
+    let lyrics = Lyrics::from_str(
+        r"[00:01.00]<00:01.00>Twinkle, <00:01.50>twinkle, <00:02.00>little <00:02.50>star<00:03.00>
+[00:05.00]How I wonder what you are
+[00:09.00]<00:09.00>Up above <00:10.00>the world so high",
+    )
+    .unwrap();
+
+    let (s, report) = lyrics.to_core_string();
+
+    assert_eq!(
+        r"[00:01.00]Twinkle, twinkle, little star
+[00:05.00]How I wonder what you are
+[00:09.00]Up above the world so high",
+        s
+    );
+
+    assert!(!report.is_lossless());
+    assert_eq!(6, report.dropped_word_timings());
+    assert_eq!(1, report.dropped_end_times());
+    assert_eq!(
+        DegradedLine {
+            index:                0,
+            time_tag:             TimeTag::new(1000),
+            dropped_word_timings: 4,
+            dropped_end_time:     Some(Timestamp::new(3000)),
+        },
+        report.lines[0]
+    );
+    assert_eq!(2, report.lines[1].index);
+
+    assert!(Lyrics::from_str("[00:05.00]How I wonder what you are")
+        .unwrap()
+        .to_core_string()
+        .1
+        .is_lossless());
+
+    let (s, report) =
+        Lyrics::from_str("[00:01.00]<00:01.000>Twinkle, <00:01.250>twinkle<00:01.500>")
+            .unwrap()
+            .to_core_string();
+
+    assert_eq!("[00:01.00]Twinkle, twinkle", s);
+    assert_eq!(
+        DegradedLine {
+            index:                0,
+            time_tag:             TimeTag::new(1000),
+            dropped_word_timings: 2,
+            dropped_end_time:     Some(Timestamp::new(1500)),
+        },
+        report.lines[0]
+    );
+}
+
+#[test]
+fn escape_brackets() {
+    let text = r"[Chorus: all] Naku Penda \ Piya";
+
+    assert_eq!(r"\[Chorus: all\] Naku Penda \\ Piya", lrc::escape_brackets(text));
+    assert_eq!(text, lrc::unescape_brackets(&lrc::escape_brackets(text)));
+
+    let mut lyrics = Lyrics::new();
+
+    assert!(lyrics.add_timed_line(TimeTag::new(12000), text).is_err());
+
+    lyrics.add_timed_line(TimeTag::new(12000), lrc::escape_brackets(text)).unwrap();
+
+    let s = lyrics.to_string();
+
+    assert_eq!(r"[00:12.00]\[Chorus: all\] Naku Penda \\ Piya", s);
+
+    let lyrics = Lyrics::from_str(s).unwrap();
+
+    assert_eq!(text, lrc::unescape_brackets(&lyrics.get_timed_lines()[0].1));
+}
+
+#[test]
+fn time_gaps() {
+    let lyrics = Lyrics::from_str(
+        r"[00:01.00]Twinkle, twinkle, little star
+[00:05.00]How I wonder what you are
+[00:07.00]Up above the world so high",
+    )
+    .unwrap();
+
+    let timed_lines = lyrics.get_timed_lines();
+
+    assert_eq!(Some(2000), lrc::time::min_gap(timed_lines));
+    assert_eq!(Some(4000), lrc::time::max_gap(timed_lines));
+    assert_eq!(Some(3000.0), lrc::time::mean_gap(timed_lines));
+    assert!(lrc::time::is_sorted(timed_lines));
+
+    let timed_lines = [(TimeTag::new(5000), "b"), (TimeTag::new(1000), "a")];
+
+    assert_eq!(Some(-4000), lrc::time::min_gap(&timed_lines));
+    assert!(!lrc::time::is_sorted(&timed_lines));
+    assert_eq!(None, lrc::time::mean_gap(&timed_lines[..1]));
+}
+
+#[test]
+fn format_into() {
+    let mut buffer = [0u8; 10];
+
+    let time_tag = TimeTag::from_str("01:15.30").unwrap();
+
+    let len = time_tag.format_into(&mut buffer).unwrap();
+
+    assert_eq!(time_tag.to_string().as_bytes(), &buffer[..len]);
+    assert!(time_tag.format_into(&mut buffer[..9]).is_err());
+}
+
+#[cfg(feature = "unicode-normalization")]
+#[test]
+fn normalize_nfc() {
+    let s = "[ti:Cafe\u{301}]\n[:]cafe\u{301}\n[00:12.00]Cafe\u{301} del Mar";
+
+    let lyrics = Lyrics::from_str(s).unwrap();
+
+    assert_eq!("Cafe\u{301} del Mar", lyrics.get_timed_lines()[0].1.as_ref() as &str);
+
+    let options = ParseOptions {
+        nfc: true,
+        ..ParseOptions::default()
+    };
+
+    let lyrics = Lyrics::from_str_with_options(s, &options).unwrap();
+
+    assert_eq!("Caf\u{e9} del Mar", lyrics.get_timed_lines()[0].1.as_ref() as &str);
+    assert_eq!(Some("Caf\u{e9}"), lyrics.title());
+    assert_eq!("caf\u{e9}", lyrics.get_comments()[0].text());
+}
+
+#[test]
+fn line_endings() {
+    let expected = r"[ti: Let's Twist Again]
+
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30]Some more lyrics";
+
+    for newline in ["\n", "\r\n", "\r"] {
+        let s = [
+            "[ti:Let's Twist Again]",
+            "[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+            "[00:15.30]Some more lyrics",
+        ]
+        .join(newline);
+
+        assert_eq!(expected, Lyrics::from_str(s).unwrap().to_string());
+    }
+
+    let (_, errors) =
+        Lyrics::from_str_partial("[ti:Twist]\r[00:12.00]Naku\r\n[00:15.30]Some [a:b]\n");
+
+    assert_eq!(3, errors[0].line_number);
+}
+
+#[test]
+fn parse_limits() {
+    let s = r"[ti:Let's Twist Again]
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30]Some more lyrics";
+
+    let parse = |limits: ParseLimits| {
+        Lyrics::from_str_with_options(s, &ParseOptions {
+            limits,
+            ..ParseOptions::default()
+        })
+    };
+
+    assert!(parse(ParseLimits::default()).is_ok());
+
+    assert_eq!(
+        LyricsError::LimitExceeded(LimitKind::Bytes),
+        parse(ParseLimits {
+            max_bytes: Some(64),
+            ..ParseLimits::default()
+        })
+        .unwrap_err()
+    );
+
+    assert_eq!(
+        LyricsError::LimitExceeded(LimitKind::Lines),
+        parse(ParseLimits {
+            max_lines: Some(2),
+            ..ParseLimits::default()
+        })
+        .unwrap_err()
+    );
+
+    assert_eq!(
+        LyricsError::LimitExceeded(LimitKind::LineLength),
+        parse(ParseLimits {
+            max_line_length: Some(32),
+            ..ParseLimits::default()
+        })
+        .unwrap_err()
+    );
+}
+
+#[test]
+fn skip_invalid_lines() {
+    let s = "[ti:Let's Twist Again]\n\u{0}\u{1}\u{2}PK\u{3}\n[00:12.00]Naku Penda Piya-Naku Taka \
+             Piya-Mpenziwe\n[00:15.30]Some [a:b] lyrics";
+
+    assert!(Lyrics::from_str(s).is_err());
+
+    let options = ParseOptions {
+        skip_invalid_lines: true,
+        ..ParseOptions::default()
+    };
+
+    let (lyrics, warnings) = Lyrics::from_str_with_warnings(s, &options).unwrap();
+
+    assert_eq!(
+        vec![
+            ParseWarning {
+                line_number: 2, kind: ParseWarningKind::SkippedLine
+            },
+            ParseWarning {
+                line_number: 4, kind: ParseWarningKind::SkippedLine
+            },
+        ],
+        warnings
+    );
+
+    assert_eq!(
+        r"[ti: Let's Twist Again]
+
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+        lyrics.to_string()
+    );
+}
+
+#[test]
+fn merge_identical_lines() {
+    let lyrics = Lyrics::from_str(
+        r"[00:12.00][01:15.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30][01:18.00]Some more lyrics
+[00:20.00]Once",
+    )
+    .unwrap();
+
+    let options = SerializeOptions {
+        merge_identical_lines: true,
+        ..SerializeOptions::default()
+    };
+
+    let s = lyrics.to_string_with_options(&options);
+
+    assert_eq!(
+        r"[00:12.00][01:15.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30][01:18.00]Some more lyrics
+[00:20.00]Once",
+        s
+    );
+
+    assert_eq!(lyrics.to_string(), Lyrics::from_str(s).unwrap().to_string());
+}
+
+#[test]
+fn generate() {
+    let lyrics = lrc::generate(42, 20, 4000);
+
+    assert_eq!(20, lyrics.get_timed_lines().len());
+    assert!(lyrics.title().is_some());
+    assert!(lyrics.artist().is_some());
+    assert!(lyrics.validate().is_empty());
+    assert!(lyrics.lint_metadata().is_empty());
+    assert!(lrc::time::is_sorted(lyrics.get_timed_lines()));
+
+    let mean_gap = lrc::time::mean_gap(lyrics.get_timed_lines()).unwrap();
+
+    assert!((2000.0..=6000.0).contains(&mean_gap));
+
+    let s = lyrics.to_string();
+
+    assert_eq!(s, lrc::generate(42, 20, 4000).to_string());
+    assert_ne!(s, lrc::generate(43, 20, 4000).to_string());
+    assert_eq!(s, Lyrics::from_str(&s).unwrap().to_string());
+}
+
+#[test]
+fn preserve_grouping() {
+    let mut lyrics = Lyrics::from_str(
+        r"[00:12.00][01:15.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30]Some more lyrics
+[01:18.00]Some more lyrics
+[00:20.00][00:40.00][01:00.00]Chorus",
+    )
+    .unwrap();
+
+    let options = SerializeOptions {
+        preserve_grouping: true,
+        ..SerializeOptions::default()
+    };
+
+    assert_eq!(
+        r"[00:12.00][01:15.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30]Some more lyrics
+[00:20.00][00:40.00][01:00.00]Chorus
+[01:18.00]Some more lyrics",
+        lyrics.to_string_with_options(&options)
+    );
+
+    lyrics.shift_time(-1000);
+
+    let index = lyrics.find_timed_line_index(39000).unwrap();
+
+    lyrics.remove_timed_line(index);
+
+    assert_eq!(
+        r"[00:11.00][01:14.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:14.30]Some more lyrics
+[00:19.00]Chorus
+[00:59.00]Chorus
+[01:17.00]Some more lyrics",
+        lyrics.to_string_with_options(&options)
+    );
+
+    let mut lyrics = Lyrics::from_str(
+        r"[00:00.50][00:20.00]Chorus
+[00:10.00][00:30.00]Naku Penda Piya",
+    )
+    .unwrap();
+
+    lyrics.shift_time_with_policy(-1000, NegativeTimePolicy::Remove).unwrap();
+
+    assert_eq!(
+        r"[00:09.00][00:29.00]Naku Penda Piya
+[00:19.00]Chorus",
+        lyrics.to_string_with_options(&options)
+    );
+
+    // a group cannot be revived by adding a line with the same time tag and text
+    lyrics.add_timed_line(TimeTag::new(-500), "Chorus").unwrap();
+
+    assert_eq!(
+        r"[-00:00.50]Chorus
+[00:09.00][00:29.00]Naku Penda Piya
+[00:19.00]Chorus",
+        lyrics.to_string_with_options(&options)
+    );
+}
+
+#[test]
+fn is_valid() {
+    assert!(lrc::is_valid_line_text("Naku Penda Piya-Naku Taka Piya-Mpenziwe"));
+    assert!(lrc::is_valid_line_text(r"\[Chorus: all\]"));
+    assert!(!lrc::is_valid_line_text("Some [ar:more] lyrics"));
+    assert!(!lrc::is_valid_line_text("Some\nmore lyrics"));
+
+    assert!(lrc::is_valid_tag_label("ti"));
+    assert!(lrc::is_valid_tag_label(" mood "));
+    assert!(!lrc::is_valid_tag_label(""));
+    assert!(!lrc::is_valid_tag_label("t:i"));
+    assert!(!lrc::is_valid_tag_label("[ti]"));
+}
+
+#[test]
+fn relaxed_dialect() {
+    let s = r#"[ti:Let's Twist Again]
+[00'12"00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00：15．3][01:18:00]Some more lyrics
+[01:20.125]Some more lyrics"#;
+
+    let options = ParseOptions {
+        dialect: Dialect::Relaxed,
+        ..ParseOptions::default()
+    };
+
+    let lyrics = Lyrics::from_str_with_options(s, &options).unwrap();
+
+    assert_eq!(
+        vec![12000, 15300, 78000, 80125],
+        lyrics.get_timed_lines().iter().map(|(t, _)| t.get_timestamp()).collect::<Vec<_>>()
+    );
+
+    assert_eq!(
+        r"[ti: Let's Twist Again]
+
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30]Some more lyrics
+[01:18.00]Some more lyrics
+[01:20.13]Some more lyrics",
+        lyrics.to_string()
+    );
+
+    assert!(Lyrics::from_str(s).is_err());
+}
+
+#[test]
+fn relaxed_dialect_non_ascii_digits() {
+    let options = ParseOptions {
+        dialect: Dialect::Relaxed,
+        ..ParseOptions::default()
+    };
+
+    for s in ["[00:１２.34]Naku Penda Piya", "[00:12.３]Naku Penda Piya", "[０:12]Naku Penda Piya"]
+    {
+        if let Ok(lyrics) = Lyrics::from_str_with_options(s, &options) {
+            assert!(lyrics.get_timed_lines().is_empty());
+        }
+    }
+}
+
+#[test]
+fn from_reader() {
+    let sources = [
+        "\u{FEFF}[ti:Let's Twist Again]\r\n[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe\r\n",
+        "[ti:Let's Twist Again]\r[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe\rSome more \
+         lyrics",
+        "",
+        "\n\n",
+    ];
+
+    for s in sources {
+        let (lyrics, _) = Lyrics::from_str_with_warnings(s, &ParseOptions::default()).unwrap();
+
+        let from_reader = Lyrics::from_reader(s.as_bytes()).unwrap();
+
+        assert_eq!(lyrics.to_string(), from_reader.to_string());
+        assert_eq!(lyrics.get_lines(), from_reader.get_lines());
+    }
+
+    let (_, errors) =
+        Lyrics::from_str_partial("[ti:Twist]\r[00:12.00]Naku\r\n[00:15.30]Some [a:b]\n");
+
+    let options = ParseOptions {
+        skip_invalid_lines: true,
+        ..ParseOptions::default()
+    };
+
+    let (_, warnings) = Lyrics::from_reader_with_warnings(
+        "[ti:Twist]\r[00:12.00]Naku\r\n[00:15.30]Some [a:b]\n".as_bytes(),
+        &options,
+    )
+    .unwrap();
+
+    assert_eq!(errors[0].line_number, warnings[0].line_number);
+
+    let options = ParseOptions {
+        limits: ParseLimits {
+            max_bytes: Some(16),
+            ..ParseLimits::default()
+        },
+        ..ParseOptions::default()
+    };
+
+    assert_eq!(
+        LyricsError::LimitExceeded(LimitKind::Bytes),
+        Lyrics::from_reader_with_options(sources[0].as_bytes(), &options).unwrap_err()
+    );
+
+    // no line break ever comes, and `\r` does not end a read
+    let endless = std::io::BufReader::new(std::io::repeat(b'\r'));
+
+    assert_eq!(
+        LyricsError::LimitExceeded(LimitKind::Bytes),
+        Lyrics::from_reader_with_options(endless, &options).unwrap_err()
+    );
+
+    let error = Lyrics::from_reader(&[0xFFu8, 0xFE, b'\n'][..]).unwrap_err();
+
+    assert!(matches!(error, LyricsError::IOError(_)));
+}
+
+#[test]
+fn to_writer() {
+    let lyrics = lrc::generate(7, 50, 3000);
+
+    let mut buffer = Vec::new();
+
+    lyrics.to_writer(&mut buffer).unwrap();
+
+    assert_eq!(lyrics.to_string().as_bytes(), buffer.as_slice());
+
+    let options = SerializeOptions {
+        newline: Newline::CrLf,
+        bom: true,
+        ..SerializeOptions::default()
+    };
+
+    let mut buffer = Vec::new();
+
+    lyrics.to_writer_with_options(&mut buffer, &options).unwrap();
+
+    assert_eq!(lyrics.to_string_with_options(&options).as_bytes(), buffer.as_slice());
+
+    let mut buffer = [0u8; 16];
+
+    let error = lyrics.to_writer(&mut &mut buffer[..]).unwrap_err();
+
+    assert_eq!(std::io::ErrorKind::WriteZero, error.kind());
+}
+
+#[test]
+fn extend_timed_lines() {
+    let mut lyrics = Lyrics::new();
+
+    lyrics.add_timed_line(TimeTag::new(15300), "Some more lyrics").unwrap();
+    lyrics.add_timed_line(TimeTag::new(12000), "Naku Penda Piya").unwrap();
+    lyrics.add_timed_line(TimeTag::new(12000), "Naku Taka Piya").unwrap();
+
+    lyrics
+        .extend_timed_lines(vec![
+            (TimeTag::new(20000), "Mpenziwe"),
+            (TimeTag::new(12000), "Mpenziwe 2"),
+            (TimeTag::new(5000), "Intro"),
+        ])
+        .unwrap();
+
+    assert_eq!(
+        "[00:05.00]Intro\n[00:12.00]Naku Penda Piya\n[00:12.00]Naku Taka Piya\n[00:12.00]Mpenziwe \
+         2\n[00:15.30]Some more lyrics\n[00:20.00]Mpenziwe",
+        lyrics.to_string()
+    );
+
+    assert!(lyrics
+        .extend_timed_lines(vec![(TimeTag::new(1000), "Valid"), (TimeTag::new(2000), "[ar:Tag]")])
+        .is_err());
+
+    assert_eq!(6, lyrics.get_timed_lines().len());
+}
+
+#[test]
+fn find_timed_line() {
+    let lyrics = Lyrics::from_str(
+        r"[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30]Some more lyrics",
+    )
+    .unwrap();
+
+    assert_eq!(None, lyrics.find_timed_line(11000));
+    assert_eq!(
+        Some((&TimeTag::new(12000), "Naku Penda Piya-Naku Taka Piya-Mpenziwe")),
+        lyrics.find_timed_line(15299)
+    );
+    assert_eq!(Some((&TimeTag::new(15300), "Some more lyrics")), lyrics.find_timed_line(15300));
+}
+
+#[test]
+fn next_and_previous_timed_lines() {
+    let lyrics = Lyrics::from_str(
+        r"[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30]Some more lyrics
+[00:20.00]The last line",
+    )
+    .unwrap();
+
+    assert_eq!(
+        Some((&TimeTag::new(12000), "Naku Penda Piya-Naku Taka Piya-Mpenziwe")),
+        lyrics.next_timed_line_after(0)
+    );
+    assert_eq!(
+        Some((&TimeTag::new(15300), "Some more lyrics")),
+        lyrics.next_timed_line_after(12000)
+    );
+    assert_eq!(None, lyrics.next_timed_line_after(20000));
+
+    assert_eq!(None, lyrics.previous_timed_line_before(12000));
+    assert_eq!(
+        Some((&TimeTag::new(12000), "Naku Penda Piya-Naku Taka Piya-Mpenziwe")),
+        lyrics.previous_timed_line_before(15300)
+    );
+    assert_eq!(
+        Some((&TimeTag::new(15300), "Some more lyrics")),
+        lyrics.previous_timed_line_before(16000)
+    );
+}
+
+#[test]
+fn timed_line_spans() {
+    let mut lyrics = Lyrics::from_str(
+        r"[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30]Some more lyrics",
+    )
+    .unwrap();
+
+    let spans: Vec<_> = lyrics.timed_line_spans(3000).collect();
+
+    assert_eq!(
+        vec![
+            (TimeTag::new(12000), TimeTag::new(15300), "Naku Penda Piya-Naku Taka Piya-Mpenziwe"),
+            (TimeTag::new(15300), TimeTag::new(18300), "Some more lyrics"),
+        ],
+        spans
+    );
+
+    lyrics.set_length(Timestamp::new(20000));
+
+    let mut spans = lyrics.timed_line_spans(3000);
+
+    assert_eq!(2, spans.len());
+    assert_eq!(Some((TimeTag::new(15300), TimeTag::new(20000), "Some more lyrics")), spans.nth(1));
+    assert_eq!(None, spans.next());
+}
+
+#[test]
+fn line_progress() {
+    let mut lyrics = Lyrics::from_str(
+        r"[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:16.00]Some more lyrics",
+    )
+    .unwrap();
+
+    assert_eq!(None, lyrics.line_progress(11000));
+    assert_eq!(Some((0, 0.0)), lyrics.line_progress(12000));
+    assert_eq!(Some((0, 0.25)), lyrics.line_progress(13000));
+    assert_eq!(Some((1, 1.0)), lyrics.line_progress(16000));
+    assert_eq!(Some((1, 1.0)), lyrics.line_progress(17000));
+
+    lyrics.set_length(Timestamp::new(20000));
+
+    assert_eq!(Some((1, 0.5)), lyrics.line_progress(18000));
+    assert_eq!(Some((1, 1.0)), lyrics.line_progress(25000));
+}
+
+#[test]
+fn find_nearest_timed_line_index() {
+    let lyrics = Lyrics::from_str(
+        r"[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:16.00]Some more lyrics",
+    )
+    .unwrap();
+
+    assert_eq!(None, Lyrics::new().find_nearest_timed_line_index(0, None));
+
+    assert_eq!(Some(0), lyrics.find_nearest_timed_line_index(0, None));
+    assert_eq!(Some(0), lyrics.find_nearest_timed_line_index(13999, None));
+    assert_eq!(Some(0), lyrics.find_nearest_timed_line_index(14000, None));
+    assert_eq!(Some(1), lyrics.find_nearest_timed_line_index(14001, None));
+    assert_eq!(Some(1), lyrics.find_nearest_timed_line_index(60000, None));
+
+    assert_eq!(Some(0), lyrics.find_nearest_timed_line_index(11500, Some(500)));
+    assert_eq!(None, lyrics.find_nearest_timed_line_index(11499, Some(500)));
+    assert_eq!(None, lyrics.find_nearest_timed_line_index(14000, Some(1000)));
+}
+
+#[test]
+fn search() {
+    let mut lyrics = Lyrics::from_str(
+        r"[00:12.00]<00:12.00>Naku <00:12.50>Penda Piya
+[00:15.30]Some more lyrics
+[00:18.00]ÜBER \[Piya\]
+Plain piya line",
+    )
+    .unwrap();
+
+    lyrics.add_line("Nothing here").unwrap();
+
+    assert_eq!(
+        vec![
+            LineMatch::Timed {
+                index:    0,
+                time_tag: TimeTag::new(12000),
+                text:     "<00:12.00>Naku <00:12.50>Penda Piya",
+            },
+            LineMatch::Timed {
+                index:    2,
+                time_tag: TimeTag::new(18000),
+                text:     r"ÜBER \[Piya\]",
+            },
+            LineMatch::Plain {
+                index: 0, text: "Plain piya line"
+            },
+        ],
+        lyrics.search("PIYA")
+    );
+
+    assert_eq!(1, lyrics.search("naku penda").len());
+    assert_eq!(1, lyrics.search("über [piya]").len());
+    assert!(lyrics.search("").is_empty());
+    assert!(lyrics.search("missing").is_empty());
+
+    assert!(lyrics.search_with_options("PIYA", &TextMatchOptions::exact()).is_empty());
+    assert_eq!(1, lyrics.search_with_options("über piya", &TextMatchOptions::loose()).len());
+    assert!(lyrics.search_with_options("[]", &TextMatchOptions::loose()).is_empty());
+}
+
+#[cfg(feature = "regex-search")]
+#[test]
+fn search_regex() {
+    use lrc::{regex::Regex, RegexLineMatch};
+
+    let lyrics = Lyrics::from_str(
+        r"[00:12.00]Naku Penda Piya (yeah) (oh)
+[00:15.30]Some more lyrics
+Plain line (ooh) (ah)",
+    )
+    .unwrap();
+
+    let regex = Regex::new(r"\([a-z]+\)").unwrap();
+
+    assert_eq!(
+        vec![
+            RegexLineMatch {
+                line:   LineMatch::Timed {
+                    index:    0,
+                    time_tag: TimeTag::new(12000),
+                    text:     "Naku Penda Piya (yeah) (oh)",
+                },
+                ranges: vec![16..22, 23..27],
+            },
+            RegexLineMatch {
+                line:   LineMatch::Plain {
+                    index: 0, text: "Plain line (ooh) (ah)"
+                },
+                ranges: vec![11..16, 17..21],
+            },
+        ],
+        lyrics.search_regex(&regex)
+    );
+}
+
+#[cfg(feature = "aho-corasick")]
+#[test]
+fn search_patterns() {
+    use lrc::{aho_corasick::AhoCorasick, PatternLineMatch};
+
+    let mut lyrics = Lyrics::from_str(
+        r"[length:0:20]
+[00:12.00]<00:12.00>Naku <00:12.50>Penda Piya
+[00:15.30]Some more lyrics
+Plain Piya line",
+    )
+    .unwrap();
+
+    lyrics.add_line("Nothing here").unwrap();
+
+    let automaton = AhoCorasick::builder()
+        .ascii_case_insensitive(true)
+        .build(["piya", "naku penda", "more"])
+        .unwrap();
+
+    assert_eq!(
+        vec![
+            PatternLineMatch {
+                line:       LineMatch::Timed {
+                    index:    0,
+                    time_tag: TimeTag::new(12000),
+                    text:     "<00:12.00>Naku <00:12.50>Penda Piya",
+                },
+                time_range: Some(TimeTag::new(12000)..TimeTag::new(15300)),
+                patterns:   vec![0, 1],
+            },
+            PatternLineMatch {
+                line:       LineMatch::Timed {
+                    index:    1,
+                    time_tag: TimeTag::new(15300),
+                    text:     "Some more lyrics",
+                },
+                time_range: Some(TimeTag::new(15300)..TimeTag::new(20000)),
+                patterns:   vec![2],
+            },
+            PatternLineMatch {
+                line:       LineMatch::Plain {
+                    index: 0, text: "Plain Piya line"
+                },
+                time_range: None,
+                patterns:   vec![0],
+            },
+        ],
+        lyrics.search_patterns(&automaton, 5000)
+    );
+}
+
+#[test]
+fn find_line() {
+    let lyrics = Lyrics::from_str(
+        r"[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30]Some more lyrics",
+    )
+    .unwrap();
+
+    assert_eq!(None, lyrics.find_line(11000));
+
+    assert_eq!(
+        Some(FoundLine {
+            index:     0,
+            time:      TimeTag::new(12000),
+            text:      "Naku Penda Piya-Naku Taka Piya-Mpenziwe",
+            next_time: Some(TimeTag::new(15300)),
+        }),
+        lyrics.find_line(15000)
+    );
+
+    assert_eq!(0, lyrics.find_line_with_boundary(15300, Boundary::Exclusive).unwrap().index);
+
+    let found_line = lyrics.find_line(15300).unwrap();
+
+    assert_eq!(
+        (1, "Some more lyrics", None),
+        (found_line.index, found_line.text, found_line.next_time)
+    );
+}
+
+#[test]
+fn approx_eq() {
+    use std::cmp::Ordering;
+
+    let time_tag = TimeTag::new(12000);
+
+    assert!(time_tag.approx_eq(TimeTag::new(12040), 50));
+    assert!(time_tag.approx_eq(TimeTag::new(11950), 50));
+    assert!(!time_tag.approx_eq(TimeTag::new(12051), 50));
+    assert!(!time_tag.approx_eq(TimeTag::new(12001), -10));
+
+    assert_eq!(Ordering::Equal, time_tag.cmp_with_tolerance(TimeTag::new(12030), 50));
+    assert_eq!(Ordering::Less, time_tag.cmp_with_tolerance(TimeTag::new(12100), 50));
+    assert_eq!(Ordering::Greater, time_tag.cmp_with_tolerance(TimeTag::new(11900), 50));
+
+    let lyrics = Lyrics::from_str(
+        r"[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30]Some more lyrics",
+    )
+    .unwrap();
+
+    assert_eq!(None, lyrics.find_line_with_tolerance(11900, 50));
+    assert_eq!(0, lyrics.find_line_with_tolerance(11960, 50).unwrap().index);
+    assert_eq!(0, lyrics.find_line_with_tolerance(15200, 50).unwrap().index);
+    assert_eq!(1, lyrics.find_line_with_tolerance(15270, 50).unwrap().index);
+}
+
+#[cfg(feature = "subparse")]
+#[test]
+fn subparse() {
+    use std::convert::TryFrom;
+
+    use lrc::subparse::{SubtitleFile, SubtitleFormat};
+
+    let lyrics = Lyrics::from_str(
+        r"[length:0:20]
+[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30]Some more lyrics",
+    )
+    .unwrap();
+
+    let file = SubtitleFile::try_from(&lyrics).unwrap();
+
+    let srt = String::from_utf8(file.to_data().unwrap()).unwrap();
+
+    assert!(srt.contains("00:00:12,000 --> 00:00:15,300"));
+    assert!(srt.contains("00:00:15,300 --> 00:00:20,000"));
+
+    let lyrics = Lyrics::from_str(
+        r"[00:12.00]<00:12.00>Naku <00:12.50>Penda \[Piya\]
+[00:15.30]
+[00:18.00]Some more lyrics",
+    )
+    .unwrap();
+
+    let file = SubtitleFile::try_from(&lyrics).unwrap();
+
+    let srt = String::from_utf8(file.to_data().unwrap()).unwrap();
+
+    assert_eq!(
+        lyrics.to_srt().lines().filter(|line| !line.is_empty()).collect::<Vec<_>>(),
+        srt.lines().filter(|line| !line.is_empty()).collect::<Vec<_>>()
+    );
+
+    let file = lrc::subparse::parse_str(
+        SubtitleFormat::SubRip,
+        "1\n00:00:12,000 --> 00:00:15,300\nNaku Penda Piya-Naku\nTaka \
+         Piya-Mpenziwe\n\n2\n00:00:15,300 --> 00:00:20,000\nSome more lyrics\n",
+        25.0,
+    )
+    .unwrap();
+
+    assert_eq!(
+        r"[00:12.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe
+[00:15.30]Some more lyrics",
+        Lyrics::try_from(&file).unwrap().to_string()
+    );
+}
+
+#[test]
+fn file() {
+    let lyrics = lrc::generate(3, 10, 3000);
+
+    let path = std::env::temp_dir().join(format!("lrc-test-{}.lrc", std::process::id()));
+
+    let options = SerializeOptions {
+        bom: true,
+        newline: Newline::CrLf,
+        ..SerializeOptions::default()
+    };
+
+    lyrics.to_file_with_options(&path, &options).unwrap();
+
+    let read = Lyrics::from_file(&path).unwrap();
+
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(lyrics.to_string(), read.to_string());
+
+    match Lyrics::from_file(&path).unwrap_err() {
+        LyricsError::FileError(p, error) => {
+            assert_eq!(path, p);
+            assert!(matches!(*error, LyricsError::IOError(_)));
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(all(feature = "arc-lines", not(feature = "compact-lines")))]
+#[test]
+fn arc_lines() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let lyrics =
+        Lyrics::from_str("[00:12.00][01:15.00]Naku Penda Piya-Naku Taka Piya-Mpenziwe").unwrap();
+
+    assert_send_sync(&lyrics);
+
+    let timed_lines = lyrics.get_timed_lines();
+
+    assert!(std::sync::Arc::ptr_eq(&timed_lines[0].1, &timed_lines[1].1));
+}
+
+#[test]
+fn event_parser() {
+    let events = LyricsEventParser::new(
+        "[ti: Title]\n[:] comment\n[00:12.00][00:15.30]Naku Penda Piya\nplain line",
+    )
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+
+    match &events[1] {
+        LyricsEvent::Comment(comment) => {
+            assert_eq!("comment", comment.text());
+            assert_eq!(Some(2), comment.line_number());
+        },
+        _ => unreachable!(),
+    }
+
+    assert_eq!(
+        vec![
+            LyricsEvent::IdTag(IDTag::from_string("ti", "Title").unwrap()),
+            events[1].clone(),
+            LyricsEvent::TimedLine(
+                vec![TimeTag::new(12000), TimeTag::new(15300)],
+                "Naku Penda Piya"
+            ),
+            LyricsEvent::PlainLine("plain line"),
+        ],
+        events
+    );
+
+    let mut parser =
+        LyricsEventParser::new("[00:01.00]a\n[00:02.00]Some [ar:more] lyrics\n[00:02.00]b");
+
+    assert!(matches!(parser.next(), Some(Ok(LyricsEvent::TimedLine(_, "a")))));
+    assert!(matches!(parser.next(), Some(Err(_))));
+    assert!(parser.next().is_none());
+
+    let mut parser = LyricsEventParser::with_options("[00:01.00]a", ParseOptions {
+        limits: ParseLimits {
+            max_bytes: Some(4),
+            ..ParseLimits::default()
+        },
+        ..ParseOptions::default()
+    });
+
+    assert_eq!(
+        Some(Err(LyricsError::LimitExceeded(LimitKind::Bytes))),
+        parser.next().map(|r| r.map(|_| ()))
+    );
+    assert!(parser.next().is_none());
+
+    let events = LyricsEventParser::with_options(
+        "[00:01.00][00:01.00]a\n[00:02.00]Some [ar:more] lyrics\n[00:03.00]b",
+        ParseOptions {
+            skip_invalid_lines: true,
+            duplicate_time_tags: DuplicateTimeTagPolicy::Merge,
+            ..ParseOptions::default()
+        },
+    )
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            LyricsEvent::Warning(ParseWarning {
+                line_number: 1,
+                kind:        ParseWarningKind::DuplicateTimeTag(TimeTag::new(1000)),
+            }),
+            LyricsEvent::TimedLine(vec![TimeTag::new(1000)], "a"),
+            LyricsEvent::Warning(ParseWarning {
+                line_number: 2,
+                kind:        ParseWarningKind::SkippedLine,
+            }),
+            LyricsEvent::TimedLine(vec![TimeTag::new(3000)], "b"),
+        ],
+        events
+    );
+}
+
+#[test]
+fn push_parser() {
+    let s = "\u{FEFF}[ti: Let's Twist Again]\r\n[00:12.00]Naku Penda Piya\r[00:15.30]Naku Taka \
+             Piya\n\n[:] comment\r\nplain";
+
+    let expected = Lyrics::from_str(s).unwrap();
+
+    for chunk_size in 1..=s.len() {
+        let mut parser = LyricsPushParser::new();
+
+        for chunk in s.as_bytes().chunks(chunk_size) {
+            parser.feed(chunk).unwrap();
+        }
+
+        let lyrics = parser.finish().unwrap();
+
+        assert_eq!(expected.to_string(), lyrics.to_string());
+        assert_eq!(expected.get_lines(), lyrics.get_lines());
+    }
+
+    let mut parser = LyricsPushParser::new();
+
+    assert_eq!(0, parser.feed("[00:12.00]Naku Penda ").unwrap());
+    assert_eq!(0, parser.feed("Piya\r").unwrap());
+    assert_eq!(0, parser.line_count());
+    assert_eq!(2, parser.feed("\n[00:15.30]Naku Taka Piya\n").unwrap());
+    assert_eq!(2, parser.line_count());
+    assert_eq!(2, parser.lyrics().get_timed_lines().len());
+
+    let lyrics = parser.finish().unwrap();
+
+    assert_eq!(2, lyrics.get_timed_lines().len());
+
+    let mut parser = LyricsPushParser::new();
+
+    assert!(parser.feed(b"[00:12.00]\xFF\n".as_ref()).is_err());
+
+    let mut parser = LyricsPushParser::with_options(ParseOptions {
+        limits: ParseLimits {
+            max_bytes: Some(8),
+            ..ParseLimits::default()
+        },
+        ..ParseOptions::default()
+    });
+
+    assert_eq!(Err(LyricsError::LimitExceeded(LimitKind::Bytes)), parser.feed("[00:12.00]"));
+}
+
+#[test]
+fn rounding() {
+    let mut lyrics = Lyrics::new();
+
+    lyrics.add_timed_line(TimeTag::new(12005), "Naku Penda Piya").unwrap();
+    lyrics.add_timed_line(TimeTag::new(15019), "Naku Taka Piya").unwrap();
+    lyrics.add_timed_line(TimeTag::new(59995), "Mpenziwe").unwrap();
+
+    let serialize = |rounding| {
+        lyrics.to_string_with_options(&SerializeOptions {
+            rounding,
+            ..SerializeOptions::default()
+        })
+    };
+
+    assert_eq!(
+        "[00:12.01]Naku Penda Piya\n[00:15.02]Naku Taka Piya\n[01:00.00]Mpenziwe",
+        serialize(Rounding::HalfUp)
+    );
+    assert_eq!(
+        "[00:12.00]Naku Penda Piya\n[00:15.02]Naku Taka Piya\n[01:00.00]Mpenziwe",
+        serialize(Rounding::HalfEven)
+    );
+    assert_eq!(
+        "[00:12.00]Naku Penda Piya\n[00:15.01]Naku Taka Piya\n[00:59.99]Mpenziwe",
+        serialize(Rounding::Truncate)
+    );
+
+    let s = serialize(Rounding::HalfEven);
+
+    assert_eq!(
+        s,
+        Lyrics::from_str(&s).unwrap().to_string_with_options(&SerializeOptions {
+            rounding: Rounding::HalfEven,
+            ..SerializeOptions::default()
+        })
+    );
+}
+
+#[test]
+fn time_format() {
+    let mut lyrics = Lyrics::new();
+
+    lyrics.add_timed_line(TimeTag::new(59990), "Naku Penda Piya").unwrap();
+    lyrics.add_timed_line(TimeTag::new(12345678), "Mpenziwe").unwrap();
+
+    assert_eq!("[00:59.99]Naku Penda Piya\n[205:45.68]Mpenziwe", lyrics.to_string());
+
+    assert_eq!(
+        "[00:59.99]Naku Penda Piya\n[3:25:45.68]Mpenziwe",
+        lyrics.to_string_with_options(&SerializeOptions {
+            time_format: TimeFormat::HoursMinutesSeconds,
+            ..SerializeOptions::default()
+        })
+    );
+
+    for dialect in [Dialect::Standard, Dialect::Relaxed] {
+        let s = lyrics.to_string_with_options(&SerializeOptions {
+            time_format: TimeFormat::HoursMinutesSeconds,
+            ..SerializeOptions::default()
+        });
+
+        let parsed = Lyrics::from_str_with_options(&s, &ParseOptions {
+            dialect,
+            ..ParseOptions::default()
+        })
+        .unwrap();
+
+        assert!(parsed.get_metadata().is_empty());
+        assert_eq!(
+            vec![59990, 12345680],
+            parsed.get_timed_lines().iter().map(|(t, _)| t.get_timestamp()).collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn cursor_rate() {
+    let lyrics = Lyrics::from_str(
+        r"[00:01.00]Line 1
+[00:03.00]Line 2
+[00:20.00]Line 3",
+    )
+    .unwrap();
+
+    let mut cursor = lyrics.cursor();
+
+    assert_eq!(1.0, cursor.rate());
+    assert!(cursor.set_rate(0.0).is_err());
+    assert!(cursor.set_rate(f32::NAN).is_err());
+
+    cursor.set_rate(2.0).unwrap();
+    cursor.set_clear_after(Some(5000));
+
+    assert!(matches!(
+        cursor.update(500),
+        Some(CursorEvent::Line {
+            index: 0,
+            ..
+        })
+    ));
+    assert!(matches!(
+        cursor.update(1500),
+        Some(CursorEvent::Line {
+            index: 1,
+            ..
+        })
+    ));
+    assert_eq!(None, cursor.update(6499));
+    assert_eq!(Some(CursorEvent::Clear), cursor.update(6500));
+
+    // at 6500 ms of real time, the position in the lyrics is 13000 ms
+    cursor.set_rate(0.5).unwrap();
+
+    assert_eq!(None, cursor.update(20499));
+    assert!(matches!(
+        cursor.update(20500),
+        Some(CursorEvent::Line {
+            index: 2,
+            ..
+        })
+    ));
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn from_async_reader() {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+
+    let s = "\u{FEFF}[ti: Let's Twist Again]\r\n[00:12.00]Naku Penda Piya\n[:] comment\n";
+
+    let lyrics = runtime.block_on(Lyrics::from_async_reader(s.as_bytes())).unwrap();
+
+    assert_eq!(Lyrics::from_str(s).unwrap().to_string(), lyrics.to_string());
+
+    assert!(runtime.block_on(Lyrics::from_async_reader(b"[00:12.00]\xFF".as_ref())).is_err());
+}
+
+#[test]
+fn diff_alignment() {
+    let left = Lyrics::from_str(
+        r"[00:01.00]Line 1
+[00:03.00]Line 2
+[00:05.00]Line 3
+[00:07.00]Line 4",
+    )
+    .unwrap();
+
+    let right = Lyrics::from_str(
+        r"[00:01.50]Line 1
+[00:04.00]Line 2.5
+[00:05.20]Line 3
+[00:07.00]Line 4
+[00:09.00]Line 5",
+    )
+    .unwrap();
+
+    let alignment = left.diff_alignment(&right);
+
+    assert_eq!(
+        vec![
+            AlignedLine::Matched {
+                left_index:  0,
+                right_index: 0,
+                left_time:   TimeTag::new(1000),
+                right_time:  TimeTag::new(1500),
+                text:        "Line 1",
+            },
+            AlignedLine::LeftOnly {
+                index: 1, time_tag: TimeTag::new(3000), text: "Line 2"
+            },
+            AlignedLine::RightOnly {
+                index:    1,
+                time_tag: TimeTag::new(4000),
+                text:     "Line 2.5",
+            },
+            AlignedLine::Matched {
+                left_index:  2,
+                right_index: 2,
+                left_time:   TimeTag::new(5000),
+                right_time:  TimeTag::new(5200),
+                text:        "Line 3",
+            },
+            AlignedLine::Matched {
+                left_index:  3,
+                right_index: 3,
+                left_time:   TimeTag::new(7000),
+                right_time:  TimeTag::new(7000),
+                text:        "Line 4",
+            },
+            AlignedLine::RightOnly {
+                index:    4,
+                time_tag: TimeTag::new(9000),
+                text:     "Line 5",
+            },
+        ],
+        alignment
+    );
+
+    assert_eq!(
+        vec![Some(500), None, None, Some(200), Some(0), None],
+        alignment.iter().map(AlignedLine::time_delta).collect::<Vec<_>>()
+    );
+
+    assert!(left
+        .diff_alignment(&Lyrics::new())
+        .iter()
+        .all(|a| matches!(a, AlignedLine::LeftOnly { .. })));
+
+    let right = Lyrics::from_str("[00:01.50]line 1!\n[00:05.20]LINE  3").unwrap();
+
+    assert!(left.diff_alignment(&right).iter().all(|a| a.time_delta().is_none()));
+
+    let alignment = left.diff_alignment_with_options(&right, &TextMatchOptions::loose());
+
+    assert_eq!(
+        vec![Some(500), None, Some(200), None],
+        alignment.iter().map(AlignedLine::time_delta).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn load_dir() {
+    let directory = std::env::temp_dir().join(format!("lrc-test-dir-{}", std::process::id()));
+    let sub_directory = directory.join("sub");
+
+    std::fs::create_dir_all(&sub_directory).unwrap();
+
+    std::fs::write(directory.join("a.lrc"), "[00:12.00]Naku Penda Piya").unwrap();
+    std::fs::write(directory.join("b.LRC"), "[00:12.00]Naku [ar:Taka] Piya").unwrap();
+    std::fs::write(directory.join("c.txt"), "[00:12.00]Mpenziwe").unwrap();
+    std::fs::write(sub_directory.join("d.lrc"), "[00:15.30]Some more lyrics").unwrap();
+
+    let results = lrc::load_dir(&directory, false);
+
+    assert_eq!(
+        vec![directory.join("a.lrc"), directory.join("b.LRC")],
+        results.iter().map(|(path, _)| path.clone()).collect::<Vec<_>>()
+    );
+    assert!(results[0].1.is_ok());
+    assert!(matches!(results[1].1, Err(LyricsError::FileError(..))));
+
+    let results = lrc::load_dir(&directory, true);
+
+    assert_eq!(3, results.len());
+    assert_eq!(sub_directory.join("d.lrc"), results[2].0);
+
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    let results = lrc::load_dir(&directory, true);
+
+    assert_eq!(1, results.len());
+    assert!(results[0].1.is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn load_dir_symlink_cycle() {
+    let directory = std::env::temp_dir().join(format!("lrc-test-cycle-{}", std::process::id()));
+    let sub_directory = directory.join("sub");
+
+    std::fs::create_dir_all(&sub_directory).unwrap();
+
+    std::fs::write(sub_directory.join("a.lrc"), "[00:12.00]Naku Penda Piya").unwrap();
+    std::os::unix::fs::symlink(&directory, sub_directory.join("parent")).unwrap();
+
+    let results = lrc::load_dir(&directory, true);
+
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    assert_eq!(
+        vec![sub_directory.join("a.lrc")],
+        results.iter().map(|(path, _)| path.clone()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn audit_text() {
+    let mut lyrics = Lyrics::new();
+
+    lyrics.add_timed_line(TimeTag::new(12000), "Naku Penda\tPiya").unwrap();
+    lyrics.add_timed_line(TimeTag::new(15300), "Naku \u{202E}Taka Piya").unwrap();
+    lyrics.add_line("Mpen\u{200B}zi\u{9B}we").unwrap();
+
+    assert_eq!(
+        vec![
+            TextIssue {
+                location:  TextLocation::TimedLine(1),
+                offset:    5,
+                character: '\u{202E}',
+                kind:      SuspiciousCharKind::Bidi,
+            },
+            TextIssue {
+                location:  TextLocation::Line(0),
+                offset:    4,
+                character: '\u{200B}',
+                kind:      SuspiciousCharKind::ZeroWidth,
+            },
+            TextIssue {
+                location:  TextLocation::Line(0),
+                offset:    9,
+                character: '\u{9B}',
+                kind:      SuspiciousCharKind::Control,
+            },
+        ],
+        lyrics.audit_text()
+    );
+
+    assert_eq!(2, lyrics.sanitize_text());
+    assert!(lyrics.audit_text().is_empty());
+    assert_eq!("Naku Taka Piya", lyrics.get_timed_lines()[1].1.as_ref() as &str);
+    assert_eq!("Mpenziwe", lyrics.get_lines()[0]);
+    assert_eq!(0, lyrics.sanitize_text());
+}
+
+#[test]
+fn sidecar() {
+    let directory = std::env::temp_dir().join(format!("lrc-test-sidecar-{}", std::process::id()));
+    let lyrics_directory = directory.join("Lyrics");
+
+    std::fs::create_dir_all(&lyrics_directory).unwrap();
+
+    let audio = directory.join("Let's Twist Again (feat. Chubby Checker).mp3");
+
+    assert_eq!(None, lrc::find_sidecar(&audio));
+    assert!(Lyrics::from_sidecar(&audio).unwrap().is_none());
+
+    let in_lyrics_directory = lyrics_directory.join("Let's Twist Again (feat. Chubby Checker).LRC");
+
+    std::fs::write(&in_lyrics_directory, "[00:15.30]Some more lyrics").unwrap();
+
+    assert_eq!(Some(in_lyrics_directory), lrc::find_sidecar(&audio));
+
+    let beside = directory.join("Let's Twist Again (feat. Chubby Checker).lrc");
+
+    std::fs::write(&beside, "[00:12.00]Naku Penda Piya").unwrap();
+
+    assert_eq!(Some(beside), lrc::find_sidecar(&audio));
+    assert_eq!(
+        "[00:12.00]Naku Penda Piya",
+        Lyrics::from_sidecar(&audio).unwrap().unwrap().to_string()
+    );
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn line_hook() {
+    let lyrics = Lyrics::from_str(
+        r"[00:12.00][01:15.00]Naku Penda Piya
+[00:15.30]Some more lyrics ...",
+    )
+    .unwrap();
+
+    fn hook(time_tag: &TimeTag, line: &str) -> Option<String> {
+        if time_tag.get_timestamp() == 75000 {
+            Some(format!("{}M:{}", time_tag, line))
+        } else {
+            None
+        }
+    }
+
+    let serialize = |merge_identical_lines, preserve_grouping| {
+        lyrics.to_string_with_options(&SerializeOptions {
+            line_hook: Some(hook),
+            merge_identical_lines,
+            preserve_grouping,
+            ..SerializeOptions::default()
+        })
+    };
+
+    let expected =
+        "[00:12.00]Naku Penda Piya\n[00:15.30]Some more lyrics ...\n[01:15.00]M:Naku Penda Piya";
+
+    assert_eq!(expected, serialize(false, false));
+    assert_eq!(expected, serialize(true, false));
+    assert_eq!(expected, serialize(false, true));
+}
+
+#[test]
+fn spaces_between_time_tags() {
+    let s = "[00:12.00] [01:15.00]\t[02:03.00] Naku Penda Piya\n[00:15.30] [ti: Title] [01:20.00] \
+             Some more lyrics ...";
+
+    let expected = vec![
+        (TimeTag::new(12000), "Naku Penda Piya"),
+        (TimeTag::new(15300), "Some more lyrics ..."),
+        (TimeTag::new(75000), "Naku Penda Piya"),
+        (TimeTag::new(80000), "Some more lyrics ..."),
+        (TimeTag::new(123000), "Naku Penda Piya"),
+    ];
+
+    for dialect in [Dialect::Standard, Dialect::Relaxed] {
+        let lyrics = Lyrics::from_str_with_options(s, &ParseOptions {
+            dialect,
+            ..ParseOptions::default()
+        })
+        .unwrap();
+
+        assert_eq!(
+            expected,
+            lyrics
+                .get_timed_lines()
+                .iter()
+                .map(|(time_tag, line)| (*time_tag, line.as_ref() as &str))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some("Title"), lyrics.title());
+    }
+}
+
+#[test]
+fn to_srt() {
+    let mut lyrics = Lyrics::from_str(
+        r"[00:12.00]Naku Penda Piya
+[00:15.30]
+[62:03.45]Some more lyrics ...",
+    )
+    .unwrap();
+
+    assert_eq!(
+        "1\n00:00:12,000 --> 00:00:15,300\nNaku Penda Piya\n\n2\n01:02:03,450 --> \
+         01:02:08,450\nSome more lyrics ...\n\n",
+        lyrics.to_srt()
+    );
+
+    assert!(lyrics
+        .to_srt_with_options(&SrtOptions {
+            last_line_duration: 1000
+        })
+        .ends_with("01:02:03,450 --> 01:02:04,450\nSome more lyrics ...\n\n"));
+
+    lyrics.set_tag(KnownTag::Length, "62:10").unwrap();
+
+    assert!(lyrics.to_srt().ends_with("01:02:03,450 --> 01:02:10,000\nSome more lyrics ...\n\n"));
+
+    assert_eq!("", Lyrics::new().to_srt());
+
+    let lyrics = Lyrics::from_str(
+        r"[00:01.00]<00:01.00>Hello <00:01.50>world \[x:y\]
+[00:03.00]<00:03.00>",
+    )
+    .unwrap();
+
+    assert_eq!("1\n00:00:01,000 --> 00:00:03,000\nHello world [x:y]\n\n", lyrics.to_srt());
+}
+
+#[cfg(feature = "conformance")]
+#[test]
+fn conformance() {
+    use lrc::conformance::{self, Outcome};
+
+    let report = conformance::run(&ParseOptions::default());
+
+    assert_eq!(conformance::samples().len(), report.results.len());
+
+    let failed: Vec<&str> = report.failed().map(|r| r.sample.name).collect();
+
+    assert_eq!(
+        vec!["millisecond_fraction", "full_width_separators", "apostrophe_separators"],
+        failed
+    );
+    assert!(matches!(report.get("millisecond_fraction").unwrap().outcome, Outcome::Mismatch(_)));
+
+    let report = conformance::run(&ParseOptions {
+        dialect: Dialect::Relaxed,
+        ..ParseOptions::default()
+    });
+
+    assert!(report.is_all_passed(), "{:?}", report.failed().collect::<Vec<_>>());
+
+    let report = conformance::run(&ParseOptions {
+        duplicate_time_tags: DuplicateTimeTagPolicy::Keep,
+        ..ParseOptions::default()
+    });
+
+    assert!(!report.get("duplicate_time_tags").unwrap().is_passed());
+}
+
+#[cfg(feature = "ass")]
+#[test]
+fn to_ass() {
+    use lrc::AssOptions;
+
+    let lyrics = Lyrics::from_str(
+        r"[ti: Let's Twist Again]
+[00:12.00]Naku Penda Piya
+[00:15.30]<00:15.30>Some <00:15.80>more {lyrics} <00:16.555>...<00:17.00>
+[00:20.00]
+[62:03.45]Mpenziwe",
+    )
+    .unwrap();
+
+    let ass = lyrics.to_ass();
+
+    assert!(ass.starts_with("[Script Info]\nTitle: Let's Twist Again\nScriptType: v4.00+\n\n"));
+    assert!(ass.contains("Style: Default,Arial,48,"));
+
+    let events: Vec<&str> = ass.lines().filter(|line| line.starts_with("Dialogue:")).collect();
+
+    assert_eq!(
+        vec![
+            "Dialogue: 0,0:00:12.00,0:00:15.30,Default,,0,0,0,,Naku Penda Piya",
+            "Dialogue: 0,0:00:15.30,0:00:17.00,Default,,0,0,0,,{\\k50}Some {\\k76}more \
+             \\{lyrics\\} {\\k44}...",
+            "Dialogue: 0,1:02:03.45,1:02:08.45,Default,,0,0,0,,Mpenziwe",
+        ],
+        events
+    );
+
+    let ass = lyrics.to_ass_with_options(&AssOptions {
+        font_name: String::from("Noto Sans"),
+        font_size: 64,
+        ..AssOptions::default()
+    });
+
+    assert!(ass.contains("Style: Default,Noto Sans,64,"));
+
+    let lyrics = Lyrics::from_str(
+        r"[00:01.00]<00:01.00>Hello <00:01.50>world \[x:y\]<00:02.00>
+[00:03.00]\[Chorus\] Naku Penda Piya",
+    )
+    .unwrap();
+
+    let ass = lyrics.to_ass();
+
+    let events: Vec<&str> = ass.lines().filter(|line| line.starts_with("Dialogue:")).collect();
+
+    assert_eq!(
+        vec![
+            "Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,{\\k50}Hello {\\k50}world [x:y]",
+            "Dialogue: 0,0:00:03.00,0:00:08.00,Default,,0,0,0,,[Chorus] Naku Penda Piya",
+        ],
+        events
+    );
+}
+
+#[cfg(feature = "ttml")]
+#[test]
+fn from_ttml() {
+    let ttml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<tt xmlns="http://www.w3.org/ns/ttml" xmlns:ttm="http://www.w3.org/ns/ttml#metadata" xmlns:itunes="http://music.apple.com/lyric-ttml-internal" xml:lang="en">
+  <head>
+    <metadata>
+      <ttm:title>Let's Twist Again</ttm:title>
+    </metadata>
+  </head>
+  <body dur="1:05.000">
+    <div begin="12.000" end="1:05.000">
+      <p begin="12.000" end="15.300" itunes:key="L1">
+        <span begin="12.000" end="12.500">Naku</span> <span begin="12.500" end="13.005">Penda</span>
+        <span begin="13.005" end="14.000">Piya</span>
+      </p>
+      <p begin="00:00:15.300" end="20s">Some [more] lyrics ...</p>
+      <p begin="65000ms">Mpenziwe</p>
+    </div>
+  </body>
+</tt>"#;
+
+    let lyrics = Lyrics::from_ttml(ttml).unwrap();
+
+    assert_eq!(Some("Let's Twist Again"), lyrics.title());
+    assert_eq!(
+        "[la: en]\n[ti: Let's Twist Again]\n\n[00:12.00]<00:12.00>Naku <00:12.50>Penda \
+         <00:13.01>Piya<00:14.00>\n[00:15.30]Some \\[more\\] lyrics ...\n[01:05.00]Mpenziwe",
+        lyrics.to_string()
+    );
+
+    assert!(Lyrics::from_ttml("<tt><p begin=\"abc\">x</p></tt>").is_err());
+    assert!(Lyrics::from_ttml("<tt>").is_err());
+    assert!(Lyrics::from_ttml("<tt><p begin=\"99999999999999999:00:00.000\">x</p></tt>").is_err());
+    assert!(Lyrics::from_ttml("<tt><p begin=\"9999999999999999999h\">x</p></tt>").is_err());
+
+    let lyrics = Lyrics::from_ttml("<tt><body><p>Naku Penda Piya</p></body></tt>").unwrap();
+
+    assert_eq!(vec!["Naku Penda Piya"], lyrics.get_lines());
+}
+
+#[cfg(feature = "ttml")]
+#[test]
+fn to_ttml() {
+    let mut lyrics = Lyrics::from_str(
+        r"[ti: Twist & Shout]
+[00:12.00]<00:12.00>Naku <00:12.50>Penda <00:13.01>Piya<00:14.00>
+[00:15.30]Some \[more\] <lyrics> ...
+[00:20.00]
+[62:03.45]Mpenziwe",
+    )
+    .unwrap();
+
+    lyrics.set_tag(KnownTag::La, "sw").unwrap();
+
+    let ttml = lyrics.to_ttml();
+
+    assert_eq!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<tt xmlns="http://www.w3.org/ns/ttml" xmlns:ttm="http://www.w3.org/ns/ttml#metadata" xml:lang="sw">
+  <head>
+    <metadata>
+      <ttm:title>Twist &amp; Shout</ttm:title>
+    </metadata>
+  </head>
+  <body>
+    <div>
+      <p begin="00:00:12.000" end="00:00:14.000"><span begin="00:00:12.000" end="00:00:12.500">Naku</span> <span begin="00:00:12.500" end="00:00:13.010">Penda</span> <span begin="00:00:13.010" end="00:00:14.000">Piya</span></p>
+      <p begin="00:00:15.300" end="00:00:20.000">Some [more] &lt;lyrics&gt; ...</p>
+      <p begin="01:02:03.450" end="01:02:08.450">Mpenziwe</p>
+    </div>
+  </body>
+</tt>
+"#,
+        ttml
+    );
+
+    assert_eq!(
+        lyrics.to_string().replace("\n[00:20.00]", ""),
+        Lyrics::from_ttml(&ttml).unwrap().to_string()
+    );
+
+    assert!(lrc::formats().iter().any(|f| f.name == "TTML" && f.capabilities.end_times));
+
+    let report = lrc::convert::verify_roundtrip::<lrc::convert::Ttml>(&lyrics).unwrap();
+
+    assert!(report.dropped_timed_lines.iter().all(|(_, line)| line.is_empty()));
+    assert!(report.changed_times.is_empty());
+}
+
+#[test]
+fn qrc() {
+    use lrc::convert::{LyricsFormat, Qrc};
+
+    let qrc = "[ti:Let's Twist Again]\n[12000,2000]Naku (12000,500)Penda (12500,505)Piya \
+               [x](13005,995)\n[15300,4700](15300,4700)\n[20000,3000]Mpenziwe(20000,3000)\n";
+
+    let lyrics = Qrc::parse(qrc).unwrap();
+
+    assert_eq!(Some("Let's Twist Again"), lyrics.title());
+    assert_eq!(
+        "[ti: Let's Twist Again]\n\n[00:12.00]<00:12.00>Naku <00:12.50>Penda <00:13.01>Piya \
+         \\[x\\]<00:14.00>\n[00:15.30]<00:15.30><00:20.00>\n[00:20.00]<00:20.00>Mpenziwe<00:23.00>",
+        lyrics.to_string()
+    );
+
+    assert_eq!(
+        "[ti:Let's Twist Again]\n[12000,2000]Naku (12000,500)Penda (12500,510)Piya \
+         [x](13010,990)\n[20000,3000]Mpenziwe(20000,3000)\n",
+        Qrc::serialize(&lyrics)
+    );
+
+    let lyrics =
+        Lyrics::from_str("[00:12.00]Naku Penda Piya\n[00:15.30]Some more lyrics ...").unwrap();
+
+    assert_eq!(
+        "[12000,3300]Naku Penda Piya(12000,3300)\n[15300,5000]Some more lyrics ...(15300,5000)\n",
+        Qrc::serialize(&lyrics)
+    );
+
+    assert!(Qrc::parse("[0,0]a(9223372036854775807,5)").is_err());
+
+    let lyrics = Qrc::parse("[ti:Title]\r[12000,500]Naku(12000,500)\r").unwrap();
+
+    assert_eq!("[ti: Title]\n\n[00:12.00]<00:12.00>Naku<00:12.50>", lyrics.to_string());
+
+    assert!(lrc::formats().iter().any(|f| f.name == "QRC" && f.capabilities.word_timing));
+}
+
+#[cfg(feature = "krc")]
+#[test]
+fn krc() {
+    use lrc::convert::{Krc, LyricsFormat};
+
+    let krc = "\u{feff}[ti:Let's Twist Again]\n[offset:0]\n[12000,2000]<0,500,0>Naku \
+               <500,505,0>Penda <1005,995,0>Piya [x]\n[20000,3000]<0,3000,0>Mpenziwe\n";
+
+    let lyrics = Krc::parse_bytes(&Krc::encode(krc)).unwrap();
+
+    assert_eq!(Some("Let's Twist Again"), lyrics.title());
+    assert_eq!(
+        "[offset: 0]\n[ti: Let's Twist Again]\n\n[00:12.00]<00:12.00>Naku <00:12.50>Penda \
+         <00:13.01>Piya \\[x\\]<00:14.00>\n[00:20.00]<00:20.00>Mpenziwe<00:23.00>",
+        lyrics.to_string()
+    );
+
+    assert_eq!(
+        "[offset:0]\n[ti:Let's Twist Again]\n[12000,2000]<0,500,0>Naku <500,510,0>Penda \
+         <1010,990,0>Piya [x]\n[20000,3000]<0,3000,0>Mpenziwe\n",
+        Krc::serialize(&lyrics)
+    );
+
+    assert_eq!(
+        lyrics.to_string(),
+        Krc::parse_bytes(&Krc::serialize_bytes(&lyrics)).unwrap().to_string()
+    );
+
+    assert!(Krc::decode(b"krc1not compressed").is_err());
+    assert!(Krc::decode(b"[ti:Not KRC]").is_err());
+
+    assert!(Krc::parse("[9223372036854775807,0]<5,5,0>a").is_err());
+    assert!(Krc::parse("[0,0]<9223372036854775807,5,0>a").is_err());
+
+    assert!(lrc::formats().iter().any(|f| f.name == "KRC"));
+}
+
+#[cfg(feature = "netease")]
+#[test]
+fn netease() {
+    use lrc::{
+        convert::{LyricsFormat, Yrc},
+        NeteaseLyrics,
+    };
+
+    let yrc = "{\"t\":0,\"c\":[{\"tx\":\"Composer: \"},{\"tx\":\"Magic \
+               Len\"}]}\n[12000,2000](12000,500,0)Naku (12500,505,0)Penda (13005,995,0)Piya\n";
+
+    let lyrics = Yrc::parse(yrc).unwrap();
+
+    assert_eq!(
+        "[00:00.00]Composer: Magic Len\n[00:12.00]<00:12.00>Naku <00:12.50>Penda \
+         <00:13.01>Piya<00:14.00>",
+        lyrics.to_string()
+    );
+
+    assert_eq!(
+        "[0,12000](0,12000,0)Composer: Magic Len\n[12000,2000](12000,500,0)Naku \
+         (12500,510,0)Penda (13010,990,0)Piya\n",
+        Yrc::serialize(&lyrics)
+    );
+
+    assert!(Yrc::parse("[0,0](9223372036854775807,5,0)a").is_err());
+
+    let json = r#"{
+        "lrc": {"version": 7, "lyric": "{\"t\":0,\"c\":[{\"tx\":\"Composer: Magic Len\"}]}\n[00:12.00]Naku Penda Piya\n[00:15.30]Some more lyrics ...\n"},
+        "tlyric": {"version": 1, "lyric": "[by:Translator]\n[00:12.00]I love you\n"},
+        "romalrc": {"version": 0, "lyric": ""},
+        "yrc": {"version": 1, "lyric": "[12000,3300](12000,3300,0)Naku Penda Piya\n"},
+        "code": 200
+    }"#;
+
+    let netease = NeteaseLyrics::from_json(json).unwrap();
+
+    assert_eq!(
+        "[00:00.00]Composer: Magic Len\n[00:12.00]Naku Penda Piya\n[00:15.30]Some more lyrics ...",
+        netease.lyrics.to_string()
+    );
+    assert_eq!(
+        Some("[by: Translator]\n\n[00:12.00]I love you".to_string()),
+        netease.translation.as_ref().map(|l| l.to_string())
+    );
+    assert!(netease.romanization.is_none());
+    assert_eq!(
+        Some("[00:12.00]<00:12.00>Naku Penda Piya<00:15.30>".to_string()),
+        netease.word_timed.as_ref().map(|l| l.to_string())
+    );
+
+    let bilingual = netease.bilingual().unwrap();
+
+    assert_eq!((TimeTag::new(12000), "Naku Penda Piya", Some("I love you")), bilingual.pairs()[1]);
+
+    let netease = NeteaseLyrics::from_json(r#"{"nolyric": true, "code": 200}"#).unwrap();
+
+    assert!(netease.lyrics.get_timed_lines().is_empty());
+    assert!(netease.bilingual().is_none());
+
+    assert!(NeteaseLyrics::from_json("[]").is_err());
+    assert!(NeteaseLyrics::from_json("not json").is_err());
+
+    let netease = NeteaseLyrics::from_json(
+        r#"{"lrc": {"lyric": "[00:12.00]Naku Penda Piya\r[00:15.30]Some more lyrics ..."}}"#,
+    )
+    .unwrap();
+
+    assert_eq!(2, netease.lyrics.get_timed_lines().len());
+}
+
+#[test]
+fn uslt() {
+    let lyrics = Lyrics::from_str(
+        "[ti:Let's Twist Again]\n[00:12.00]<00:12.00>Naku <00:12.50>Penda \\[x\\]\n[00:15.30]Some \
+         more lyrics ...\nPlain line\nAnother \\[plain\\] line",
+    )
+    .unwrap();
+
+    let uslt = lyrics.to_uslt();
+
+    assert_eq!(
+        Uslt {
+            language:    "XXX".to_string(),
+            description: String::new(),
+            text:        "Plain line\nAnother [plain] line".to_string(),
+        },
+        uslt
+    );
+
+    let uslt = lyrics.to_uslt_with_options(&UsltOptions {
+        language: "eng".to_string(),
+        flatten_timed_lines: true,
+        ..UsltOptions::default()
+    });
+
+    assert_eq!("Naku Penda [x]\nSome more lyrics ...\nPlain line\nAnother [plain] line", uslt.text);
+
+    let data = uslt.to_frame_data();
+
+    assert_eq!(&[1, b'e', b'n', b'g', 0xFF, 0xFE, 0, 0, 0xFF, 0xFE, b'N', 0], &data[..12]);
+    assert_eq!(uslt, Uslt::from_frame_data(&data).unwrap());
+
+    let uslt = Uslt::from_frame_data(b"\x03engDescription\x00One\n[Two]\n\x00").unwrap();
+
+    assert_eq!("eng", uslt.language);
+    assert_eq!("Description", uslt.description);
+    assert_eq!("One\n[Two]\n", uslt.text);
+
+    let uslt = Uslt::from_frame_data(b"\x00XXX\x00Caf\xe9").unwrap();
+
+    assert_eq!("Café", uslt.text);
+
+    let uslt = Uslt::from_frame_data(b"\x02XXX\x00\x00\x00O\x00K").unwrap();
+
+    assert_eq!("OK", uslt.text);
+
+    assert!(Uslt::from_frame_data(b"\x03en").is_err());
+    assert!(Uslt::from_frame_data(b"\x09XXX\x00text").is_err());
+
+    let lyrics =
+        Lyrics::from_uslt(&Uslt::from_frame_data(b"\x03engDescription\x00One\n[Two]\n").unwrap())
+            .unwrap();
+
+    assert_eq!(&["One".to_string(), "\\[Two\\]".to_string()], lyrics.get_lines());
+    assert!(lyrics.get_timed_lines().is_empty());
+}
+
+#[cfg(feature = "lofty")]
+#[test]
+fn embedded() {
+    use lrc::lofty::{
+        prelude::ItemKey,
+        tag::{Tag, TagType},
+    };
+
+    let lyrics = Lyrics::from_str(
+        "[ti:Let's Twist Again]\n[00:12.00]Naku Penda Piya\n[00:15.30]Some more lyrics ...",
+    )
+    .unwrap();
+
+    let mut tag = Tag::new(TagType::VorbisComments);
+
+    assert!(Lyrics::from_tag(&tag).unwrap().is_none());
+
+    assert!(lyrics.write_to_tag(&mut tag));
+    assert_eq!(Some(lyrics.to_string().as_str()), tag.get_string(ItemKey::Lyrics));
+    assert_eq!(lyrics.to_string(), Lyrics::from_tag(&tag).unwrap().unwrap().to_string());
+
+    let mut tag = Tag::new(TagType::Id3v2);
+
+    assert!(lyrics.write_to_tag(&mut tag));
+    assert_eq!(None, tag.get_string(ItemKey::Lyrics));
+    assert_eq!(Some(lyrics.to_string().as_str()), tag.get_string(ItemKey::UnsyncLyrics));
+    assert_eq!(lyrics.to_string(), Lyrics::from_tag(&tag).unwrap().unwrap().to_string());
+
+    let mut tag = Tag::new(TagType::Mp4Ilst);
+
+    tag.insert_text(ItemKey::UnsyncLyrics, "Plain line".to_string());
+
+    assert_eq!(&["Plain line".to_string()], Lyrics::from_tag(&tag).unwrap().unwrap().get_lines());
+
+    assert!(matches!(
+        Lyrics::from_tagged_file("/path/to/nothing.flac"),
+        Err(LyricsError::FileError(..))
+    ));
+}
+
+#[test]
+fn to_plain_text() {
+    let lyrics = Lyrics::from_str(
+        "[ti:Let's Twist Again]\n[00:15.30][01:15.30]<00:15.30>Some <00:16.00>more \
+         \\[x\\]\n[00:12.00]Naku Penda Piya\n[00:14.00]\n[00:20.00]  \nPlain line\n# a comment",
+    )
+    .unwrap();
+
+    assert_eq!(
+        "Naku Penda Piya\nSome more [x]\nSome more [x]\nPlain line\n# a comment",
+        lyrics.to_plain_text()
+    );
+
+    assert_eq!("", Lyrics::new().to_plain_text());
+}
+
+#[test]
+fn from_plain_text() {
+    let text = "Naku Penda Piya\n\n  Some more lyrics ...  \n[x]\n";
+
+    let lyrics = Lyrics::from_plain_text(text, Timestamp::new(60000), Distribution::Even).unwrap();
+
+    assert_eq!(
+        "[length: 01:00]\n\n[00:00.00]Naku Penda Piya\n[00:20.00]Some more lyrics \
+         ...\n[00:40.00]\\[x\\]",
+        lyrics.to_string()
+    );
+
+    let lyrics =
+        Lyrics::from_plain_text(text, Timestamp::new(10000), Distribution::WeightedByLength)
+            .unwrap();
+
+    assert_eq!(
+        "[length: 00:10]\n\n[00:00.00]Naku Penda Piya\n[00:03.94]Some more lyrics \
+         ...\n[00:09.21]\\[x\\]",
+        lyrics.to_string()
+    );
+
+    let lyrics = Lyrics::from_plain_text("", Timestamp::new(10000), Distribution::Even).unwrap();
+
+    assert!(lyrics.get_timed_lines().is_empty());
+
+    assert!(Lyrics::from_plain_text(text, Timestamp::new(-1), Distribution::Even).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    let mut lyrics = Lyrics::new();
+
+    lyrics.set_title("Let's Twist Again").unwrap();
+    lyrics.add_comment("Some comment").unwrap();
+    lyrics.add_timed_line(TimeTag::new(12000), "Naku Penda Piya").unwrap();
+    lyrics.add_timed_line(TimeTag::new(15300), "Some more \\[x\\]").unwrap();
+    lyrics.add_line("Plain line").unwrap();
+
+    let json = serde_json::to_string(&lyrics).unwrap();
+
+    assert_eq!(
+        r#"{"metadata":[{"label":"ti","text":"Let's Twist Again"}],"comments":["Some comment"],"timed_lines":[[12000,"Naku Penda Piya"],[15300,"Some more \\[x\\]"]],"lines":["Plain line"]}"#,
+        json
+    );
+
+    let deserialized: Lyrics = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(lyrics.to_string(), deserialized.to_string());
+
+    assert_eq!("12340", serde_json::to_string(&TimeTag::new(12340)).unwrap());
+    assert_eq!(Timestamp::new(-500), serde_json::from_str::<Timestamp>("-500").unwrap());
+    assert_eq!(
+        IDTag::from_string("ar", "Magic Len").unwrap(),
+        serde_json::from_str::<IDTag>(r#"{"label":"ar","text":"Magic Len"}"#).unwrap()
+    );
+
+    assert!(serde_json::from_str::<IDTag>(r#"{"label":"a:r","text":"Magic Len"}"#).is_err());
+    assert!(serde_json::from_str::<Lyrics>(r#"{"timed_lines":[[0,"[ar:tag]"]]}"#).is_err());
+
+    let lyrics: Lyrics = serde_json::from_str(r#"{"lines":["Only plain"]}"#).unwrap();
+
+    assert_eq!(&["Only plain".to_string()], lyrics.get_lines());
+}
+
+#[cfg(feature = "rkyv")]
+#[test]
+fn archive() {
+    let mut lyrics = Lyrics::new();
+
+    lyrics.set_title("Let's Twist Again").unwrap();
+    lyrics.add_comment("Some comment").unwrap();
+    lyrics.add_timed_line(TimeTag::new(15300), "Some more \\[x\\]").unwrap();
+    lyrics.add_timed_line(TimeTag::new(12000), "Naku Penda Piya").unwrap();
+    lyrics.add_line("Plain line").unwrap();
+
+    let bytes = lyrics.to_archive();
+
+    let archived = Lyrics::access_archive(&bytes).unwrap();
+
+    assert_eq!(2, archived.timed_lines.len());
+    assert_eq!(12000, archived.timed_lines[0].timestamp.to_native());
+    assert_eq!("Naku Penda Piya", archived.timed_lines[0].text.as_str());
+    assert_eq!("ti", archived.metadata[0].label.as_str());
+    assert_eq!("Plain line", archived.lines[0].as_str());
+
+    assert_eq!(lyrics.to_string(), archived.to_lyrics().unwrap().to_string());
+
+    let mut unaligned = vec![0];
+
+    unaligned.extend_from_slice(&bytes);
+
+    assert_eq!(lyrics.to_string(), Lyrics::from_archive(&unaligned[1..]).unwrap().to_string());
+
+    assert!(Lyrics::from_archive(b"not an archive").is_err());
+}
+
+#[test]
+fn csv() {
+    let lyrics = Lyrics::from_str(
+        "[ti:Let's Twist Again]\n[00:12.00]Naku, Penda \"Piya\"\n[00:15.30]Some more \
+         \\[x\\]\n[00:20.00]",
+    )
+    .unwrap();
+
+    let csv = lyrics.to_csv();
+
+    assert_eq!(
+        "timestamp_ms,text\r\n12000,\"Naku, Penda \"\"Piya\"\"\"\r\n15300,Some more \
+         [x]\r\n20000,\r\n",
+        csv
+    );
+
+    let imported = Lyrics::from_csv(&csv).unwrap();
+
+    assert_eq!(lyrics.get_timed_lines(), imported.get_timed_lines());
+
+    let imported = Lyrics::from_csv("\u{feff}15300,Corrected\n12000,\"Naku, Penda\"").unwrap();
+
+    assert_eq!("[00:12.00]Naku, Penda\n[00:15.30]Corrected", imported.to_string());
+
+    assert!(Lyrics::from_csv("12000,a,b").is_err());
+    assert!(Lyrics::from_csv("twelve,a").is_err());
+    assert!(Lyrics::from_csv("12000,\"a").is_err());
+    assert!(Lyrics::from_csv("12000,\"multi\nline\"").is_err());
+
+    let lyrics =
+        Lyrics::from_str("[00:01.00]=1+1\n[00:02.00]- Chorus -\n[00:03.00]'@x\n[00:04.00]It's")
+            .unwrap();
+
+    let csv = lyrics.to_csv();
+
+    assert_eq!(
+        "timestamp_ms,text\r\n1000,\"'=1+1\"\r\n2000,\"'- Chorus \
+         -\"\r\n3000,\"''@x\"\r\n4000,It's\r\n",
+        csv
+    );
+    assert_eq!(lyrics.get_timed_lines(), Lyrics::from_csv(&csv).unwrap().get_timed_lines());
+}
+
+#[test]
+fn to_markdown() {
+    let mut lyrics = Lyrics::from_str(
+        "[ti:Let's Twist Again]\n[ar:Chubby *Checker*]\n[x-source:web]\n[00:12.00]<00:12.00>Naku \
+         <00:12.50>Penda | Piya\n[00:14.00]\nPlain line\nAnother one",
+    )
+    .unwrap();
+
+    lyrics.add_timed_line(TimeTag::new(3675305), "Some more \\[x\\]").unwrap();
+
+    assert_eq!(
+        "# Let's Twist Again\n\n- **Artist:** Chubby \\*Checker\\*\n- **x-source:** web\n\n| Time \
+         | Lyrics |\n| ---: | :--- |\n| 0:12.00 | Naku Penda \\| Piya |\n| 1:01:15.31 | Some more \
+         \\[x\\] |\n\nPlain line  \nAnother one\n",
+        lyrics.to_markdown()
+    );
+
+    let lyrics = Lyrics::from_str("[00:01.00]Only one").unwrap();
+
+    assert_eq!(
+        "| Time | Lyrics |\n| ---: | :--- |\n| 0:01.00 | Only one |\n",
+        lyrics.to_markdown()
+    );
+
+    assert_eq!("", Lyrics::new().to_markdown());
+}
+
+#[test]
+fn time_arithmetic() {
+    let a = Timestamp::new(12000);
+    let b = Timestamp::new(3300);
+
+    assert_eq!(Timestamp::new(15300), a + b);
+    assert_eq!(Timestamp::new(8700), a - b);
+    assert_eq!(Timestamp::new(-8700), b - a);
+    assert_eq!(Timestamp::new(-12000), -a);
+    assert_eq!(Timestamp::new(12500), a + 500);
+    assert_eq!(Timestamp::new(11500), a - 500);
+
+    let mut c = a;
+
+    c += b;
+    c -= 300;
+
+    assert_eq!(Timestamp::new(15000), c);
+
+    c -= b;
+    c += 100;
+
+    assert_eq!(Timestamp::new(11800), c);
+
+    let t = TimeTag::new(12000);
+
+    assert_eq!(TimeTag::new(15300), t + b);
+    assert_eq!(TimeTag::new(8700), t - b);
+    assert_eq!(TimeTag::new(12500), t + 500);
+    assert_eq!(TimeTag::new(11500), t - 500);
+    assert_eq!(Timestamp::new(3300), TimeTag::new(15300) - t);
+    assert_eq!(Timestamp::new(-3300), t - TimeTag::new(15300));
+
+    let mut u = t;
+
+    u += b;
+    u -= 300;
+
+    assert_eq!(TimeTag::new(15000), u);
+
+    u -= b;
+    u += 100;
+
+    assert_eq!(TimeTag::new(11800), u);
+    assert!(t + b > t);
+}
+
+#[test]
+fn duration_conversions() {
+    use std::time::Duration;
+
+    assert_eq!(Timestamp::new(12345), Timestamp::from_duration(Duration::from_micros(12_345_678)));
+    assert_eq!(Timestamp::new(i64::MAX), Timestamp::from_duration(Duration::MAX));
+    assert_eq!(Timestamp::new(1500), Timestamp::from(Duration::from_millis(1500)));
+
+    assert_eq!(Ok(Duration::from_millis(12340)), Duration::try_from(Timestamp::new(12340)));
+    assert!(Duration::try_from(Timestamp::new(-1)).is_err());
+    assert_eq!(Ok(Duration::from_millis(12340)), Duration::try_from(TimeTag::new(12340)));
+    assert!(Duration::try_from(TimeTag::new(-10)).is_err());
+
+    let t = TimeTag::new(12000);
+
+    assert_eq!(TimeTag::new(13500), t + Duration::from_millis(1500));
+    assert_eq!(TimeTag::new(10500), t - Duration::from_millis(1500));
+    assert_eq!(Timestamp::new(13500), Timestamp::new(12000) + Duration::from_millis(1500));
+    assert_eq!(Timestamp::new(10500), Timestamp::new(12000) - Duration::from_millis(1500));
+
+    let mut u = t;
+
+    u += Duration::from_secs(2);
+    u -= Duration::from_millis(500);
+
+    assert_eq!(TimeTag::new(13500), u);
+
+    let mut v = Timestamp::new(0);
+
+    v += Duration::from_secs(1);
+    v -= Duration::from_millis(250);
+
+    assert_eq!(Timestamp::new(750), v);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_conversions() {
+    use lrc::chrono::Duration;
+
+    assert_eq!(Duration::milliseconds(-12340), Duration::from(Timestamp::new(-12340)));
+    assert_eq!(Duration::MIN, Duration::from(Timestamp::new(i64::MIN)));
+    assert_eq!(Timestamp::new(12345), Timestamp::from(Duration::microseconds(12_345_678)));
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_conversions() {
+    use time::Duration;
+
+    assert_eq!(Duration::milliseconds(-12340), Duration::from(Timestamp::new(-12340)));
+    assert_eq!(Timestamp::new(12345), Timestamp::from(Duration::microseconds(12_345_678)));
+    assert_eq!(Timestamp::new(i64::MAX), Timestamp::from(Duration::MAX));
+}
+
+#[test]
+fn timestamp_components() {
+    let t = Timestamp::from_components(1, 2, 34).unwrap();
+
+    assert_eq!(Timestamp::new(62340), t);
+    assert_eq!(Timestamp::from_str("01:02.34").unwrap(), t);
+
+    assert!(Timestamp::from_components(1, 60, 0).is_err());
+    assert!(Timestamp::from_components(1, 0, 100).is_err());
+
+    let t = Timestamp::new(7_384_567);
+
+    assert!(!t.is_negative());
+    assert_eq!(123, t.minutes());
+    assert_eq!(4, t.seconds());
+    assert_eq!(56, t.hundredths());
+    assert_eq!(567, t.subsec_millis());
+
+    let t = Timestamp::new(-62345);
+
+    assert!(t.is_negative());
+    assert_eq!(1, t.minutes());
+    assert_eq!(2, t.seconds());
+    assert_eq!(34, t.hundredths());
+    assert_eq!(345, t.subsec_millis());
+}
+
+#[test]
+fn checked_time_arithmetic() {
+    let max = Timestamp::new(i64::MAX);
+    let min = Timestamp::new(i64::MIN);
+    let one = Timestamp::new(1);
+
+    assert_eq!(Some(Timestamp::new(3)), Timestamp::new(2).checked_add(one));
+    assert_eq!(None, max.checked_add(one));
+    assert_eq!(Some(Timestamp::new(1)), Timestamp::new(2).checked_sub(one));
+    assert_eq!(None, min.checked_sub(one));
+    assert_eq!(max, max.saturating_add(one));
+    assert_eq!(min, min.saturating_sub(one));
+    assert_eq!(Timestamp::new(3), Timestamp::new(2).saturating_add(one));
+
+    let t = TimeTag::new(i64::MAX - 1);
+
+    assert_eq!(Some(TimeTag::new(i64::MAX)), t.checked_add(one));
+    assert_eq!(None, t.checked_add(Timestamp::new(2)));
+    assert_eq!(None, TimeTag::new(i64::MIN).checked_sub(one));
+    assert_eq!(Some(TimeTag::new(0)), TimeTag::new(1).checked_sub(one));
+    assert_eq!(TimeTag::new(i64::MAX), t.saturating_add(Timestamp::new(10)));
+    assert_eq!(
+        TimeTag::new(i64::MIN),
+        TimeTag::new(i64::MIN + 1).saturating_sub(Timestamp::new(10))
+    );
+
+    let mut lyrics = Lyrics::new();
+
+    lyrics.add_timed_line(TimeTag::new(i64::MAX - 5), "End").unwrap();
+    lyrics.shift_time(10);
+
+    assert_eq!(TimeTag::new(i64::MAX), lyrics.get_timed_lines()[0].0);
+}
+
+#[test]
+fn duration_until() {
+    let a = TimeTag::new(12000);
+    let b = TimeTag::new(15300);
+
+    assert_eq!(Timestamp::new(3300), a.duration_until(b));
+    assert_eq!(Timestamp::new(-3300), b.duration_until(a));
+    assert_eq!(Timestamp::new(0), a.duration_until(a));
+    assert_eq!(b - a, a.duration_until(b));
 }